@@ -48,11 +48,12 @@ impl<N: Network> Process<N> {
         // Ensure the fee has the correct function.
         let function_name = Identifier::from_str("fee_private")?;
 
-        // Ensure the record contains a sufficient balance to pay the fee.
-        ensure_record_microcredits_is_sufficient(
-            &credits,
-            base_fee_in_microcredits.saturating_add(priority_fee_in_microcredits),
-        )?;
+        // Ensure the record is a credits record, and contains a sufficient balance to pay the fee.
+        let balance = self.get_stack(program_id)?.matches_credits_record(&credits)?;
+        ensure!(
+            balance >= base_fee_in_microcredits.saturating_add(priority_fee_in_microcredits),
+            "Credits record balance is insufficient to pay the fee"
+        );
 
         // Construct the inputs.
         let inputs = [
@@ -106,18 +107,3 @@ impl<N: Network> Process<N> {
         Ok(authorization)
     }
 }
-
-/// Ensures the record contains a sufficient balance to pay the fee.
-fn ensure_record_microcredits_is_sufficient<N: Network>(
-    record: &Record<N, Plaintext<N>>,
-    fee_in_microcredits: u64,
-) -> Result<()> {
-    // Retrieve the balance from the record.
-    let balance = match record.find(&[Identifier::from_str("microcredits")?]) {
-        Ok(console::program::Entry::Private(Plaintext::Literal(Literal::U64(amount), _))) => *amount,
-        _ => bail!("The fee record does not contain a 'microcredits' entry"),
-    };
-    // Ensure the balance is sufficient to pay the fee.
-    ensure!(balance >= fee_in_microcredits, "Credits record balance is insufficient to pay the fee");
-    Ok(())
-}
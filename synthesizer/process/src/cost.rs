@@ -0,0 +1,122 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::{
+    network::prelude::*,
+    program::{LiteralType, PlaintextType},
+};
+use synthesizer_program::{Command, Instruction};
+
+/// Returns the cost in microcredits of the given finalize command.
+pub fn cost_per_command<N: Network>(command: &Command<N>) -> Result<u64> {
+    match command {
+        Command::Instruction(Instruction::Abs(_)) => Ok(2_000),
+        Command::Instruction(Instruction::AbsWrapped(_)) => Ok(2_000),
+        Command::Instruction(Instruction::Add(_)) => Ok(2_000),
+        Command::Instruction(Instruction::AddWrapped(_)) => Ok(2_000),
+        Command::Instruction(Instruction::And(_)) => Ok(2_000),
+        Command::Instruction(Instruction::AssertEq(_)) => Ok(2_000),
+        Command::Instruction(Instruction::AssertNeq(_)) => Ok(2_000),
+        Command::Instruction(Instruction::Async(_)) => bail!("`async` is not supported in finalize."),
+        Command::Instruction(Instruction::Call(_)) => bail!("`call` is not supported in finalize."),
+        Command::Instruction(Instruction::Cast(_)) => Ok(2_000),
+        Command::Instruction(Instruction::CastLossy(_)) => Ok(2_000),
+        Command::Instruction(Instruction::CommitBHP256(_)) => Ok(200_000),
+        Command::Instruction(Instruction::CommitBHP512(_)) => Ok(200_000),
+        Command::Instruction(Instruction::CommitBHP768(_)) => Ok(200_000),
+        Command::Instruction(Instruction::CommitBHP1024(_)) => Ok(200_000),
+        Command::Instruction(Instruction::CommitPED64(_)) => Ok(100_000),
+        Command::Instruction(Instruction::CommitPED128(_)) => Ok(100_000),
+        Command::Instruction(Instruction::Div(_)) => Ok(10_000),
+        Command::Instruction(Instruction::DivWrapped(_)) => Ok(2_000),
+        Command::Instruction(Instruction::Double(_)) => Ok(2_000),
+        Command::Instruction(Instruction::GreaterThan(_)) => Ok(2_000),
+        Command::Instruction(Instruction::GreaterThanOrEqual(_)) => Ok(2_000),
+        Command::Instruction(Instruction::HashBHP256(_)) => Ok(100_000),
+        Command::Instruction(Instruction::HashBHP512(_)) => Ok(100_000),
+        Command::Instruction(Instruction::HashBHP768(_)) => Ok(100_000),
+        Command::Instruction(Instruction::HashBHP1024(_)) => Ok(100_000),
+        Command::Instruction(Instruction::HashKeccak256(_)) => Ok(100_000),
+        Command::Instruction(Instruction::HashKeccak384(_)) => Ok(100_000),
+        Command::Instruction(Instruction::HashKeccak512(_)) => Ok(100_000),
+        Command::Instruction(Instruction::HashPED64(_)) => Ok(20_000),
+        Command::Instruction(Instruction::HashPED128(_)) => Ok(30_000),
+        Command::Instruction(Instruction::HashPSD2(hash)) => match hash.destination_type() {
+            PlaintextType::Literal(LiteralType::Address) | PlaintextType::Literal(LiteralType::Group) => Ok(600_000),
+            PlaintextType::Literal(..) => Ok(60_000),
+            plaintext_type => bail!("`hash.psd2` is not supported for plaintext type '{plaintext_type}'"),
+        },
+        Command::Instruction(Instruction::HashPSD4(hash)) => match hash.destination_type() {
+            PlaintextType::Literal(LiteralType::Address) | PlaintextType::Literal(LiteralType::Group) => Ok(700_000),
+            PlaintextType::Literal(..) => Ok(100_000),
+            plaintext_type => bail!("`hash.psd4` is not supported for plaintext type '{plaintext_type}'"),
+        },
+        Command::Instruction(Instruction::HashPSD8(hash)) => match hash.destination_type() {
+            PlaintextType::Literal(LiteralType::Address) | PlaintextType::Literal(LiteralType::Group) => Ok(800_000),
+            PlaintextType::Literal(..) => Ok(200_000),
+            plaintext_type => bail!("`hash.psd8` is not supported for plaintext type '{plaintext_type}'"),
+        },
+        Command::Instruction(Instruction::HashSha3_256(_)) => Ok(100_000),
+        Command::Instruction(Instruction::HashSha3_384(_)) => Ok(100_000),
+        Command::Instruction(Instruction::HashSha3_512(_)) => Ok(100_000),
+        Command::Instruction(Instruction::HashManyPSD2(_)) => {
+            bail!("`hash_many.psd2` is not supported in finalize.")
+        }
+        Command::Instruction(Instruction::HashManyPSD4(_)) => {
+            bail!("`hash_many.psd4` is not supported in finalize.")
+        }
+        Command::Instruction(Instruction::HashManyPSD8(_)) => {
+            bail!("`hash_many.psd8` is not supported in finalize.")
+        }
+        Command::Instruction(Instruction::Inv(_)) => Ok(10_000),
+        Command::Instruction(Instruction::IsEq(_)) => Ok(2_000),
+        Command::Instruction(Instruction::IsNeq(_)) => Ok(2_000),
+        Command::Instruction(Instruction::LessThan(_)) => Ok(2_000),
+        Command::Instruction(Instruction::LessThanOrEqual(_)) => Ok(2_000),
+        Command::Instruction(Instruction::Modulo(_)) => Ok(2_000),
+        Command::Instruction(Instruction::Mul(_)) => Ok(150_000),
+        Command::Instruction(Instruction::MulWrapped(_)) => Ok(2_000),
+        Command::Instruction(Instruction::Nand(_)) => Ok(2_000),
+        Command::Instruction(Instruction::Neg(_)) => Ok(2_000),
+        Command::Instruction(Instruction::Nor(_)) => Ok(2_000),
+        Command::Instruction(Instruction::Not(_)) => Ok(2_000),
+        Command::Instruction(Instruction::Or(_)) => Ok(2_000),
+        Command::Instruction(Instruction::Pow(_)) => Ok(20_000),
+        Command::Instruction(Instruction::PowWrapped(_)) => Ok(2_000),
+        Command::Instruction(Instruction::Rem(_)) => Ok(2_000),
+        Command::Instruction(Instruction::RemWrapped(_)) => Ok(2_000),
+        Command::Instruction(Instruction::SignVerify(_)) => Ok(250_000),
+        Command::Instruction(Instruction::Shl(_)) => Ok(2_000),
+        Command::Instruction(Instruction::ShlWrapped(_)) => Ok(2_000),
+        Command::Instruction(Instruction::Shr(_)) => Ok(2_000),
+        Command::Instruction(Instruction::ShrWrapped(_)) => Ok(2_000),
+        Command::Instruction(Instruction::Square(_)) => Ok(2_000),
+        Command::Instruction(Instruction::SquareRoot(_)) => Ok(120_000),
+        Command::Instruction(Instruction::Sub(_)) => Ok(10_000),
+        Command::Instruction(Instruction::SubWrapped(_)) => Ok(2_000),
+        Command::Instruction(Instruction::Ternary(_)) => Ok(2_000),
+        Command::Instruction(Instruction::Xor(_)) => Ok(2_000),
+        // TODO: The following 'finalize' commands are currently priced higher than expected.
+        //  Expect these numbers to change as their usage is stabilized.
+        Command::Await(_) => Ok(2_000),
+        Command::Contains(_) => Ok(12_500),
+        Command::Get(_) => Ok(25_000),
+        Command::GetOrUse(_) => Ok(25_000),
+        Command::RandChaCha(_) => Ok(25_000),
+        Command::Remove(_) => Ok(10_000),
+        Command::Set(_) => Ok(100_000),
+        Command::BranchEq(_) | Command::BranchNeq(_) => Ok(5_000),
+        Command::Position(_) => Ok(1_000),
+    }
+}
@@ -18,6 +18,17 @@ impl<N: Network> Process<N> {
     /// Evaluates a program function on the given request.
     #[inline]
     pub fn evaluate<A: circuit::Aleo<Network = N>>(&self, authorization: Authorization<N>) -> Result<Response<N>> {
+        self.evaluate_with_options::<A>(authorization, &ExecutionOptions::default())
+    }
+
+    /// Evaluates a program function on the given request, with the given options.
+    /// The options are applied to the registers of the function and of every nested call.
+    #[inline]
+    pub fn evaluate_with_options<A: circuit::Aleo<Network = N>>(
+        &self,
+        authorization: Authorization<N>,
+        options: &ExecutionOptions<N>,
+    ) -> Result<Response<N>> {
         let timer = timer!("Process::evaluate");
 
         // Retrieve the top-level request (without popping it).
@@ -29,7 +40,7 @@ impl<N: Network> Process<N> {
         // Retrieve the stack.
         let stack = self.get_stack(request.program_id())?;
         // Evaluate the function.
-        let response = stack.evaluate_function::<A>(CallStack::evaluate(authorization)?, None);
+        let response = stack.evaluate_function::<A>(CallStack::evaluate(authorization)?, options, None);
         lap!(timer, "Evaluate the function");
 
         finish!(timer);
@@ -21,6 +21,18 @@ impl<N: Network> Process<N> {
         &self,
         authorization: Authorization<N>,
         rng: &mut R,
+    ) -> Result<(Response<N>, Trace<N>)> {
+        self.execute_with_options::<A, R>(authorization, &ExecutionOptions::default(), rng)
+    }
+
+    /// Executes the given authorization, with the given options.
+    /// The options are applied to the registers of the function and of every nested call.
+    #[inline]
+    pub fn execute_with_options<A: circuit::Aleo<Network = N>, R: CryptoRng + Rng>(
+        &self,
+        authorization: Authorization<N>,
+        options: &ExecutionOptions<N>,
+        rng: &mut R,
     ) -> Result<(Response<N>, Trace<N>)> {
         let timer = timer!("Process::execute");
 
@@ -41,7 +53,7 @@ impl<N: Network> Process<N> {
         // Retrieve the stack.
         let stack = self.get_stack(request.program_id())?;
         // Execute the circuit.
-        let response = stack.execute_function::<A, R>(call_stack, None, rng)?;
+        let response = stack.execute_function::<A, R>(call_stack, options, None, rng)?;
         lap!(timer, "Execute the function");
 
         // Extract the trace.
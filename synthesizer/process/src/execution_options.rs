@@ -0,0 +1,211 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{CallInterceptor, ExecutionTrace};
+use console::{
+    network::prelude::*,
+    program::{Identifier, Locator, Plaintext, ProgramID},
+    types::Field,
+};
+use synthesizer_program::RecordObserver;
+
+use core::marker::PhantomData;
+use indexmap::IndexMap;
+use parking_lot::Mutex;
+use std::{collections::BTreeSet, sync::Arc};
+
+/// The sink for the traces of the evaluated closures and functions, each with the locator of its closure or function.
+pub type TraceSink<N> = Arc<Mutex<Vec<(Locator<N>, ExecutionTrace<N>)>>>;
+
+/// A snapshot of the mappings, which the finalize scopes read and write in place of the finalize store.
+/// The clones of a snapshot share its entries, so the writes of each finalize scope are visible to the later scopes,
+/// and to the holder of the snapshot once the finalization completes.
+#[derive(Clone)]
+pub struct MappingSnapshot<N: Network> {
+    /// The value of each key, keyed by the program ID, the mapping name, and the bits of the key,
+    /// as plaintexts are not hashable.
+    entries: Arc<Mutex<IndexMap<(ProgramID<N>, Identifier<N>, Vec<bool>), Plaintext<N>>>>,
+}
+
+impl<N: Network> MappingSnapshot<N> {
+    /// Initializes a new snapshot, where each entry is a program ID, a mapping name, a key, and its value.
+    /// The snapshot is complete, so a key that is not in the entries does not exist in its mapping.
+    pub fn new(entries: impl IntoIterator<Item = (ProgramID<N>, Identifier<N>, Plaintext<N>, Plaintext<N>)>) -> Self {
+        let entries = entries
+            .into_iter()
+            .map(|(program_id, mapping_name, key, value)| ((program_id, mapping_name, key.to_bits_le()), value))
+            .collect();
+        Self { entries: Arc::new(Mutex::new(entries)) }
+    }
+
+    /// Returns the value of the given key in the given mapping, if it exists.
+    pub fn get(
+        &self,
+        program_id: &ProgramID<N>,
+        mapping_name: &Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Option<Plaintext<N>> {
+        self.entries.lock().get(&(*program_id, *mapping_name, key.to_bits_le())).cloned()
+    }
+
+    /// Sets the value of the given key in the given mapping.
+    pub fn insert(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        key: &Plaintext<N>,
+        value: Plaintext<N>,
+    ) {
+        self.entries.lock().insert((program_id, mapping_name, key.to_bits_le()), value);
+    }
+
+    /// Removes the given key from the given mapping, if it exists.
+    pub fn remove(&self, program_id: ProgramID<N>, mapping_name: Identifier<N>, key: &Plaintext<N>) {
+        self.entries.lock().shift_remove(&(program_id, mapping_name, key.to_bits_le()));
+    }
+}
+
+/// The options for an evaluation, execution, or finalization.
+///
+/// The options are applied to the registers of every closure, function, and finalize scope that is run,
+/// including those of nested calls and awaited futures.
+#[derive(Clone)]
+pub struct ExecutionOptions<N: Network> {
+    /// The number of loads and stores allowed in each set of registers, if a step budget is set.
+    step_budget: Option<u64>,
+    /// The observer for stored records, if one is set, which is shared by the registers of every scope.
+    record_observer: Option<Arc<Mutex<RecordObserver<N>>>>,
+    /// The programs that external calls are restricted to, if an allowlist is set.
+    call_allowlist: Option<BTreeSet<ProgramID<N>>>,
+    /// The maximum depth of nested calls, if a limit is set.
+    max_call_depth: Option<usize>,
+    /// The interceptor for calls, if one is set, which is shared by the registers of every scope.
+    call_interceptor: Option<Arc<Mutex<CallInterceptor<N>>>>,
+    /// The sink for the traces of the evaluated closures and functions, if one is set.
+    trace_sink: Option<TraceSink<N>>,
+    /// The seed for the `rand` commands of every finalize scope, if one is set in place of the seed from the global state.
+    randomness_seed: Option<Field<N>>,
+    /// The snapshot of the mappings, if one is set in place of the finalize store.
+    mapping_snapshot: Option<MappingSnapshot<N>>,
+    /// PhantomData.
+    _phantom: PhantomData<N>,
+}
+
+impl<N: Network> Default for ExecutionOptions<N> {
+    /// Returns the default options, which do not restrict or observe the registers.
+    fn default() -> Self {
+        Self {
+            step_budget: None,
+            record_observer: None,
+            call_allowlist: None,
+            max_call_depth: None,
+            call_interceptor: None,
+            trace_sink: None,
+            randomness_seed: None,
+            mapping_snapshot: None,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<N: Network> ExecutionOptions<N> {
+    /// Returns the number of loads and stores allowed in each set of registers, if a step budget is set.
+    pub const fn step_budget(&self) -> Option<u64> {
+        self.step_budget
+    }
+
+    /// Sets the step budget, which is the number of loads and stores allowed in each set of registers before it halts.
+    pub fn set_step_budget(&mut self, steps: u64) {
+        self.step_budget = Some(steps);
+    }
+
+    /// Returns the observer for stored records, if one is set.
+    pub const fn record_observer(&self) -> Option<&Arc<Mutex<RecordObserver<N>>>> {
+        self.record_observer.as_ref()
+    }
+
+    /// Sets the observer that is invoked with every record stored in the registers of a function or closure.
+    pub fn set_record_observer(&mut self, observer: RecordObserver<N>) {
+        self.record_observer = Some(Arc::new(Mutex::new(observer)));
+    }
+
+    /// Returns the programs that external calls are restricted to, if an allowlist is set.
+    pub const fn call_allowlist(&self) -> Option<&BTreeSet<ProgramID<N>>> {
+        self.call_allowlist.as_ref()
+    }
+
+    /// Restricts the external calls, including those of nested calls, to the given set of programs.
+    pub fn set_call_allowlist(&mut self, allowed: BTreeSet<ProgramID<N>>) {
+        self.call_allowlist = Some(allowed);
+    }
+
+    /// Returns the maximum depth of nested calls, if a limit is set.
+    pub const fn max_call_depth(&self) -> Option<usize> {
+        self.max_call_depth
+    }
+
+    /// Sets the maximum depth of nested calls, including the call itself, which is checked before every call.
+    pub fn set_max_call_depth(&mut self, max: usize) {
+        self.max_call_depth = Some(max);
+    }
+
+    /// Returns the interceptor for calls, if one is set.
+    pub const fn call_interceptor(&self) -> Option<&Arc<Mutex<CallInterceptor<N>>>> {
+        self.call_interceptor.as_ref()
+    }
+
+    /// Sets the interceptor that is consulted before every call, including nested calls, once the call is permitted.
+    /// When evaluating, if the interceptor returns outputs, the callee is not evaluated and the outputs are used instead.
+    /// When executing, a call that the interceptor would intercept halts, as the callee must be executed.
+    pub fn set_call_interceptor(&mut self, interceptor: CallInterceptor<N>) {
+        self.call_interceptor = Some(Arc::new(Mutex::new(interceptor)));
+    }
+
+    /// Returns the sink for the traces of the evaluated closures and functions, if one is set.
+    pub const fn trace_sink(&self) -> Option<&TraceSink<N>> {
+        self.trace_sink.as_ref()
+    }
+
+    /// Sets the sink for the traces of the evaluated closures and functions. When set, the registers of every
+    /// closure and function that is evaluated record their trace, and add it to the sink once it completes.
+    /// As a caller completes after its nested calls, the trace of each callee is added before that of its caller.
+    ///
+    /// Note that traces are only recorded when evaluating, and not when executing.
+    pub fn set_trace_sink(&mut self, sink: TraceSink<N>) {
+        self.trace_sink = Some(sink);
+    }
+
+    /// Returns the seed for the `rand` commands, if one is set in place of the seed from the global state.
+    pub const fn randomness_seed(&self) -> Option<Field<N>> {
+        self.randomness_seed
+    }
+
+    /// Sets the seed for the `rand` commands of every finalize scope, in place of the random seed from the global state.
+    /// This allows an execution to be finalized with deterministic randomness, such as in tests.
+    pub fn set_randomness_seed(&mut self, seed: Field<N>) {
+        self.randomness_seed = Some(seed);
+    }
+
+    /// Returns the snapshot of the mappings, if one is set.
+    pub const fn mapping_snapshot(&self) -> Option<&MappingSnapshot<N>> {
+        self.mapping_snapshot.as_ref()
+    }
+
+    /// Sets the snapshot of the mappings, which the `contains`, `get`, `get.or_use`, `set`, and `remove` commands
+    /// of every finalize scope read and write in place of the finalize store, for the mappings of every program.
+    /// This allows a finalization to be simulated against a given ledger state, without reading or writing the ledger.
+    pub fn set_mapping_snapshot(&mut self, snapshot: MappingSnapshot<N>) {
+        self.mapping_snapshot = Some(snapshot);
+    }
+}
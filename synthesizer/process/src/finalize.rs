@@ -80,6 +80,21 @@ impl<N: Network> Process<N> {
         store: &FinalizeStore<N, P>,
         execution: &Execution<N>,
         fee: Option<&Fee<N>>,
+    ) -> Result<Vec<FinalizeOperation<N>>> {
+        self.finalize_execution_with_options(state, store, execution, fee, &ExecutionOptions::default())
+    }
+
+    /// Finalizes the execution and fee, with the given options.
+    /// The options are applied to the registers of every finalize scope of the execution, but not to those of the fee.
+    /// This method assumes the given execution **is valid**.
+    #[inline]
+    pub fn finalize_execution_with_options<P: FinalizeStorage<N>>(
+        &self,
+        state: FinalizeGlobalState,
+        store: &FinalizeStore<N, P>,
+        execution: &Execution<N>,
+        fee: Option<&Fee<N>>,
+        options: &ExecutionOptions<N>,
     ) -> Result<Vec<FinalizeOperation<N>>> {
         let timer = timer!("Program::finalize_execution");
 
@@ -107,7 +122,7 @@ impl<N: Network> Process<N> {
             // Finalize the root transition.
             // Note that this will result in all the remaining transitions being finalized, since the number
             // of calls matches the number of transitions.
-            let mut finalize_operations = finalize_transition(state, store, stack, transition, call_graph)?;
+            let mut finalize_operations = finalize_transition(state, store, stack, transition, call_graph, options)?;
 
             /* Finalize the fee. */
 
@@ -162,7 +177,7 @@ fn finalize_fee_transition<N: Network, P: FinalizeStorage<N>>(
     call_graph.insert(*fee.transition_id(), Vec::new());
 
     // Finalize the transition.
-    match finalize_transition(state, store, stack, fee, call_graph) {
+    match finalize_transition(state, store, stack, fee, call_graph, &ExecutionOptions::default()) {
         // If the evaluation succeeds, return the finalize operations.
         Ok(finalize_operations) => Ok(finalize_operations),
         // If the evaluation fails, bail and return the error.
@@ -177,6 +192,7 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
     stack: &Stack<N>,
     transition: &Transition<N>,
     call_graph: HashMap<N::TransitionID, Vec<N::TransitionID>>,
+    options: &ExecutionOptions<N>,
 ) -> Result<Vec<FinalizeOperation<N>>> {
     // Retrieve the program ID.
     let program_id = transition.program_id();
@@ -206,7 +222,7 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
     let mut states = Vec::new();
 
     // Initialize the top-level finalize state.
-    states.push(initialize_finalize_state(state, future, stack, *transition.id())?);
+    states.push(initialize_finalize_state(state, future, stack, *transition.id(), options)?);
 
     // While there are active finalize states, finalize them.
     while let Some(FinalizeState {
@@ -279,7 +295,7 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
 
                     let callee_state = match handle_halting!(panic::AssertUnwindSafe(|| {
                         // Set up the finalize state for the await.
-                        setup_await(state, await_, stack, &registers, child_transition_id)
+                        setup_await(state, await_, stack, &registers, child_transition_id, options)
                     })) {
                         Ok(Ok(callee_state)) => callee_state,
                         // If the evaluation fails, bail and return the error.
@@ -351,6 +367,7 @@ fn initialize_finalize_state<'a, N: Network>(
     future: &Future<N>,
     stack: &'a Stack<N>,
     transition_id: N::TransitionID,
+    options: &ExecutionOptions<N>,
 ) -> Result<FinalizeState<'a, N>> {
     // Get the finalize logic and the stack.
     let (finalize, stack) = match stack.program_id() == future.program_id() {
@@ -376,6 +393,8 @@ fn initialize_finalize_state<'a, N: Network>(
         *future.function_name(),
         stack.get_finalize_types(future.function_name())?.clone(),
     );
+    // Set the options.
+    registers.set_options(options);
 
     // Store the inputs.
     finalize.inputs().iter().map(|i| i.register()).zip_eq(future.arguments().iter()).try_for_each(
@@ -396,6 +415,7 @@ fn setup_await<'a, N: Network>(
     stack: &'a Stack<N>,
     registers: &FinalizeRegisters<N>,
     transition_id: N::TransitionID,
+    options: &ExecutionOptions<N>,
 ) -> Result<FinalizeState<'a, N>> {
     // Retrieve the input as a future.
     let future = match registers.load(stack, &Operand::Register(await_.register().clone()))? {
@@ -403,7 +423,7 @@ fn setup_await<'a, N: Network>(
         _ => bail!("The input to 'await' is not a future"),
     };
     // Initialize the state.
-    initialize_finalize_state(state, &future, stack, transition_id)
+    initialize_finalize_state(state, &future, stack, transition_id, options)
 }
 
 // A helper function that returns the index to branch to.
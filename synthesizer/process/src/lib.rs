@@ -18,6 +18,12 @@
 // TODO (howardwu): Update the return type on `execute` after stabilizing the interface.
 #![allow(clippy::type_complexity)]
 
+mod cost;
+pub use cost::*;
+
+mod execution_options;
+pub use execution_options::*;
+
 mod stack;
 pub use stack::*;
 
@@ -35,6 +41,7 @@ mod finalize;
 mod verify_deployment;
 mod verify_execution;
 mod verify_fee;
+mod verify_program;
 
 #[cfg(test)]
 mod tests;
@@ -56,8 +63,10 @@ use synthesizer_program::{
     FinalizeOperation,
     Instruction,
     Program,
+    ProgramWarning,
     RegistersLoad,
     RegistersStore,
+    StackMatches,
     StackProgram,
 };
 use synthesizer_snark::{ProvingKey, UniversalSRS, VerifyingKey};
@@ -0,0 +1,75 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::{
+    network::Network,
+    program::{Identifier, ProgramID},
+};
+
+/// The estimated cost of a call, derived statically from the callee and its nested calls.
+///
+/// The finalize cost is priced in microcredits with `cost_per_command`, assuming every finalize scope
+/// of the call is awaited. The number of constraints is not estimated, as it is only known once the
+/// circuit of the callee is synthesized.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CallCost<N: Network> {
+    /// The program ID of the callee.
+    program_id: ProgramID<N>,
+    /// The name of the callee.
+    resource: Identifier<N>,
+    /// The number of transitions the call produces (zero for a closure).
+    num_transitions: usize,
+    /// The number of instructions executed, including those of nested calls.
+    num_instructions: usize,
+    /// The cost in microcredits of the finalize scopes, including those of nested calls.
+    finalize_cost: u64,
+}
+
+impl<N: Network> CallCost<N> {
+    /// Initializes a new call cost.
+    pub const fn new(
+        program_id: ProgramID<N>,
+        resource: Identifier<N>,
+        num_transitions: usize,
+        num_instructions: usize,
+        finalize_cost: u64,
+    ) -> Self {
+        Self { program_id, resource, num_transitions, num_instructions, finalize_cost }
+    }
+
+    /// Returns the program ID of the callee.
+    pub const fn program_id(&self) -> &ProgramID<N> {
+        &self.program_id
+    }
+
+    /// Returns the name of the callee.
+    pub const fn resource(&self) -> &Identifier<N> {
+        &self.resource
+    }
+
+    /// Returns the number of transitions the call produces (zero for a closure).
+    pub const fn num_transitions(&self) -> usize {
+        self.num_transitions
+    }
+
+    /// Returns the number of instructions executed, including those of nested calls.
+    pub const fn num_instructions(&self) -> usize {
+        self.num_instructions
+    }
+
+    /// Returns the cost in microcredits of the finalize scopes, including those of nested calls.
+    pub const fn finalize_cost(&self) -> u64 {
+        self.finalize_cost
+    }
+}
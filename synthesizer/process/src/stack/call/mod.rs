@@ -12,8 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod cost;
+pub use cost::*;
+
 use crate::{CallStack, Registers, RegistersCall, StackEvaluate, StackExecute};
-use console::{network::prelude::*, program::Request};
+use console::{
+    network::prelude::*,
+    program::{Locator, Request},
+};
 use synthesizer_program::{
     Call,
     CallOperator,
@@ -64,6 +70,8 @@ impl<N: Network> CallTrait<N> for Call<N> {
         let (substack, resource) = match self.operator() {
             // Retrieve the call stack and resource from the locator.
             CallOperator::Locator(locator) => {
+                // Ensure the external call is permitted.
+                registers.check_call_permitted(stack, locator.program_id())?;
                 (stack.get_external_stack(locator.program_id())?.clone(), locator.resource())
             }
             CallOperator::Resource(resource) => {
@@ -78,6 +86,38 @@ impl<N: Network> CallTrait<N> for Call<N> {
             }
         };
 
+        // Ensure the call does not exceed the maximum call depth.
+        registers.check_call_depth(&substack, resource)?;
+
+        // If the call is intercepted, assign the intercepted outputs in place of evaluating the callee.
+        let callee = Locator::new(*substack.program_id(), *resource);
+        if let Some(outputs) = registers.intercept_call(&callee, &inputs) {
+            // Ensure the number of outputs matches the number of destinations.
+            ensure!(
+                outputs.len() == self.destinations().len(),
+                "Expected {} outputs from the intercepted call to '{callee}', found {}",
+                self.destinations().len(),
+                outputs.len()
+            );
+            // If the callee is a function, consume its request, so that the requests of the later calls remain aligned.
+            if substack.program().contains_function(resource) {
+                if let CallStack::Evaluate(authorization) = registers.call_stack() {
+                    let request = authorization.next()?;
+                    ensure!(
+                        request.program_id() == callee.program_id() && request.function_name() == callee.resource(),
+                        "The next request is for '{}/{}', not for the intercepted call to '{callee}'",
+                        request.program_id(),
+                        request.function_name()
+                    );
+                }
+            }
+            // Assign the outputs to the destination registers.
+            for (output, register) in outputs.into_iter().zip_eq(&self.destinations()) {
+                registers.store(stack, register, output)?;
+            }
+            return Ok(());
+        }
+
         // If the operator is a closure, retrieve the closure and compute the output.
         let outputs = if let Ok(closure) = substack.program().get_closure(resource) {
             // Ensure the number of inputs matches the number of input statements.
@@ -89,6 +129,7 @@ impl<N: Network> CallTrait<N> for Call<N> {
                 &closure,
                 &inputs,
                 registers.call_stack(),
+                registers.options(),
                 registers.signer()?,
                 registers.caller()?,
                 registers.tvk()?,
@@ -103,7 +144,7 @@ impl<N: Network> CallTrait<N> for Call<N> {
             // Set the (console) caller.
             let console_caller = Some(*stack.program_id());
             // Evaluate the function.
-            let response = substack.evaluate_function::<A>(registers.call_stack(), console_caller)?;
+            let response = substack.evaluate_function::<A>(registers.call_stack(), registers.options(), console_caller)?;
             // Load the outputs.
             response.outputs().to_vec()
         }
@@ -142,6 +183,9 @@ impl<N: Network> CallTrait<N> for Call<N> {
         let (substack, resource) = match self.operator() {
             // Retrieve the call stack and resource from the locator.
             CallOperator::Locator(locator) => {
+                // Ensure the external call is permitted.
+                registers.check_call_permitted(stack, locator.program_id())?;
+
                 // Check the external call locator.
                 let function_name = locator.name().to_string();
                 let is_credits_program = &locator.program_id().to_string() == "credits.aleo";
@@ -167,6 +211,15 @@ impl<N: Network> CallTrait<N> for Call<N> {
             }
         };
 
+        // Ensure the call does not exceed the maximum call depth.
+        registers.check_call_depth(&substack, resource)?;
+
+        // Ensure the call is not intercepted, as the circuit of the callee is required to synthesize the transition.
+        let callee = Locator::new(*substack.program_id(), *resource);
+        if registers.intercept_call(&callee, &circuit::Eject::eject_value(&inputs)).is_some() {
+            bail!("Cannot intercept the call to '{callee}' in execute mode")
+        }
+
         // If the operator is a closure, retrieve the closure and compute the output.
         let outputs = if let Ok(closure) = substack.program().get_closure(resource) {
             // Execute the closure, and load the outputs.
@@ -174,6 +227,7 @@ impl<N: Network> CallTrait<N> for Call<N> {
                 &closure,
                 &inputs,
                 registers.call_stack(),
+                registers.options(),
                 registers.signer_circuit()?,
                 registers.caller_circuit()?,
                 registers.tvk_circuit()?,
@@ -224,7 +278,7 @@ impl<N: Network> CallTrait<N> for Call<N> {
                         authorization.push(request.clone());
 
                         // Execute the request.
-                        let response = substack.execute_function::<A, R>(call_stack, console_caller, rng)?;
+                        let response = substack.execute_function::<A, R>(call_stack, registers.options(), console_caller, rng)?;
 
                         // Return the request and response.
                         (request, response)
@@ -246,7 +300,7 @@ impl<N: Network> CallTrait<N> for Call<N> {
                         call_stack.push(request.clone())?;
 
                         // Execute the request.
-                        let response = substack.execute_function::<A, R>(call_stack, console_caller, rng)?;
+                        let response = substack.execute_function::<A, R>(call_stack, registers.options(), console_caller, rng)?;
                         // Return the request and response.
                         (request, response)
                     }
@@ -265,11 +319,18 @@ impl<N: Network> CallTrait<N> for Call<N> {
                         })?;
 
                         // Evaluate the function, and load the outputs.
-                        let console_response =
-                            substack.evaluate_function::<A>(registers.call_stack().replicate(), console_caller)?;
+                        let console_response = substack.evaluate_function::<A>(
+                            registers.call_stack().replicate(),
+                            registers.options(),
+                            console_caller,
+                        )?;
                         // Execute the request.
-                        let response =
-                            substack.execute_function::<A, R>(registers.call_stack(), console_caller, rng)?;
+                        let response = substack.execute_function::<A, R>(
+                            registers.call_stack(),
+                            registers.options(),
+                            console_caller,
+                            rng,
+                        )?;
                         // Ensure the values are equal.
                         if console_response.outputs() != response.outputs() {
                             #[cfg(debug_assertions)]
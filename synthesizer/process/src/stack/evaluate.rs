@@ -15,7 +15,7 @@
 use super::*;
 
 impl<N: Network> StackEvaluate<N> for Stack<N> {
-    /// Evaluates a program closure on the given inputs.
+    /// Evaluates a program closure on the given inputs, with the given options.
     ///
     /// # Errors
     /// This method will halt if the given inputs are not the same length as the input statements.
@@ -25,12 +25,16 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
         closure: &Closure<N>,
         inputs: &[Value<N>],
         call_stack: CallStack<N>,
+        options: &ExecutionOptions<N>,
         signer: Address<N>,
         caller: Address<N>,
         tvk: Field<N>,
     ) -> Result<Vec<Value<N>>> {
         let timer = timer!("Stack::evaluate_closure");
 
+        // Retrieve the trace sink, if one is set, as traces are only recorded in evaluate mode.
+        let trace_sink = options.trace_sink().filter(|_| matches!(call_stack, CallStack::Evaluate(..)));
+
         // Ensure the number of inputs matches the number of input statements.
         if closure.inputs().len() != inputs.len() {
             bail!("Expected {} inputs, found {}", closure.inputs().len(), inputs.len())
@@ -38,6 +42,12 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
 
         // Initialize the registers.
         let mut registers = Registers::<N, A>::new(call_stack, self.get_register_types(closure.name())?.clone());
+        // Set the options.
+        registers.set_options(options);
+        // If a trace sink is set, record the trace.
+        if trace_sink.is_some() {
+            registers.start_recording();
+        }
         // Set the transition signer.
         registers.set_signer(signer);
         // Set the transition caller.
@@ -55,10 +65,12 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
 
         // Evaluate the instructions.
         for instruction in closure.instructions() {
+            registers.begin_instruction(instruction);
             // If the evaluation fails, bail and return the error.
             if let Err(error) = instruction.evaluate(self, &mut registers) {
                 bail!("Failed to evaluate instruction ({instruction}): {error}");
             }
+            registers.end_instruction();
         }
         lap!(timer, "Evaluate the instructions");
 
@@ -87,11 +99,16 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
             .collect();
         lap!(timer, "Load the outputs");
 
+        // If recording, add the trace of the closure to the trace sink.
+        if let (Some(trace_sink), Ok(..)) = (trace_sink, &outputs) {
+            trace_sink.lock().push((Locator::new(*self.program_id(), *closure.name()), registers.take_trace()));
+        }
+
         finish!(timer);
         outputs
     }
 
-    /// Evaluates a program function on the given inputs.
+    /// Evaluates a program function on the given inputs, with the given options.
     ///
     /// # Errors
     /// This method will halt if the given inputs are not the same length as the input statements.
@@ -99,10 +116,14 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
     fn evaluate_function<A: circuit::Aleo<Network = N>>(
         &self,
         call_stack: CallStack<N>,
+        options: &ExecutionOptions<N>,
         caller: Option<ProgramID<N>>,
     ) -> Result<Response<N>> {
         let timer = timer!("Stack::evaluate_function");
 
+        // Retrieve the trace sink, if one is set, as traces are only recorded in evaluate mode.
+        let trace_sink = options.trace_sink().filter(|_| matches!(call_stack, CallStack::Evaluate(..)));
+
         // Retrieve the next request, based on the call stack mode.
         let (request, call_stack) = match &call_stack {
             CallStack::Evaluate(authorization) => (authorization.next()?, call_stack),
@@ -154,6 +175,12 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
 
         // Initialize the registers.
         let mut registers = Registers::<N, A>::new(call_stack, self.get_register_types(function.name())?.clone());
+        // Set the options.
+        registers.set_options(options);
+        // If a trace sink is set, record the trace.
+        if trace_sink.is_some() {
+            registers.start_recording();
+        }
         // Set the transition signer.
         registers.set_signer(signer);
         // Set the transition caller.
@@ -176,6 +203,7 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
         // Evaluate the instructions.
         // Note: We handle the `call` instruction separately, as it requires special handling.
         for instruction in function.instructions() {
+            registers.begin_instruction(instruction);
             // Evaluate the instruction.
             let result = match instruction {
                 // If the instruction is a `call` instruction, we need to handle it separately.
@@ -187,6 +215,7 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
             if let Err(error) = result {
                 bail!("Failed to evaluate instruction ({instruction}): {error}");
             }
+            registers.end_instruction();
         }
         lap!(timer, "Evaluate the instructions");
 
@@ -218,6 +247,11 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
             .collect::<Result<Vec<_>>>()?;
         lap!(timer, "Load the outputs");
 
+        // If recording, add the trace of the function to the trace sink.
+        if let Some(trace_sink) = trace_sink {
+            trace_sink.lock().push((Locator::new(*self.program_id(), *function.name()), registers.take_trace()));
+        }
+
         finish!(timer);
 
         // Map the output operands to registers.
@@ -15,7 +15,7 @@
 use super::*;
 
 impl<N: Network> StackExecute<N> for Stack<N> {
-    /// Executes a program closure on the given inputs.
+    /// Executes a program closure on the given inputs, with the given options.
     ///
     /// # Errors
     /// This method will halt if the given inputs are not the same length as the input statements.
@@ -25,6 +25,7 @@ impl<N: Network> StackExecute<N> for Stack<N> {
         closure: &Closure<N>,
         inputs: &[circuit::Value<A>],
         call_stack: CallStack<N>,
+        options: &ExecutionOptions<N>,
         signer: circuit::Address<A>,
         caller: circuit::Address<A>,
         tvk: circuit::Field<A>,
@@ -45,6 +46,8 @@ impl<N: Network> StackExecute<N> for Stack<N> {
 
         // Initialize the registers.
         let mut registers = Registers::new(call_stack, self.get_register_types(closure.name())?.clone());
+        // Set the options.
+        registers.set_options(options);
         // Set the transition signer, as a circuit.
         registers.set_signer_circuit(signer);
         // Set the transition caller, as a circuit.
@@ -124,7 +127,7 @@ impl<N: Network> StackExecute<N> for Stack<N> {
         outputs
     }
 
-    /// Executes a program function on the given inputs.
+    /// Executes a program function on the given inputs, with the given options.
     ///
     /// Note: To execute a transition, do **not** call this method. Instead, call `Process::execute`.
     ///
@@ -134,6 +137,7 @@ impl<N: Network> StackExecute<N> for Stack<N> {
     fn execute_function<A: circuit::Aleo<Network = N>, R: CryptoRng + Rng>(
         &self,
         mut call_stack: CallStack<N>,
+        options: &ExecutionOptions<N>,
         console_caller: Option<ProgramID<N>>,
         rng: &mut R,
     ) -> Result<Response<N>> {
@@ -193,6 +197,8 @@ impl<N: Network> StackExecute<N> for Stack<N> {
 
         // Initialize the registers.
         let mut registers = Registers::new(call_stack, self.get_register_types(function.name())?.clone());
+        // Set the options.
+        registers.set_options(options);
 
         use circuit::{Eject, Inject};
 
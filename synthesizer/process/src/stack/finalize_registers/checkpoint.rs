@@ -0,0 +1,71 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// A checkpoint of the assigned registers in a finalize scope.
+///
+/// The checkpoint is register-only: it does not capture the mappings, or the finalize operations produced
+/// by the `set` and `remove` commands.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FinalizeCheckpoint<N: Network> {
+    /// The transition ID for the finalize scope.
+    transition_id: N::TransitionID,
+    /// The number of assigned registers.
+    num_registers: usize,
+    /// The tracker for the last register locator.
+    last_register: Option<u64>,
+}
+
+impl<N: Network> FinalizeRegisters<N> {
+    /// Returns a checkpoint of the currently-assigned registers.
+    ///
+    /// Note that the checkpoint only covers the registers. Mapping writes are rolled back separately,
+    /// as each finalize is executed in an atomic batch of the finalize store, which is aborted as a whole.
+    #[inline]
+    pub fn checkpoint(&self) -> FinalizeCheckpoint<N> {
+        FinalizeCheckpoint {
+            transition_id: self.transition_id,
+            num_registers: self.registers.len(),
+            last_register: self.last_register,
+        }
+    }
+
+    /// Restores the registers to the given checkpoint, unassigning every register stored since.
+    ///
+    /// Note that mapping writes performed since the checkpoint are not undone, as they are already applied
+    /// to the finalize store. A caller that restores a checkpoint after a mapping write must also abort the
+    /// atomic batch of the finalize store, to discard the write.
+    ///
+    /// # Errors
+    /// This method will halt if the checkpoint was taken from another finalize scope.
+    /// This method will halt if the checkpoint is ahead of the current registers.
+    #[inline]
+    pub fn restore(&mut self, checkpoint: FinalizeCheckpoint<N>) -> Result<()> {
+        // Ensure the checkpoint is for this finalize scope.
+        ensure!(
+            checkpoint.transition_id == self.transition_id,
+            "Cannot restore a checkpoint from another finalize scope"
+        );
+        // Ensure the checkpoint is not ahead of the current registers.
+        ensure!(
+            checkpoint.num_registers <= self.registers.len(),
+            "Cannot restore a checkpoint that is ahead of the current registers"
+        );
+        // Unassign the registers stored since the checkpoint.
+        self.registers.truncate(checkpoint.num_registers);
+        self.last_register = checkpoint.last_register;
+        Ok(())
+    }
+}
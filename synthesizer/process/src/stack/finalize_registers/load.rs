@@ -23,6 +23,9 @@ impl<N: Network> RegistersLoad<N> for FinalizeRegisters<N> {
     /// In the case of register accesses, this method will halt if the access is not found.
     #[inline]
     fn load(&self, stack: &(impl StackMatches<N> + StackProgram<N>), operand: &Operand<N>) -> Result<Value<N>> {
+        // Consume a step from the step budget.
+        self.consume_step()?;
+
         // Retrieve the register.
         let register = match operand {
             // If the operand is a literal, return the literal.
@@ -72,4 +75,37 @@ impl<N: Network> RegistersLoad<N> for FinalizeRegisters<N> {
 
         Ok(value)
     }
+
+    /// Loads the value of a given operand, and returns it with whether it is an input, computed, or literal value.
+    ///
+    /// # Errors
+    /// This method will halt if the register locator is not found.
+    /// In the case of register accesses, this method will halt if the access is not found.
+    #[inline]
+    fn load_with_origin(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+    ) -> Result<(Value<N>, RegisterOrigin)> {
+        let origin = RegisterOrigin::from_operand(operand, |register| self.finalize_types.is_input(register));
+        Ok((self.load(stack, operand)?, origin))
+    }
+
+    /// Returns the plaintext type of a given operand, without loading its value.
+    ///
+    /// # Errors
+    /// This method will halt if the register type is not found.
+    /// This method will halt if the operand is not a plaintext.
+    #[inline]
+    fn operand_plaintext_type(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+    ) -> Result<PlaintextType<N>> {
+        // Retrieve the type of the operand.
+        match self.finalize_types.get_type_from_operand(stack, operand)? {
+            FinalizeType::Plaintext(plaintext_type) => Ok(plaintext_type),
+            finalize_type => bail!("Operand '{operand}' is a '{finalize_type}', not a plaintext"),
+        }
+    }
 }
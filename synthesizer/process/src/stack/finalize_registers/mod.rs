@@ -12,19 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod checkpoint;
+pub use checkpoint::*;
+
 mod load;
 mod store;
 
-use crate::FinalizeTypes;
+use crate::{ExecutionOptions, FinalizeTypes, MappingSnapshot};
 use console::{
     network::prelude::*,
-    program::{Identifier, Literal, Plaintext, Register, Value},
-    types::U32,
+    program::{Identifier, Literal, Plaintext, PlaintextType, ProgramID, Register, Value},
+    types::{Field, U32},
 };
 use synthesizer_program::{
     FinalizeGlobalState,
     FinalizeRegistersState,
     Operand,
+    RecordObserver,
+    RegisterOrigin,
     RegistersLoad,
     RegistersStore,
     StackMatches,
@@ -32,6 +37,7 @@ use synthesizer_program::{
 };
 
 use indexmap::IndexMap;
+use std::cell::Cell;
 
 #[derive(Clone)]
 pub struct FinalizeRegisters<N: Network> {
@@ -41,12 +47,16 @@ pub struct FinalizeRegisters<N: Network> {
     transition_id: N::TransitionID,
     /// The function name for the finalize scope.
     function_name: Identifier<N>,
-    /// The mapping of all registers to their defined types.
+    /// The mapping of all registers to their defined types, as declared in the finalize scope.
     finalize_types: FinalizeTypes<N>,
     /// The mapping of assigned registers to their values.
     registers: IndexMap<u64, Value<N>>,
     /// The tracker for the last register locator.
     last_register: Option<u64>,
+    /// The options that the registers are run with.
+    options: ExecutionOptions<N>,
+    /// The remaining number of loads and stores, if a step budget is set.
+    step_budget: Cell<Option<u64>>,
 }
 
 impl<N: Network> FinalizeRegisters<N> {
@@ -58,7 +68,53 @@ impl<N: Network> FinalizeRegisters<N> {
         function_name: Identifier<N>,
         finalize_types: FinalizeTypes<N>,
     ) -> Self {
-        Self { state, transition_id, finalize_types, function_name, registers: IndexMap::new(), last_register: None }
+        Self {
+            state,
+            transition_id,
+            finalize_types,
+            function_name,
+            registers: IndexMap::new(),
+            last_register: None,
+            options: ExecutionOptions::default(),
+            step_budget: Cell::new(None),
+        }
+    }
+
+    /// Sets the options that the registers are run with.
+    #[inline]
+    pub fn set_options(&mut self, options: &ExecutionOptions<N>) {
+        self.step_budget.set(options.step_budget());
+        self.options = options.clone();
+    }
+
+    /// Sets the step budget, which is the number of loads and stores allowed before the registers halt.
+    #[inline]
+    pub fn set_step_budget(&mut self, steps: u64) {
+        self.options.set_step_budget(steps);
+        self.step_budget.set(Some(steps));
+    }
+
+    /// Sets the seed for the `rand` commands, in place of the random seed from the global state.
+    /// This allows a finalize scope to be run with deterministic randomness, such as in tests.
+    #[inline]
+    pub fn set_randomness_seed(&mut self, seed: Field<N>) {
+        self.options.set_randomness_seed(seed);
+    }
+
+    /// Sets the snapshot of the mappings, which the mapping commands read and write in place of the finalize store.
+    #[inline]
+    pub fn set_mapping_snapshot(&mut self, snapshot: MappingSnapshot<N>) {
+        self.options.set_mapping_snapshot(snapshot);
+    }
+
+    /// Consumes one step from the step budget, if one is set.
+    #[inline]
+    fn consume_step(&self) -> Result<()> {
+        if let Some(steps) = self.step_budget.get() {
+            ensure!(steps > 0, "step budget exhausted");
+            self.step_budget.set(Some(steps - 1));
+        }
+        Ok(())
     }
 }
 
@@ -80,4 +136,47 @@ impl<N: Network> FinalizeRegistersState<N> for FinalizeRegisters<N> {
     fn function_name(&self) -> &Identifier<N> {
         &self.function_name
     }
+
+    /// Returns the seed for the `rand` commands, if one is set in place of the seed from the global state.
+    #[inline]
+    fn randomness_seed(&self) -> Option<Field<N>> {
+        self.options.randomness_seed()
+    }
+
+    /// Returns `None` if no mapping snapshot is set, and otherwise the value of the given key in the given mapping
+    /// of the snapshot, if it exists.
+    #[inline]
+    fn mapping_snapshot_value(
+        &self,
+        program_id: &ProgramID<N>,
+        mapping_name: &Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Option<Option<Plaintext<N>>> {
+        self.options.mapping_snapshot().map(|snapshot| snapshot.get(program_id, mapping_name, key))
+    }
+
+    /// Returns `true` if a mapping snapshot is set, in which case the mapping commands use it in place of the store.
+    #[inline]
+    fn has_mapping_snapshot(&self) -> bool {
+        self.options.mapping_snapshot().is_some()
+    }
+
+    /// Sets the value of the given key in the given mapping of the snapshot, or removes the key if the value is `None`.
+    #[inline]
+    fn update_mapping_snapshot(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        key: &Plaintext<N>,
+        value: Option<Plaintext<N>>,
+    ) -> Result<()> {
+        let Some(snapshot) = self.options.mapping_snapshot() else {
+            bail!("Cannot update mapping '{program_id}/{mapping_name}', as no mapping snapshot is set");
+        };
+        match value {
+            Some(value) => snapshot.insert(program_id, mapping_name, key, value),
+            None => snapshot.remove(program_id, mapping_name, key),
+        }
+        Ok(())
+    }
 }
@@ -18,6 +18,9 @@ use console::program::FinalizeType;
 impl<N: Network> RegistersStore<N> for FinalizeRegisters<N> {
     /// Assigns the given value to the given register, assuming the register is not already assigned.
     ///
+    /// The value is checked against the finalize types of the register, which are derived from the
+    /// finalize scope (via `get_finalize_types`), and not from the register types of the function.
+    ///
     /// # Errors
     /// This method will halt if the given register is a register access.
     /// This method will halt if the given register is an input register.
@@ -29,6 +32,9 @@ impl<N: Network> RegistersStore<N> for FinalizeRegisters<N> {
         register: &Register<N>,
         stack_value: Value<N>,
     ) -> Result<()> {
+        // Consume a step from the step budget.
+        self.consume_step()?;
+
         // Store the value to the register.
         match (register, stack_value) {
             (Register::Locator(locator), stack_value) => {
@@ -75,4 +81,87 @@ impl<N: Network> RegistersStore<N> for FinalizeRegisters<N> {
             (Register::Access(..), _) => bail!("Cannot store to a register access: '{register}'"),
         }
     }
+
+    /// Assigns the given value to the given register, assuming the register is not already assigned,
+    /// and returns a reference to the stored value.
+    ///
+    /// # Errors
+    /// This method will halt if the given register is a register access.
+    /// This method will halt if the given register is an input register.
+    /// This method will halt if the register is already used.
+    #[inline]
+    fn store_and_return(
+        &mut self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        register: &Register<N>,
+        stack_value: Value<N>,
+    ) -> Result<&Value<N>> {
+        // Store the value.
+        self.store(stack, register, stack_value)?;
+        // Return the stored value.
+        self.registers.get(&register.locator()).ok_or_else(|| anyhow!("'{register}' does not exist"))
+    }
+
+    /// Sets the observer that is invoked with every record stored in the registers.
+    ///
+    /// As records cannot be stored in a finalize scope, the observer is never invoked.
+    #[inline]
+    fn set_record_observer(&mut self, _observer: RecordObserver<N>) {}
+
+    /// Shrinks the storage of the registers to fit the assigned registers.
+    /// This method does not change the value of any assigned register.
+    #[inline]
+    fn compact(&mut self) {
+        self.registers.shrink_to_fit();
+    }
+
+    /// Exchanges the values assigned to the given registers, which must be assigned destination registers of the same type.
+    ///
+    /// # Errors
+    /// This method will halt if either register is an input register.
+    /// This method will halt if either register is not assigned.
+    /// This method will halt if the registers are not of the same type.
+    #[inline]
+    fn swap_registers(&mut self, stack: &(impl StackMatches<N> + StackProgram<N>), a: u64, b: u64) -> Result<()> {
+        // Ensure neither register is an input register.
+        for locator in [a, b] {
+            ensure!(
+                !self.finalize_types.is_input(&Register::Locator(locator)),
+                "Cannot swap input register 'r{locator}'"
+            );
+        }
+        // Ensure the registers are of the same type.
+        let type_a = self.finalize_types.get_type(stack, &Register::Locator(a))?;
+        let type_b = self.finalize_types.get_type(stack, &Register::Locator(b))?;
+        ensure!(type_a == type_b, "Cannot swap 'r{a}' of type '{type_a}' with 'r{b}' of type '{type_b}'");
+        // Retrieve the values.
+        let (Some(value_a), Some(value_b)) = (self.registers.get(&a), self.registers.get(&b)) else {
+            bail!("Cannot swap 'r{a}' and 'r{b}', as both must be assigned")
+        };
+        let (value_a, value_b) = (value_a.clone(), value_b.clone());
+        // Exchange the values, without changing the order of assignment.
+        self.registers.insert(a, value_b);
+        self.registers.insert(b, value_a);
+        Ok(())
+    }
+
+    /// Clears every assigned register, including the input registers, so the registers can be reused.
+    /// The step budget is restored to its configured value, while the global state, finalize types,
+    /// and options are retained.
+    #[inline]
+    fn reset(&mut self) {
+        self.registers.clear();
+        self.last_register = None;
+        self.step_budget.set(self.options.step_budget());
+    }
+
+    /// Halts, as a finalize scope does not have outputs.
+    #[inline]
+    fn assert_outputs_complete(
+        &self,
+        _stack: &(impl StackMatches<N> + StackProgram<N>),
+        function_name: &Identifier<N>,
+    ) -> Result<()> {
+        bail!("The finalize scope of '{function_name}' does not have outputs")
+    }
 }
@@ -150,7 +150,7 @@ impl<N: Network> FinalizeTypes<N> {
                 // Access the input to the future to output the register type and check that it is in bounds.
                 (FinalizeRefType::Future(locator), Access::Index(index)) => {
                     // Retrieve the associated function.
-                    let function = match locator.program_id() == stack.program_id() {
+                    let function = match stack.is_current_program(locator.program_id()) {
                         true => stack.get_function_ref(locator.resource())?,
                         false => {
                             stack.get_external_program(locator.program_id())?.get_function_ref(locator.resource())?
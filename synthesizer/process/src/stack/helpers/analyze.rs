@@ -0,0 +1,440 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use console::{
+    program::{FinalizeType, LiteralType, StructType},
+    types::Scalar,
+};
+use synthesizer_program::{CastType, MappingLocator};
+
+impl<N: Network> Stack<N> {
+    /// Returns the instructions of the closure or function with the given name.
+    pub(crate) fn get_instructions(&self, name: &Identifier<N>) -> Result<&[Instruction<N>]> {
+        // Retrieve the instructions of the closure, if it exists.
+        if let Some(closure) = self.program.closures().get(name) {
+            return Ok(closure.instructions());
+        }
+        // Retrieve the instructions of the function, if it exists.
+        match self.program.functions().get(name) {
+            Some(function) => Ok(function.instructions()),
+            None => bail!("'{name}' is not a closure or function in '{}'", self.program_id()),
+        }
+    }
+
+    /// Returns an iterator over the name of each closure or function in this program,
+    /// paired with the operator of each call it makes, in program order.
+    pub(crate) fn calls(&self) -> impl Iterator<Item = (&Identifier<N>, &CallOperator<N>)> {
+        let closures = self.program.closures().iter().map(|(name, closure)| (name, closure.instructions()));
+        let functions = self.program.functions().iter().map(|(name, function)| (name, function.instructions()));
+        closures.chain(functions).flat_map(|(name, instructions)| {
+            instructions.iter().filter_map(move |instruction| match instruction {
+                Instruction::Call(call) => Some((name, call.operator())),
+                _ => None,
+            })
+        })
+    }
+
+    /// Checks that no struct in the given structs refers to itself, either directly or through other structs.
+    pub(crate) fn verify_acyclic_struct_types(structs: &IndexMap<Identifier<N>, StructType<N>>) -> Result<()> {
+        let mut visited = IndexSet::new();
+        for struct_name in structs.keys() {
+            Self::visit_struct(structs, struct_name, &mut Vec::new(), &mut visited)?;
+        }
+        Ok(())
+    }
+
+    /// Visits the struct with the given name and the structs it refers to, depth-first,
+    /// and halts with the cycle if a struct on the current path is reached again.
+    fn visit_struct(
+        structs: &IndexMap<Identifier<N>, StructType<N>>,
+        struct_name: &Identifier<N>,
+        path: &mut Vec<Identifier<N>>,
+        visited: &mut IndexSet<Identifier<N>>,
+    ) -> Result<()> {
+        // Ensure the struct is not already on the current path.
+        if let Some(index) = path.iter().position(|name| name == struct_name) {
+            let cycle = path[index..].iter().chain([struct_name]).map(|name| name.to_string()).collect::<Vec<_>>();
+            bail!("Struct '{struct_name}' is recursive: {}", cycle.join(" -> "))
+        }
+        // Skip the struct if it has already been fully visited.
+        if visited.contains(struct_name) {
+            return Ok(());
+        }
+        // Retrieve the struct, skipping undefined structs, which are rejected elsewhere.
+        let Some(struct_) = structs.get(struct_name) else {
+            return Ok(());
+        };
+
+        path.push(*struct_name);
+        for member_type in struct_.members().values() {
+            // Retrieve the struct referenced by the member, if any.
+            let member_type = match member_type {
+                PlaintextType::Array(array_type) => array_type.base_element_type(),
+                member_type => member_type,
+            };
+            if let PlaintextType::Struct(member_struct) = member_type {
+                Self::visit_struct(structs, member_struct, path, visited)?;
+            }
+        }
+        path.pop();
+
+        visited.insert(*struct_name);
+        Ok(())
+    }
+
+    /// Visits the imports of this program depth-first, adding each import and its program after its own imports,
+    /// and halts with the cycle if a program on the current path is reached again.
+    pub(crate) fn visit_imports<'a>(
+        &'a self,
+        path: &mut Vec<ProgramID<N>>,
+        imports: &mut IndexMap<ProgramID<N>, &'a Program<N>>,
+    ) -> Result<()> {
+        path.push(*self.program_id());
+        for import_id in self.program.imports().keys() {
+            // Ensure the import is not already on the current path.
+            if let Some(index) = path.iter().position(|program_id| program_id == import_id) {
+                let cycle = path[index..].iter().chain([import_id]).map(|id| id.to_string()).collect::<Vec<_>>();
+                bail!("Program '{import_id}' is imported cyclically: {}", cycle.join(" -> "))
+            }
+            // Skip the import if it has already been visited.
+            if imports.contains_key(import_id) {
+                continue;
+            }
+            // Visit the imports of the import through its own stack, before adding the import itself,
+            // as the imports of the import are not necessarily imported by this program.
+            let import = self.get_external_stack(import_id)?;
+            import.visit_imports(path, imports)?;
+            imports.insert(*import_id, import.program());
+        }
+        path.pop();
+        Ok(())
+    }
+
+    /// Visits the given function and the external functions it calls depth-first,
+    /// adding each function before its callees. Closures are not visited, as they do not have keys.
+    pub(crate) fn visit_functions(
+        &self,
+        function_name: &Identifier<N>,
+        functions: &mut IndexSet<Locator<N>>,
+    ) -> Result<()> {
+        // Skip the function if it has already been visited.
+        if !functions.insert(Locator::new(*self.program_id(), *function_name)) {
+            return Ok(());
+        }
+        // Visit the external functions called by the function.
+        for instruction in self.get_function_ref(function_name)?.instructions() {
+            if let Instruction::Call(call) = instruction {
+                if let CallOperator::Locator(locator) = call.operator() {
+                    self.get_external_stack(locator.program_id())?.visit_functions(locator.resource(), functions)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number of bytes in the serialized form of a plaintext of the given type.
+    /// Strings are not supported, as the size of a string is not determined by its type.
+    pub(crate) fn plaintext_size_in_bytes(&self, plaintext_type: &PlaintextType<N>) -> Result<u64> {
+        match plaintext_type {
+            // A literal is serialized as its variant, type, and primitive.
+            PlaintextType::Literal(literal_type) => {
+                let num_primitive_bytes = match literal_type {
+                    LiteralType::Boolean | LiteralType::I8 | LiteralType::U8 => 1,
+                    LiteralType::I16 | LiteralType::U16 => 2,
+                    LiteralType::I32 | LiteralType::U32 => 4,
+                    LiteralType::I64 | LiteralType::U64 => 8,
+                    LiteralType::I128 | LiteralType::U128 => 16,
+                    LiteralType::Address | LiteralType::Field | LiteralType::Group => Field::<N>::SIZE_IN_BYTES,
+                    LiteralType::Scalar => Scalar::<N>::SIZE_IN_BYTES,
+                    // A signature is serialized as its challenge, response, and the two group elements of its compute key.
+                    LiteralType::Signature => 2 * Scalar::<N>::SIZE_IN_BYTES + 2 * Field::<N>::SIZE_IN_BYTES,
+                    LiteralType::String => bail!("The size of a 'string' is not determined by its type"),
+                };
+                Ok(1 + 2 + num_primitive_bytes as u64)
+            }
+            // A struct is serialized as its variant and number of members, followed by the name,
+            // number of bytes, and value of each member.
+            PlaintextType::Struct(struct_name) => {
+                let mut num_bytes = 1 + 1;
+                for (member_name, member_type) in self.program.get_struct(struct_name)?.members() {
+                    num_bytes +=
+                        1 + member_name.to_string().len() as u64 + 2 + self.plaintext_size_in_bytes(member_type)?;
+                }
+                Ok(num_bytes)
+            }
+            // An array is serialized as its variant and length, followed by the number of bytes and value of each element.
+            PlaintextType::Array(array_type) => {
+                let num_element_bytes = 2 + self.plaintext_size_in_bytes(array_type.next_element_type())?;
+                Ok(1 + 4 + **array_type.length() as u64 * num_element_bytes)
+            }
+        }
+    }
+
+    /// Returns the external programs referenced by the given program, through calls, external record types,
+    /// futures, and reads of external mappings.
+    pub(crate) fn referenced_programs(program: &Program<N>) -> IndexSet<ProgramID<N>> {
+        let mut locators = Vec::new();
+        // Collect the external locators of the calls and casts.
+        let closures = program.closures().values().map(|closure| closure.instructions());
+        let functions = program.functions().values().map(|function| function.instructions());
+        for instruction in closures.chain(functions).flatten() {
+            match instruction {
+                Instruction::Call(call) => {
+                    if let CallOperator::Locator(locator) = call.operator() {
+                        locators.push(locator);
+                    }
+                }
+                Instruction::Cast(cast) => {
+                    if let CastType::ExternalRecord(locator) = cast.cast_type() {
+                        locators.push(locator);
+                    }
+                }
+                Instruction::CastLossy(cast) => {
+                    if let CastType::ExternalRecord(locator) = cast.cast_type() {
+                        locators.push(locator);
+                    }
+                }
+                _ => (),
+            }
+        }
+        // Collect the external locators of the closure inputs and outputs.
+        for closure in program.closures().values() {
+            let inputs = closure.inputs().iter().map(|input| input.register_type());
+            let outputs = closure.outputs().iter().map(|output| output.register_type());
+            for register_type in inputs.chain(outputs) {
+                if let RegisterType::ExternalRecord(locator) | RegisterType::Future(locator) = register_type {
+                    locators.push(locator);
+                }
+            }
+        }
+        for function in program.functions().values() {
+            // Collect the external locators of the function inputs and outputs.
+            let inputs = function.inputs().iter().map(|input| input.value_type());
+            let outputs = function.outputs().iter().map(|output| output.value_type());
+            for value_type in inputs.chain(outputs) {
+                if let ValueType::ExternalRecord(locator) | ValueType::Future(locator) = value_type {
+                    locators.push(locator);
+                }
+            }
+            // Collect the external locators of the finalize inputs and mapping reads.
+            if let Some(finalize) = function.finalize_logic() {
+                for input in finalize.inputs() {
+                    if let FinalizeType::Future(locator) = input.finalize_type() {
+                        locators.push(locator);
+                    }
+                }
+                for command in finalize.commands() {
+                    let mapping = match command {
+                        Command::Get(get) => get.mapping(),
+                        Command::GetOrUse(get_or_use) => get_or_use.mapping(),
+                        _ => continue,
+                    };
+                    if let MappingLocator::Locator(locator) = mapping {
+                        locators.push(locator);
+                    }
+                }
+            }
+        }
+        locators
+            .into_iter()
+            .map(|locator| *locator.program_id())
+            .filter(|program_id| program_id != program.id())
+            .collect()
+    }
+
+    /// Runs the structural checks of the given program, and returns the warnings and errors they report.
+    /// As the checks only need the stacks of the imports, which are resolved with the given function,
+    /// the program is checked without building its own stack.
+    pub(crate) fn program_warnings<'a>(
+        program: &Program<N>,
+        get_external_stack: impl Fn(&ProgramID<N>) -> Result<&'a Stack<N>>,
+    ) -> Vec<ProgramWarning<N>> {
+        let mut warnings = Vec::new();
+        // Check that the structs are acyclic, reporting the first cycle.
+        let mut visited = IndexSet::new();
+        for struct_name in program.structs().keys() {
+            if let Err(error) = Self::visit_struct(program.structs(), struct_name, &mut Vec::new(), &mut visited) {
+                warnings.push(ProgramWarning::CyclicStruct(*struct_name, error.to_string()));
+                break;
+            }
+        }
+        // Check that no register is read before it is written, in every closure and function.
+        for name in program.closures().keys().chain(program.functions().keys()) {
+            if let Err(error) = Self::verify_no_read_before_write_in(program, name) {
+                warnings.push(ProgramWarning::ReadBeforeWrite(*name, error.to_string()));
+            }
+        }
+        for function_name in program.functions().keys() {
+            // Check that the future is consistent with the finalize scope.
+            if let Err(error) = Self::verify_future_consistency(program, function_name) {
+                warnings.push(ProgramWarning::InconsistentFuture(*function_name, error.to_string()));
+            }
+            // Check that the calls fit in a transaction, which holds back one transition for the fee.
+            match Self::number_of_calls(program, function_name, &get_external_stack) {
+                Ok(num_calls) if num_calls >= usize::pow(2, TRANSACTION_DEPTH as u32) => {
+                    warnings.push(ProgramWarning::CallLimitExceeded(*function_name, num_calls));
+                }
+                Ok(_) => (),
+                Err(error) => warnings.push(ProgramWarning::UnresolvedCalls(*function_name, error.to_string())),
+            }
+        }
+        // Check that every import is used.
+        let referenced = Self::referenced_programs(program);
+        for program_id in program.imports().keys() {
+            if !referenced.contains(program_id) {
+                warnings.push(ProgramWarning::UnusedImport(*program_id));
+            }
+        }
+        warnings
+    }
+
+    /// Returns the number of calls made by the given function of the given program, including the function itself
+    /// and the nested calls. The stacks of the imports are resolved with the given function.
+    fn number_of_calls<'a>(
+        program: &Program<N>,
+        function_name: &Identifier<N>,
+        get_external_stack: &impl Fn(&ProgramID<N>) -> Result<&'a Stack<N>>,
+    ) -> Result<usize> {
+        let mut num_calls = 1;
+        for instruction in program.get_function_ref(function_name)?.instructions() {
+            if let Instruction::Call(call) = instruction {
+                num_calls += match call.operator() {
+                    CallOperator::Locator(locator) => {
+                        get_external_stack(locator.program_id())?.get_number_of_calls(locator.resource())?
+                    }
+                    CallOperator::Resource(resource) if program.contains_function(resource) => {
+                        Self::number_of_calls(program, resource, get_external_stack)?
+                    }
+                    // A call to a closure does not produce a transition.
+                    CallOperator::Resource(_) => 0,
+                };
+            }
+        }
+        Ok(num_calls)
+    }
+
+    /// Checks that no register in the given closure or function of the given program is read before it is written.
+    pub(crate) fn verify_no_read_before_write_in(program: &Program<N>, name: &Identifier<N>) -> Result<()> {
+        // Retrieve the input registers, instructions, and outputs of the closure or function.
+        let (inputs, instructions, outputs) = match program.closures().get(name) {
+            Some(closure) => (
+                closure.inputs().iter().map(|input| input.register()).collect::<Vec<_>>(),
+                closure.instructions(),
+                closure.outputs().iter().map(|output| (output.to_string(), output.operand())).collect::<Vec<_>>(),
+            ),
+            None => {
+                let function = program.get_function_ref(name)?;
+                (
+                    function.inputs().iter().map(|input| input.register()).collect(),
+                    function.instructions(),
+                    function.outputs().iter().map(|output| (output.to_string(), output.operand())).collect(),
+                )
+            }
+        };
+        // Initialize the set of written locators, starting with the input registers.
+        let mut written: IndexSet<u64> = inputs.iter().map(|register| register.locator()).collect();
+
+        // Ensure every register read by an instruction has been written.
+        for (index, instruction) in instructions.iter().enumerate() {
+            for operand in instruction.operands() {
+                if let Operand::Register(register) = operand {
+                    if !written.contains(&register.locator()) {
+                        bail!("Instruction {index} ('{instruction}') reads '{register}' before it is written")
+                    }
+                }
+            }
+            written.extend(instruction.destinations().iter().map(|register| register.locator()));
+        }
+
+        // Ensure every register read by an output has been written.
+        for (output, operand) in outputs {
+            if let Operand::Register(register) = operand {
+                if !written.contains(&register.locator()) {
+                    bail!("Output '{output}' reads '{register}' before it is written")
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the `async` instruction and future output of the given function are consistent with its finalize scope.
+    /// A function with a finalize scope must contain one `async` instruction and output one future of itself,
+    /// while a function without a finalize scope must do neither.
+    pub(crate) fn verify_future_consistency(program: &Program<N>, function_name: &Identifier<N>) -> Result<()> {
+        // Retrieve the function.
+        let function = program.get_function_ref(function_name)?;
+        // Count the `async` instructions.
+        let num_async =
+            function.instructions().iter().filter(|instruction| matches!(instruction, Instruction::Async(..))).count();
+        // Retrieve the future outputs.
+        let futures = function
+            .outputs()
+            .iter()
+            .filter_map(|output| match output.value_type() {
+                ValueType::Future(locator) => Some(locator),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        match function.finalize_logic() {
+            Some(_) => {
+                ensure!(num_async == 1, "Expected 1 'async' instruction, found {num_async}");
+                ensure!(futures.len() == 1, "Expected 1 future output, found {}", futures.len());
+                let expected = Locator::new(*program.id(), *function_name);
+                ensure!(*futures[0] == expected, "Expected a future of '{expected}', found '{}'", futures[0]);
+            }
+            None => {
+                ensure!(num_async == 0, "Expected no 'async' instructions, found {num_async}");
+                ensure!(futures.is_empty(), "Expected no future outputs, found {}", futures.len());
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the live range of each register in the given function, from the position that assigns it
+    /// to the last position that reads it. The inputs are at position 0, the instructions at positions
+    /// `1..=n`, and the outputs at position `n + 1`. A register that is never read is live only where it is assigned.
+    pub(crate) fn live_ranges(&self, function_name: &Identifier<N>) -> Result<IndexMap<u64, (usize, usize)>> {
+        // Retrieve the function.
+        let function = self.get_function_ref(function_name)?;
+        let num_instructions = function.instructions().len();
+
+        // Assign the inputs at the start.
+        let mut ranges: IndexMap<u64, (usize, usize)> =
+            function.inputs().iter().map(|input| (input.register().locator(), (0, 0))).collect();
+        // Extends the live range of the register in the given operand, if any, to the given position.
+        let read = |ranges: &mut IndexMap<u64, (usize, usize)>, operand: &Operand<N>, position: usize| {
+            if let Operand::Register(register) = operand {
+                if let Some((_, end)) = ranges.get_mut(&register.locator()) {
+                    *end = position;
+                }
+            }
+        };
+        for (index, instruction) in function.instructions().iter().enumerate() {
+            // Read the operands, then assign the destinations.
+            for operand in instruction.operands() {
+                read(&mut ranges, operand, index + 1);
+            }
+            for destination in instruction.destinations() {
+                ranges.insert(destination.locator(), (index + 1, index + 1));
+            }
+        }
+        // Read the outputs at the end.
+        for output in function.outputs() {
+            read(&mut ranges, output.operand(), num_instructions + 1);
+        }
+        Ok(ranges)
+    }
+}
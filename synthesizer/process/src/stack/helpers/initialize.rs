@@ -24,6 +24,7 @@ impl<N: Network> Stack<N> {
             external_stacks: Default::default(),
             register_types: Default::default(),
             finalize_types: Default::default(),
+            call_depths: Default::default(),
             universal_srs: process.universal_srs().clone(),
             proving_keys: Default::default(),
             verifying_keys: Default::default(),
@@ -50,6 +51,13 @@ impl<N: Network> Stack<N> {
             // Add the function to the stack.
             stack.insert_function(function)?;
         }
+        // Cache the call depth of each closure and function.
+        for name in program.closures().keys().chain(program.functions().keys()) {
+            // Compute the call depth.
+            let depth = stack.compute_call_depth(name)?;
+            // Add the call depth to the stack.
+            stack.call_depths.insert(*name, depth);
+        }
         // Return the stack.
         Ok(stack)
     }
@@ -73,6 +81,29 @@ impl<N: Network> Stack<N> {
         Ok(())
     }
 
+    /// Computes the maximum depth of nested calls, starting from the given closure or function.
+    /// Callees in external stacks, and local callees computed earlier, use the cached depth.
+    fn compute_call_depth(&self, name: &Identifier<N>) -> Result<usize> {
+        let mut depth = 0;
+        for instruction in self.get_instructions(name)? {
+            if let Instruction::Call(call) = instruction {
+                // Determine the call depth of the callee.
+                let callee_depth = match call.operator() {
+                    CallOperator::Locator(locator) => {
+                        self.get_external_stack(locator.program_id())?.get_call_depth(locator.resource())?
+                    }
+                    CallOperator::Resource(resource) => match self.call_depths.get(resource) {
+                        Some(callee_depth) => *callee_depth,
+                        None => self.compute_call_depth(resource)?,
+                    },
+                };
+                // Update the depth, accounting for this call.
+                depth = depth.max(callee_depth + 1);
+            }
+        }
+        Ok(depth)
+    }
+
     /// Inserts the given closure to the stack.
     #[inline]
     fn insert_closure(&mut self, closure: &Closure<N>) -> Result<()> {
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use super::*;
-use console::program::{Argument, FinalizeType};
+use console::program::{Argument, FinalizeType, LiteralType};
 
 impl<N: Network> StackMatches<N> for Stack<N> {
     /// Checks that the given value matches the layout of the value type.
@@ -34,6 +34,21 @@ impl<N: Network> StackMatches<N> for Stack<N> {
         }
     }
 
+    /// Checks that the given values match the layouts of the value types, in order.
+    fn matches_value_types(&self, values: &[Value<N>], value_types: &[ValueType<N>]) -> Result<()> {
+        // Ensure the number of values matches the number of value types.
+        if values.len() != value_types.len() {
+            bail!("Expected {} values, found {}", value_types.len(), values.len())
+        }
+        // Ensure each value matches its value type, in order.
+        for (index, (value, value_type)) in values.iter().zip_eq(value_types).enumerate() {
+            if let Err(error) = self.matches_value_type(value, value_type) {
+                bail!("Value {index} does not match '{value_type}': {error}")
+            }
+        }
+        Ok(())
+    }
+
     /// Checks that the given stack value matches the layout of the register type.
     fn matches_register_type(&self, stack_value: &Value<N>, register_type: &RegisterType<N>) -> Result<()> {
         match (stack_value, register_type) {
@@ -88,6 +103,26 @@ impl<N: Network> StackMatches<N> for Stack<N> {
         self.matches_record_internal(record, record_type, 0)
     }
 
+    /// Checks that the given entry of the record matches the layout of the plaintext type.
+    /// The entry is checked regardless of its visibility, and composite entries are checked recursively.
+    fn matches_record_entry(
+        &self,
+        record: &Record<N, Plaintext<N>>,
+        entry: &Identifier<N>,
+        expected: &PlaintextType<N>,
+    ) -> Result<()> {
+        // Retrieve the plaintext of the entry.
+        let plaintext = match record.data().get(entry) {
+            Some(Entry::Constant(plaintext) | Entry::Public(plaintext) | Entry::Private(plaintext)) => plaintext,
+            None => bail!("Record entry '{entry}' does not exist"),
+        };
+        // Ensure the entry matches, starting at the depth of the record entries.
+        match self.matches_plaintext_internal(plaintext, expected, 1) {
+            Ok(()) => Ok(()),
+            Err(error) => bail!("Invalid record entry '{entry}': {error}"),
+        }
+    }
+
     /// Checks that the given plaintext matches the layout of the plaintext type.
     fn matches_plaintext(&self, plaintext: &Plaintext<N>, plaintext_type: &PlaintextType<N>) -> Result<()> {
         self.matches_plaintext_internal(plaintext, plaintext_type, 0)
@@ -97,6 +132,390 @@ impl<N: Network> StackMatches<N> for Stack<N> {
     fn matches_future(&self, future: &Future<N>, locator: &Locator<N>) -> Result<()> {
         self.matches_future_internal(future, locator, 0)
     }
+
+    /// Checks that the given future matches the finalize signature of the given callee,
+    /// as declared in the program of the callee.
+    ///
+    /// The future is checked by the stack of the callee, such that the structs in the finalize
+    /// signature resolve to the definitions of the callee, rather than to those of this program.
+    fn matches_future_call(&self, future: &Future<N>, callee: &Locator<N>) -> Result<()> {
+        match self.is_current_program(callee.program_id()) {
+            true => self.matches_future_internal(future, callee, 0),
+            false => self.get_external_stack(callee.program_id())?.matches_future_internal(future, callee, 0),
+        }
+    }
+
+    /// Checks that the given key and value match the declared key and value types of the given mapping.
+    fn matches_mapping_entry(
+        &self,
+        mapping_name: &Identifier<N>,
+        key: &Plaintext<N>,
+        value: &Plaintext<N>,
+    ) -> Result<()> {
+        // Retrieve the mapping.
+        let mapping = self.program().get_mapping(mapping_name)?;
+        // Ensure the key matches the key type.
+        if let Err(error) = self.matches_plaintext(key, mapping.key().plaintext_type()) {
+            bail!("Key '{key}' does not match the key type of mapping '{mapping_name}': {error}")
+        }
+        // Ensure the value matches the value type.
+        if let Err(error) = self.matches_plaintext(value, mapping.value().plaintext_type()) {
+            bail!("Value '{value}' does not match the value type of mapping '{mapping_name}': {error}")
+        }
+        Ok(())
+    }
+
+    /// Checks that the given default of a `get.or_use` command matches the declared value type of the given mapping.
+    fn validate_or_use_default(&self, mapping_name: &Identifier<N>, default: &Plaintext<N>) -> Result<()> {
+        // Retrieve the mapping.
+        let mapping = self.program().get_mapping(mapping_name)?;
+        // Ensure the default value matches the value type.
+        if let Err(error) = self.matches_plaintext(default, mapping.value().plaintext_type()) {
+            bail!("Default '{default}' does not match the value type of mapping '{mapping_name}': {error}")
+        }
+        Ok(())
+    }
+
+    /// Checks that the inputs of the given request match the declared input types of the function.
+    fn matches_request_inputs(&self, request: &Request<N>, function_name: &Identifier<N>) -> Result<()> {
+        // Ensure the request is for the given function.
+        ensure!(
+            request.program_id() == self.program_id() && request.function_name() == function_name,
+            "Request is for '{}/{}', expected '{}/{function_name}'",
+            request.program_id(),
+            request.function_name(),
+            self.program_id()
+        );
+        // Retrieve the input types.
+        let input_types = self.get_function_ref(function_name)?.input_types();
+        // Ensure the number of inputs matches the number of input types.
+        let (num_inputs, num_input_types) = (request.inputs().len(), input_types.len());
+        if num_inputs != num_input_types {
+            bail!("Function '{function_name}' expects {num_input_types} inputs, but the request has {num_inputs}")
+        }
+        // Ensure each input matches its declared input type, in order.
+        for (index, (input, input_type)) in request.inputs().iter().zip_eq(&input_types).enumerate() {
+            if let Err(error) = self.matches_value_type(input, input_type) {
+                bail!("Input {index} of '{function_name}' does not match '{input_type}': {error}")
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the outputs of the given response match the declared output types of the function.
+    fn matches_response_outputs(&self, response: &Response<N>, function_name: &Identifier<N>) -> Result<()> {
+        // Retrieve the output types.
+        let output_types = self.get_function_ref(function_name)?.output_types();
+        // Ensure the number of outputs matches the number of output types.
+        let (num_outputs, num_output_types) = (response.outputs().len(), output_types.len());
+        if num_outputs != num_output_types {
+            bail!("Function '{function_name}' expects {num_output_types} outputs, but the response has {num_outputs}")
+        }
+        // Ensure each output matches its declared output type, in order.
+        for (index, (output, output_type)) in response.outputs().iter().zip_eq(&output_types).enumerate() {
+            if let Err(error) = self.matches_value_type(output, output_type) {
+                bail!("Output {index} of '{function_name}' does not match '{output_type}': {error}")
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the canonical form of the given value.
+    ///
+    /// Canonicalization discards the cached bit representations of the value and its nested members.
+    /// No other transformation is applied: members are not reordered, and arrays, structs,
+    /// and literals are never converted into one another, as they are distinct values.
+    fn canonicalize_value(&self, value: Value<N>) -> Result<Value<N>> {
+        match value {
+            Value::Plaintext(plaintext) => Ok(Value::Plaintext(Self::canonicalize_plaintext_internal(plaintext, 0)?)),
+            Value::Record(record) => {
+                // Canonicalize the record owner.
+                let owner = match record.owner() {
+                    RecordOwner::Public(address) => RecordOwner::Public(*address),
+                    RecordOwner::Private(plaintext) => {
+                        RecordOwner::Private(Self::canonicalize_plaintext_internal(plaintext.clone(), 1)?)
+                    }
+                };
+                // Canonicalize the record entries.
+                let data = record
+                    .data()
+                    .iter()
+                    .map(|(name, entry)| {
+                        let entry = match entry {
+                            Entry::Constant(plaintext) => {
+                                Entry::Constant(Self::canonicalize_plaintext_internal(plaintext.clone(), 1)?)
+                            }
+                            Entry::Public(plaintext) => {
+                                Entry::Public(Self::canonicalize_plaintext_internal(plaintext.clone(), 1)?)
+                            }
+                            Entry::Private(plaintext) => {
+                                Entry::Private(Self::canonicalize_plaintext_internal(plaintext.clone(), 1)?)
+                            }
+                        };
+                        Ok((*name, entry))
+                    })
+                    .collect::<Result<IndexMap<_, _>>>()?;
+                Ok(Value::Record(Record::<N, Plaintext<N>>::from_plaintext(owner, data, *record.nonce())?))
+            }
+            Value::Future(future) => Ok(Value::Future(Self::canonicalize_future_internal(&future, 0)?)),
+        }
+    }
+
+    /// Returns a hash of the given value, which is the same for equal values regardless of how they were constructed.
+    /// The hash is computed as the BHP-1024 hash of the bits of the canonical form of the value.
+    fn value_hash(&self, value: &Value<N>) -> Result<Field<N>> {
+        // Canonicalize the value, so that no cached bits are reused.
+        let value = self.canonicalize_value(value.clone())?;
+        // Hash the bits of the canonical value.
+        N::hash_bhp1024(&value.to_bits_le())
+    }
+
+    /// Returns a JSON schema describing the given value type, including its kind, visibility,
+    /// and the layout of any nested members, elements, or record entries.
+    fn value_type_schema(&self, value_type: &ValueType<N>) -> serde_json::Value {
+        match value_type {
+            ValueType::Constant(plaintext_type) => self.plaintext_type_schema_internal(plaintext_type, "constant"),
+            ValueType::Public(plaintext_type) => self.plaintext_type_schema_internal(plaintext_type, "public"),
+            ValueType::Private(plaintext_type) => self.plaintext_type_schema_internal(plaintext_type, "private"),
+            ValueType::Record(record_name) => {
+                let mut schema = serde_json::json!({ "kind": "record", "name": record_name.to_string() });
+                if let Ok(record_type) = self.program().get_record(record_name) {
+                    self.record_type_schema_internal(&mut schema, record_type);
+                }
+                schema
+            }
+            ValueType::ExternalRecord(locator) => {
+                let mut schema = serde_json::json!({ "kind": "external_record", "locator": locator.to_string() });
+                if let Ok(external_stack) = self.get_external_stack(locator.program_id()) {
+                    if let Ok(record_type) = external_stack.program().get_record(locator.resource()) {
+                        external_stack.record_type_schema_internal(&mut schema, record_type);
+                    }
+                }
+                schema
+            }
+            ValueType::Future(locator) => {
+                serde_json::json!({ "kind": "future", "locator": locator.to_string(), "visibility": "public" })
+            }
+        }
+    }
+
+    /// Checks that the given JSON is structurally compatible with the given value type, without constructing the value.
+    ///
+    /// Structs and records are JSON objects with exactly the declared members, and arrays are JSON arrays of the
+    /// declared length.
+    /// Booleans are JSON booleans, and integers are JSON numbers within the range of their type, where 128-bit integers
+    /// may also be decimal strings. All other literals are JSON strings, whose contents are not checked.
+    /// A record may include its `_nonce`, and futures are not supported.
+    fn json_matches_value_type(&self, json: &serde_json::Value, value_type: &ValueType<N>) -> Result<()> {
+        match value_type {
+            ValueType::Constant(plaintext_type)
+            | ValueType::Public(plaintext_type)
+            | ValueType::Private(plaintext_type) => {
+                self.json_matches_plaintext_type_internal(json, plaintext_type, "$", 0)
+            }
+            ValueType::Record(record_name) => {
+                self.json_matches_record_type_internal(json, self.program().get_record(record_name)?)
+            }
+            ValueType::ExternalRecord(locator) => {
+                let external_stack = self.get_external_stack(locator.program_id())?;
+                let record_type = external_stack.program().get_record(locator.resource())?;
+                external_stack.json_matches_record_type_internal(json, record_type)
+            }
+            ValueType::Future(..) => bail!("A future cannot be provided as JSON"),
+        }
+    }
+
+    /// Returns the least general value type that both given value types are compatible with.
+    ///
+    /// As there is no subtyping between value types, two value types only unify if they describe the same type.
+    /// A record and an external record that refers to the same record in this program unify to the record.
+    /// Value types that differ in visibility do not unify, as visibility determines how a value is committed to.
+    fn unify_value_types(&self, a: &ValueType<N>, b: &ValueType<N>) -> Result<ValueType<N>> {
+        match (a, b) {
+            (a, b) if a == b => Ok(a.clone()),
+            (ValueType::Record(record_name), ValueType::ExternalRecord(locator))
+            | (ValueType::ExternalRecord(locator), ValueType::Record(record_name))
+                if self.is_current_program(locator.program_id()) && record_name == locator.resource() =>
+            {
+                Ok(ValueType::Record(*record_name))
+            }
+            _ => bail!("Value types '{a}' and '{b}' cannot be unified"),
+        }
+    }
+
+    /// Returns `true` if the given value types have the same underlying structure, ignoring their visibility.
+    /// Plaintext types are compared by layout, so structs with different names but the same members are equal.
+    fn types_structurally_equal(&self, a: &ValueType<N>, b: &ValueType<N>) -> bool {
+        match (a, b) {
+            (
+                ValueType::Constant(a) | ValueType::Public(a) | ValueType::Private(a),
+                ValueType::Constant(b) | ValueType::Public(b) | ValueType::Private(b),
+            ) => self.plaintext_types_structurally_equal_internal(a, b),
+            // Records and futures have no visibility, and are equal if they can be unified.
+            (
+                ValueType::Record(..) | ValueType::ExternalRecord(..),
+                ValueType::Record(..) | ValueType::ExternalRecord(..),
+            )
+            | (ValueType::Future(..), ValueType::Future(..)) => self.unify_value_types(a, b).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// Returns a human-readable rendering of the given value, using the struct and record names,
+    /// member names, and entry names declared in the given value type.
+    fn display_value(&self, value: &Value<N>, value_type: &ValueType<N>) -> Result<String> {
+        // Ensure the value matches the value type.
+        self.matches_value_type(value, value_type)?;
+
+        match (value, value_type) {
+            (Value::Plaintext(plaintext), ValueType::Constant(plaintext_type))
+            | (Value::Plaintext(plaintext), ValueType::Public(plaintext_type))
+            | (Value::Plaintext(plaintext), ValueType::Private(plaintext_type)) => {
+                self.display_plaintext_internal(plaintext, plaintext_type)
+            }
+            (Value::Record(record), ValueType::Record(record_name)) => {
+                self.display_record_internal(record, self.program().get_record(record_name)?)
+            }
+            // Render the external record using the external program, which declares its structs.
+            (Value::Record(..), ValueType::ExternalRecord(locator)) => self
+                .get_external_stack(locator.program_id())?
+                .display_value(value, &ValueType::Record(*locator.resource())),
+            (Value::Future(future), ValueType::Future(..)) => Ok(future.to_string()),
+            _ => bail!("A value does not match its declared value type '{value_type}'"),
+        }
+    }
+
+    /// Checks that the given plaintext is an array with no duplicate elements.
+    fn assert_array_elements_unique(&self, plaintext: &Plaintext<N>) -> Result<()> {
+        // Retrieve the array elements.
+        let Plaintext::Array(elements, ..) = plaintext else {
+            bail!("Expected an array, found '{plaintext}'")
+        };
+        // Ensure each element is unique, by comparing the bits of the elements,
+        // which are distinct for distinct values of the same type.
+        let mut seen = IndexSet::with_capacity(elements.len());
+        for element in elements {
+            if !seen.insert(element.to_bits_le()) {
+                bail!("Array element '{element}' is duplicated")
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the plaintext of the given entry in the given record, such as its `amount`.
+    /// The `owner` of the record is returned as an address literal.
+    fn extract_record_field(&self, record: &Record<N, Plaintext<N>>, field: &Identifier<N>) -> Result<Plaintext<N>> {
+        // Retrieve the owner, if it is requested.
+        if field == &Identifier::from_str("owner")? {
+            return Ok(Plaintext::from(Literal::Address(**record.owner())));
+        }
+        // Retrieve the entry.
+        match record.data().get(field) {
+            Some(Entry::Constant(plaintext) | Entry::Public(plaintext) | Entry::Private(plaintext)) => {
+                Ok(plaintext.clone())
+            }
+            None => bail!("Record does not contain an entry named '{field}'"),
+        }
+    }
+
+    /// Checks that the given circuit value ejects to a value of the same type as the given console value.
+    /// The values agree in type if they have the same literal types, struct members, array lengths,
+    /// record entries and visibilities, and future locators, regardless of the values themselves.
+    fn assert_type_agreement<A: circuit::Aleo<Network = N>>(
+        &self,
+        value: &Value<N>,
+        circuit_value: &circuit::Value<A>,
+    ) -> Result<()> {
+        use circuit::Eject;
+
+        // Eject the circuit value.
+        let circuit_value = circuit_value.eject_value();
+        // Ensure the types agree.
+        match Self::value_types_agree_internal(value, &circuit_value) {
+            true => Ok(()),
+            false => bail!("Circuit value '{circuit_value}' does not agree in type with console value '{value}'"),
+        }
+    }
+
+    /// Checks that the given record matches the layout of a `credits.aleo/credits` record,
+    /// and returns the amount of microcredits in the record.
+    fn matches_credits_record(&self, record: &Record<N, Plaintext<N>>) -> Result<u64> {
+        // Retrieve the layout of the credits record, from the credits stack if it is this stack or an import.
+        let credits_id = ProgramID::from_str("credits.aleo")?;
+        let record_name = Identifier::from_str("credits")?;
+        let record_type = match self.is_current_program(&credits_id) {
+            true => self.program().get_record(&record_name)?.clone(),
+            false => match self.get_external_program(&credits_id) {
+                Ok(credits) => credits.get_record(&record_name)?.clone(),
+                Err(_) => Program::<N>::credits()?.get_record(&record_name)?.clone(),
+            },
+        };
+        // Ensure the record matches the layout of the credits record.
+        if let Err(error) = self.matches_record_internal(record, &record_type, 0) {
+            bail!("Record is not a credits record: {error}")
+        }
+        // Retrieve the amount of microcredits.
+        match record.find(&[Identifier::from_str("microcredits")?])? {
+            Entry::Private(Plaintext::Literal(Literal::U64(microcredits), ..)) => Ok(*microcredits),
+            entry => bail!("Expected 'microcredits' to be a private u64, found '{entry}'"),
+        }
+    }
+
+    /// Checks that the given literal is an address, whose group element is on the curve and in the prime-order subgroup.
+    /// Addresses parsed from strings are always valid, but an address recovered from unchecked coordinates may not be.
+    fn matches_valid_address(&self, literal: &Literal<N>) -> Result<()> {
+        // Retrieve the address.
+        let Literal::Address(address) = literal else { bail!("Expected an address, found '{literal}'") };
+        // Ensure the group element is on the curve and in the prime-order subgroup.
+        let group = address.to_group().to_affine();
+        ensure!(group.is_on_curve(), "Address '{address}' is not on the curve");
+        ensure!(
+            group.is_in_correct_subgroup_assuming_on_curve(),
+            "Address '{address}' is not in the prime-order subgroup"
+        );
+        Ok(())
+    }
+
+    /// Checks that the given plaintext is a nested array whose length at each level matches the given dimensions,
+    /// where the first dimension is the length of the outermost array. The elements of the innermost arrays are not checked.
+    fn matches_array_dims(&self, plaintext: &Plaintext<N>, dims: &[u32]) -> Result<()> {
+        Self::matches_array_dims_internal(plaintext, dims, 0)
+    }
+
+    /// Checks that the provided members of the given plaintext match the layout of the given struct.
+    /// Members that are not provided are not checked, but every member of the plaintext must be declared in the struct.
+    fn matches_partial_struct(
+        &self,
+        plaintext: &Plaintext<N>,
+        struct_name: &Identifier<N>,
+        provided: &IndexSet<Identifier<N>>,
+    ) -> Result<()> {
+        // Retrieve the struct from the program.
+        let Ok(struct_) = self.program().get_struct(struct_name) else {
+            bail!("Struct '{struct_name}' is not defined in the program")
+        };
+        // Retrieve the struct members.
+        let Plaintext::Struct(members, ..) = plaintext else {
+            bail!("'{struct_name}' is invalid: expected struct, found '{plaintext}'")
+        };
+        // Ensure every member of the plaintext is declared in the struct.
+        if let Some(member_name) = members.keys().find(|member_name| !struct_.members().contains_key(*member_name)) {
+            bail!("'{member_name}' is not a member of '{struct_name}'")
+        }
+        // Ensure each provided member is present, and matches its declared type.
+        for member_name in provided {
+            let Some(member_type) = struct_.members().get(member_name) else {
+                bail!("'{member_name}' is not a member of '{struct_name}'")
+            };
+            let Some(member) = members.get(member_name) else {
+                bail!("Member '{member_name}' of '{struct_name}' is not provided")
+            };
+            if let Err(error) = self.matches_plaintext_internal(member, member_type, 1) {
+                bail!("Invalid member '{struct_name}.{member_name}': {error}")
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<N: Network> Stack<N> {
@@ -285,6 +704,291 @@ impl<N: Network> Stack<N> {
         }
     }
 
+    /// Returns the given plaintext, without any cached bit representations.
+    fn canonicalize_plaintext_internal(plaintext: Plaintext<N>, depth: usize) -> Result<Plaintext<N>> {
+        // If the depth exceeds the maximum depth, then the plaintext is invalid.
+        ensure!(depth <= N::MAX_DATA_DEPTH, "Plaintext exceeded maximum depth of {}", N::MAX_DATA_DEPTH);
+
+        Ok(match plaintext {
+            Plaintext::Literal(literal, ..) => Plaintext::from(literal),
+            Plaintext::Struct(members, ..) => Plaintext::Struct(
+                members
+                    .into_iter()
+                    .map(|(name, member)| Ok((name, Self::canonicalize_plaintext_internal(member, depth + 1)?)))
+                    .collect::<Result<_>>()?,
+                Default::default(),
+            ),
+            Plaintext::Array(elements, ..) => Plaintext::Array(
+                elements
+                    .into_iter()
+                    .map(|element| Self::canonicalize_plaintext_internal(element, depth + 1))
+                    .collect::<Result<_>>()?,
+                Default::default(),
+            ),
+        })
+    }
+
+    /// Returns the given future, with each argument canonicalized.
+    fn canonicalize_future_internal(future: &Future<N>, depth: usize) -> Result<Future<N>> {
+        // If the depth exceeds the maximum depth, then the future is invalid.
+        ensure!(depth <= N::MAX_DATA_DEPTH, "Future exceeded maximum depth of {}", N::MAX_DATA_DEPTH);
+
+        let arguments = future
+            .arguments()
+            .iter()
+            .map(|argument| match argument {
+                Argument::Plaintext(plaintext) => {
+                    Ok(Argument::Plaintext(Self::canonicalize_plaintext_internal(plaintext.clone(), depth + 1)?))
+                }
+                Argument::Future(future) => {
+                    Ok(Argument::Future(Self::canonicalize_future_internal(future, depth + 1)?))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Future::new(*future.program_id(), *future.function_name(), arguments))
+    }
+
+    /// Checks that the given plaintext is a nested array matching the given dimensions, starting at the given level.
+    fn matches_array_dims_internal(plaintext: &Plaintext<N>, dims: &[u32], level: usize) -> Result<()> {
+        // Retrieve the expected length at this level, or return if every level is checked.
+        let Some((expected, dims)) = dims.split_first() else { return Ok(()) };
+        // Retrieve the elements.
+        let Plaintext::Array(elements, ..) = plaintext else {
+            bail!("Expected an array at level {level}, found '{plaintext}'")
+        };
+        // Ensure the number of elements matches the expected length.
+        ensure!(
+            elements.len() == *expected as usize,
+            "Array at level {level} expects {expected} elements, found {}",
+            elements.len()
+        );
+        // Ensure each element matches the remaining dimensions.
+        for element in elements {
+            Self::matches_array_dims_internal(element, dims, level + 1)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the given JSON is structurally compatible with the given plaintext type.
+    /// The path locates the JSON in the outermost JSON, for error messages.
+    fn json_matches_plaintext_type_internal(
+        &self,
+        json: &serde_json::Value,
+        plaintext_type: &PlaintextType<N>,
+        path: &str,
+        depth: usize,
+    ) -> Result<()> {
+        // If the depth exceeds the maximum depth, then the JSON is invalid.
+        ensure!(depth <= N::MAX_DATA_DEPTH, "JSON exceeded maximum depth of {}", N::MAX_DATA_DEPTH);
+
+        match plaintext_type {
+            PlaintextType::Literal(literal_type) => {
+                let is_valid = match literal_type {
+                    LiteralType::Boolean => json.is_boolean(),
+                    LiteralType::U8 => json.as_u64().and_then(|n| u8::try_from(n).ok()).is_some(),
+                    LiteralType::U16 => json.as_u64().and_then(|n| u16::try_from(n).ok()).is_some(),
+                    LiteralType::U32 => json.as_u64().and_then(|n| u32::try_from(n).ok()).is_some(),
+                    LiteralType::U64 => json.as_u64().is_some(),
+                    LiteralType::U128 => {
+                        json.as_u64().is_some() || json.as_str().and_then(|s| s.parse::<u128>().ok()).is_some()
+                    }
+                    LiteralType::I8 => json.as_i64().and_then(|n| i8::try_from(n).ok()).is_some(),
+                    LiteralType::I16 => json.as_i64().and_then(|n| i16::try_from(n).ok()).is_some(),
+                    LiteralType::I32 => json.as_i64().and_then(|n| i32::try_from(n).ok()).is_some(),
+                    LiteralType::I64 => json.as_i64().is_some(),
+                    LiteralType::I128 => {
+                        json.as_i64().is_some() || json.as_str().and_then(|s| s.parse::<i128>().ok()).is_some()
+                    }
+                    LiteralType::Address
+                    | LiteralType::Field
+                    | LiteralType::Group
+                    | LiteralType::Scalar
+                    | LiteralType::Signature
+                    | LiteralType::String => json.is_string(),
+                };
+                ensure!(is_valid, "Expected a '{literal_type}' at '{path}', found '{json}'");
+            }
+            PlaintextType::Struct(struct_name) => {
+                let struct_ = self.program().get_struct(struct_name)?;
+                let Some(object) = json.as_object() else {
+                    bail!("Expected a '{struct_name}' object at '{path}', found '{json}'")
+                };
+                // Ensure every key is a member of the struct.
+                let is_member = |key: &str| struct_.members().keys().any(|member| member.to_string() == key);
+                if let Some(key) = object.keys().find(|key| !is_member(key)) {
+                    bail!("Unexpected member '{key}' at '{path}'")
+                }
+                // Ensure every member is present and compatible.
+                for (member, member_type) in struct_.members() {
+                    let Some(member_json) = object.get(&member.to_string()) else {
+                        bail!("Missing member '{member}' at '{path}'")
+                    };
+                    let member_path = format!("{path}.{member}");
+                    self.json_matches_plaintext_type_internal(member_json, member_type, &member_path, depth + 1)?;
+                }
+            }
+            PlaintextType::Array(array_type) => {
+                let Some(elements) = json.as_array() else { bail!("Expected an array at '{path}', found '{json}'") };
+                // Ensure the number of elements matches the declared length.
+                ensure!(
+                    elements.len() as u64 == **array_type.length() as u64,
+                    "Expected {} elements at '{path}', found {}",
+                    **array_type.length(),
+                    elements.len()
+                );
+                for (index, element) in elements.iter().enumerate() {
+                    let element_path = format!("{path}[{index}]");
+                    let element_type = array_type.next_element_type();
+                    self.json_matches_plaintext_type_internal(element, element_type, &element_path, depth + 1)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the given JSON is structurally compatible with the given record type.
+    fn json_matches_record_type_internal(&self, json: &serde_json::Value, record_type: &RecordType<N>) -> Result<()> {
+        let Some(object) = json.as_object() else {
+            bail!("Expected a '{}' record at '$', found '{json}'", record_type.name())
+        };
+        // Ensure every key is the owner, the nonce, or an entry of the record.
+        let is_entry = |key: &str| record_type.entries().keys().any(|entry| entry.to_string() == key);
+        if let Some(key) = object.keys().find(|key| !matches!(key.as_str(), "owner" | "_nonce") && !is_entry(key)) {
+            bail!("Unexpected entry '{key}' at '$'")
+        }
+        // Ensure the owner is present, along with the nonce, if it is provided.
+        match object.get("owner") {
+            Some(owner) => ensure!(owner.is_string(), "Expected an 'address' at '$.owner', found '{owner}'"),
+            None => bail!("Missing entry 'owner' at '$'"),
+        }
+        if let Some(nonce) = object.get("_nonce") {
+            ensure!(nonce.is_string(), "Expected a 'group' at '$._nonce', found '{nonce}'");
+        }
+        // Ensure every entry is present and compatible.
+        for (entry, entry_type) in record_type.entries() {
+            let Some(entry_json) = object.get(&entry.to_string()) else { bail!("Missing entry '{entry}' at '$'") };
+            let entry_path = format!("$.{entry}");
+            self.json_matches_plaintext_type_internal(entry_json, entry_type.plaintext_type(), &entry_path, 1)?;
+        }
+        Ok(())
+    }
+
+    /// Returns a JSON schema describing the given plaintext type, with the given visibility.
+    fn plaintext_type_schema_internal(&self, plaintext_type: &PlaintextType<N>, visibility: &str) -> serde_json::Value {
+        let mut schema = self.plaintext_type_layout_internal(plaintext_type);
+        schema["visibility"] = serde_json::Value::from(visibility);
+        schema
+    }
+
+    /// Returns a JSON schema describing the layout of the given plaintext type.
+    fn plaintext_type_layout_internal(&self, plaintext_type: &PlaintextType<N>) -> serde_json::Value {
+        match plaintext_type {
+            PlaintextType::Literal(literal_type) => {
+                serde_json::json!({ "kind": "literal", "type": literal_type.to_string() })
+            }
+            PlaintextType::Struct(struct_name) => {
+                let mut schema = serde_json::json!({ "kind": "struct", "name": struct_name.to_string() });
+                if let Ok(struct_type) = self.program().get_struct(struct_name) {
+                    schema["members"] = struct_type
+                        .members()
+                        .iter()
+                        .map(|(name, member_type)| {
+                            serde_json::json!({
+                                "name": name.to_string(),
+                                "type": self.plaintext_type_layout_internal(member_type),
+                            })
+                        })
+                        .collect();
+                }
+                schema
+            }
+            PlaintextType::Array(array_type) => serde_json::json!({
+                "kind": "array",
+                "length": **array_type.length(),
+                "element": self.plaintext_type_layout_internal(array_type.next_element_type()),
+            }),
+        }
+    }
+
+    /// Adds the owner and entries of the given record type to the given JSON schema.
+    fn record_type_schema_internal(&self, schema: &mut serde_json::Value, record_type: &RecordType<N>) {
+        schema["owner"] = match record_type.owner().is_public() {
+            true => "public".into(),
+            false => "private".into(),
+        };
+        schema["entries"] = record_type
+            .entries()
+            .iter()
+            .map(|(name, entry_type)| {
+                let entry_schema = match entry_type {
+                    EntryType::Constant(plaintext_type) => {
+                        self.plaintext_type_schema_internal(plaintext_type, "constant")
+                    }
+                    EntryType::Public(plaintext_type) => self.plaintext_type_schema_internal(plaintext_type, "public"),
+                    EntryType::Private(plaintext_type) => {
+                        self.plaintext_type_schema_internal(plaintext_type, "private")
+                    }
+                };
+                serde_json::json!({ "name": name.to_string(), "type": entry_schema })
+            })
+            .collect();
+    }
+
+    /// Returns a human-readable rendering of the given record, using the entry names of the record type.
+    fn display_record_internal(&self, record: &Record<N, Plaintext<N>>, record_type: &RecordType<N>) -> Result<String> {
+        let record_name = record_type.name();
+        // Render the owner, followed by each entry in the declared order.
+        let mut entries = vec![format!("owner: {}", **record.owner())];
+        for (entry_name, entry_type) in record_type.entries() {
+            let entry = record
+                .data()
+                .get(entry_name)
+                .ok_or_else(|| anyhow!("Entry '{entry_name}' does not exist in '{record_name}'"))?;
+            let (Entry::Constant(plaintext) | Entry::Public(plaintext) | Entry::Private(plaintext)) = entry;
+            let (EntryType::Constant(plaintext_type)
+            | EntryType::Public(plaintext_type)
+            | EntryType::Private(plaintext_type)) = entry_type;
+            entries.push(format!("{entry_name}: {}", self.display_plaintext_internal(plaintext, plaintext_type)?));
+        }
+        Ok(format!("{record_name} {{ {} }}", entries.join(", ")))
+    }
+
+    /// Returns a human-readable rendering of the given plaintext, using the member names of the plaintext type.
+    fn display_plaintext_internal(
+        &self,
+        plaintext: &Plaintext<N>,
+        plaintext_type: &PlaintextType<N>,
+    ) -> Result<String> {
+        match (plaintext, plaintext_type) {
+            (Plaintext::Literal(literal, ..), PlaintextType::Literal(..)) => Ok(literal.to_string()),
+            (Plaintext::Struct(members, ..), PlaintextType::Struct(struct_name)) => {
+                // Render each member in the declared order.
+                let members = self
+                    .program()
+                    .get_struct(struct_name)?
+                    .members()
+                    .iter()
+                    .map(|(member_name, member_type)| {
+                        let member = members
+                            .get(member_name)
+                            .ok_or_else(|| anyhow!("Member '{member_name}' does not exist in '{struct_name}'"))?;
+                        Ok(format!("{member_name}: {}", self.display_plaintext_internal(member, member_type)?))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(format!("{struct_name} {{ {} }}", members.join(", ")))
+            }
+            (Plaintext::Array(elements, ..), PlaintextType::Array(array_type)) => {
+                // Render each element with the element type.
+                let elements = elements
+                    .iter()
+                    .map(|element| self.display_plaintext_internal(element, array_type.next_element_type()))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(format!("[{}]", elements.join(", ")))
+            }
+            _ => bail!("Plaintext '{plaintext}' does not match '{plaintext_type}'"),
+        }
+    }
+
     /// Checks that the given future matches the layout of the future type.
     fn matches_future_internal(&self, future: &Future<N>, locator: &Locator<N>, depth: usize) -> Result<()> {
         // If the depth exceeds the maximum depth, then the future type is invalid.
@@ -297,7 +1001,7 @@ impl<N: Network> Stack<N> {
         ensure!(future.function_name() == locator.resource(), "Future name does not match");
 
         // Retrieve the associated function.
-        let function = match locator.program_id() == self.program_id() {
+        let function = match self.is_current_program(locator.program_id()) {
             true => self.get_function_ref(locator.resource())?,
             false => self.get_external_program(locator.program_id())?.get_function_ref(locator.resource())?,
         };
@@ -327,4 +1031,77 @@ impl<N: Network> Stack<N> {
 
         Ok(())
     }
+
+    /// Returns `true` if the given values have the same type.
+    fn value_types_agree_internal(a: &Value<N>, b: &Value<N>) -> bool {
+        match (a, b) {
+            (Value::Plaintext(a), Value::Plaintext(b)) => Self::plaintext_types_agree_internal(a, b),
+            (Value::Record(a), Value::Record(b)) => {
+                a.owner().is_public() == b.owner().is_public()
+                    && a.data().len() == b.data().len()
+                    && a.data().iter().zip_eq(b.data()).all(|((a_name, a_entry), (b_name, b_entry))| {
+                        a_name == b_name
+                            && match (a_entry, b_entry) {
+                                (Entry::Constant(a), Entry::Constant(b))
+                                | (Entry::Public(a), Entry::Public(b))
+                                | (Entry::Private(a), Entry::Private(b)) => Self::plaintext_types_agree_internal(a, b),
+                                _ => false,
+                            }
+                    })
+            }
+            (Value::Future(a), Value::Future(b)) => Self::future_types_agree_internal(a, b),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the given plaintexts have the same type.
+    fn plaintext_types_agree_internal(a: &Plaintext<N>, b: &Plaintext<N>) -> bool {
+        match (a, b) {
+            (Plaintext::Literal(a, ..), Plaintext::Literal(b, ..)) => a.to_type() == b.to_type(),
+            (Plaintext::Struct(a, ..), Plaintext::Struct(b, ..)) => {
+                a.len() == b.len()
+                    && a.iter().zip_eq(b).all(|((a_name, a_member), (b_name, b_member))| {
+                        a_name == b_name && Self::plaintext_types_agree_internal(a_member, b_member)
+                    })
+            }
+            (Plaintext::Array(a, ..), Plaintext::Array(b, ..)) => {
+                a.len() == b.len() && a.iter().zip_eq(b).all(|(a, b)| Self::plaintext_types_agree_internal(a, b))
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the given plaintext types have the same layout, comparing structs by their members.
+    fn plaintext_types_structurally_equal_internal(&self, a: &PlaintextType<N>, b: &PlaintextType<N>) -> bool {
+        match (a, b) {
+            (PlaintextType::Literal(a), PlaintextType::Literal(b)) => a == b,
+            (PlaintextType::Struct(a), PlaintextType::Struct(b)) => {
+                // Retrieve the structs from the program.
+                let (Ok(a), Ok(b)) = (self.program().get_struct(a), self.program().get_struct(b)) else {
+                    return false;
+                };
+                a.members().len() == b.members().len()
+                    && a.members().iter().zip_eq(b.members()).all(|((a_name, a_type), (b_name, b_type))| {
+                        a_name == b_name && self.plaintext_types_structurally_equal_internal(a_type, b_type)
+                    })
+            }
+            (PlaintextType::Array(a), PlaintextType::Array(b)) => {
+                a.length() == b.length()
+                    && self.plaintext_types_structurally_equal_internal(a.next_element_type(), b.next_element_type())
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the given futures have the same type.
+    fn future_types_agree_internal(a: &Future<N>, b: &Future<N>) -> bool {
+        a.program_id() == b.program_id()
+            && a.function_name() == b.function_name()
+            && a.arguments().len() == b.arguments().len()
+            && a.arguments().iter().zip_eq(b.arguments()).all(|(a, b)| match (a, b) {
+                (Argument::Plaintext(a), Argument::Plaintext(b)) => Self::plaintext_types_agree_internal(a, b),
+                (Argument::Future(a), Argument::Future(b)) => Self::future_types_agree_internal(a, b),
+                _ => false,
+            })
+    }
 }
@@ -14,7 +14,9 @@
 
 use super::*;
 
+mod analyze;
 mod initialize;
 mod matches;
 mod sample;
+mod substitute;
 mod synthesize;
@@ -0,0 +1,78 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network> StackProgramMut<N> for Stack<N> {
+    /// Substitutes the operand at the given index of the given instruction in the given function,
+    /// ensuring the new operand has the same register type as the operand it replaces.
+    ///
+    /// The register types of the function are recomputed, and the proving and verifying keys
+    /// of the function are discarded, as they no longer correspond to the function.
+    #[inline]
+    fn substitute_operand(
+        &mut self,
+        function_name: &Identifier<N>,
+        instruction_index: usize,
+        operand_index: usize,
+        new_operand: Operand<N>,
+    ) -> Result<()> {
+        // Retrieve the function.
+        let function = self.get_function_ref(function_name)?;
+        // Retrieve the instruction.
+        let instruction = function
+            .instructions()
+            .get(instruction_index)
+            .ok_or_else(|| anyhow!("Instruction {instruction_index} does not exist in '{function_name}'"))?;
+        // Retrieve the operand.
+        let operand = instruction
+            .operands()
+            .get(operand_index)
+            .ok_or_else(|| anyhow!("Operand {operand_index} does not exist in '{instruction}'"))?;
+
+        // Ensure the substitution preserves the register type of the operand.
+        let register_types = self.get_register_types(function_name)?;
+        let operand_type = register_types.get_type_from_operand(self, operand)?;
+        let new_operand_type = register_types.get_type_from_operand(self, &new_operand)?;
+        ensure!(
+            operand_type == new_operand_type,
+            "Cannot substitute '{operand}' with '{new_operand}', as '{operand_type}' does not match '{new_operand_type}'"
+        );
+
+        // Rebuild the instruction with the new operand, and swap it into a copy of the function.
+        let new_instruction = instruction.with_operand(operand_index, new_operand)?;
+        let mut function = function.clone();
+        function.replace_instruction(instruction_index, new_instruction)?;
+        // Swap the function into a copy of the program.
+        let mut program = self.program.clone();
+        program.replace_function(function)?;
+
+        // Recompute the register types of the function, which ensures the function remains well-typed.
+        let register_types = RegisterTypes::from_function(self, program.get_function_ref(function_name)?)?;
+
+        // Update the program and the register types.
+        self.program = program;
+        self.register_types.insert(*function_name, register_types);
+
+        // Discard the proving and verifying keys of the function.
+        // Note: The key maps are shared between clones of the stack, so they are copied before removal.
+        let mut proving_keys = self.proving_keys.read().clone();
+        proving_keys.shift_remove(function_name);
+        self.proving_keys = Arc::new(RwLock::new(proving_keys));
+        let mut verifying_keys = self.verifying_keys.read().clone();
+        verifying_keys.shift_remove(function_name);
+        self.verifying_keys = Arc::new(RwLock::new(verifying_keys));
+        Ok(())
+    }
+}
@@ -36,13 +36,14 @@ mod evaluate;
 mod execute;
 mod helpers;
 
-use crate::{traits::*, CallMetrics, Process, Trace};
+use crate::{cost_per_command, traits::*, CallMetrics, ExecutionOptions, Process, Trace};
 use console::{
     account::{Address, PrivateKey},
     network::prelude::*,
     program::{
         Entry,
         EntryType,
+        FinalizeType,
         Future,
         Identifier,
         Literal,
@@ -53,22 +54,40 @@ use console::{
         ProgramID,
         Record,
         RecordType,
+        Register,
         RegisterType,
         Request,
         Response,
         Value,
         ValueType,
+        TRANSACTION_DEPTH,
     },
-    types::{Field, Group},
+    types::{Field, Group, U16},
 };
 use ledger_block::{Deployment, Transition};
-use synthesizer_program::{traits::*, CallOperator, Closure, Function, Instruction, Operand, Program};
+use synthesizer_program::{
+    traits::*,
+    CallOperator,
+    CallSite,
+    Closure,
+    Command,
+    DeclarationDiff,
+    EntryVisibility,
+    Function,
+    Instruction,
+    Operand,
+    Program,
+    ProgramComplexity,
+    ProgramDiff,
+    ProgramSchema,
+    ProgramWarning,
+};
 use synthesizer_snark::{Certificate, ProvingKey, UniversalSRS, VerifyingKey};
 
 use aleo_std::prelude::{finish, lap, timer};
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 use parking_lot::RwLock;
-use std::sync::Arc;
+use std::{collections::BTreeSet, sync::Arc};
 
 #[cfg(not(feature = "serial"))]
 use rayon::prelude::*;
@@ -174,6 +193,8 @@ pub struct Stack<N: Network> {
     register_types: IndexMap<Identifier<N>, RegisterTypes<N>>,
     /// The mapping of finalize names to their register types.
     finalize_types: IndexMap<Identifier<N>, FinalizeTypes<N>>,
+    /// The mapping of closure and function names to their maximum depth of nested calls.
+    call_depths: IndexMap<Identifier<N>, usize>,
     /// The universal SRS.
     universal_srs: Arc<UniversalSRS<N>>,
     /// The mapping of function name to proving key.
@@ -243,7 +264,7 @@ impl<N: Network> StackProgram<N> for Stack<N> {
     /// Returns the external program for the given program ID.
     #[inline]
     fn get_external_program(&self, program_id: &ProgramID<N>) -> Result<&Program<N>> {
-        match self.program.id() == program_id {
+        match self.is_current_program(program_id) {
             true => bail!("Attempted to get the main program '{}' as an external program", self.program.id()),
             // Retrieve the external stack, and return the external program.
             false => Ok(self.get_external_stack(program_id)?.program()),
@@ -259,6 +280,12 @@ impl<N: Network> StackProgram<N> for Stack<N> {
         external_program.get_record(locator.resource())
     }
 
+    /// Returns the record type for the given record name, if it is declared in this program.
+    #[inline]
+    fn get_record_type(&self, record_name: &Identifier<N>) -> Result<RecordType<N>> {
+        self.program.get_record(record_name).cloned()
+    }
+
     /// Returns the function with the given function name.
     #[inline]
     fn get_function(&self, function_name: &Identifier<N>) -> Result<Function<N>> {
@@ -292,6 +319,629 @@ impl<N: Network> StackProgram<N> for Stack<N> {
         }
         Ok(num_calls)
     }
+
+    /// Returns the input registers of the given function name, paired with their register types.
+    #[inline]
+    fn input_register_types(&self, function_name: &Identifier<N>) -> Result<Vec<(Register<N>, RegisterType<N>)>> {
+        Ok(self
+            .get_function_ref(function_name)?
+            .inputs()
+            .iter()
+            .map(|input| (input.register().clone(), RegisterType::from(input.value_type().clone())))
+            .collect())
+    }
+
+    /// Returns the output operands of the given function name, paired with their register types.
+    #[inline]
+    fn output_register_types(&self, function_name: &Identifier<N>) -> Result<Vec<(Operand<N>, RegisterType<N>)>> {
+        Ok(self
+            .get_function_ref(function_name)?
+            .outputs()
+            .iter()
+            .map(|output| (output.operand().clone(), RegisterType::from(output.value_type().clone())))
+            .collect())
+    }
+
+    /// Returns the maximum depth of nested calls, starting from the given closure or function.
+    #[inline]
+    fn get_call_depth(&self, name: &Identifier<N>) -> Result<usize> {
+        // Retrieve the call depth, which is cached when the stack is initialized.
+        self.call_depths
+            .get(name)
+            .copied()
+            .ok_or_else(|| anyhow!("'{name}' is not a closure or function in '{}'", self.program.id()))
+    }
+
+    /// Returns a summary of the size and structure of the program.
+    #[inline]
+    fn complexity_summary(&self) -> Result<ProgramComplexity> {
+        // Retrieve the closures and functions.
+        let (closures, functions) = (self.program.closures(), self.program.functions());
+        // Count the instructions across all closures and functions.
+        let num_instructions = closures.values().map(|closure| closure.instructions().len()).sum::<usize>()
+            + functions.values().map(|function| function.instructions().len()).sum::<usize>();
+        // Count the commands across all finalize blocks.
+        let num_commands =
+            functions.values().filter_map(|function| function.finalize_logic()).map(|f| f.commands().len()).sum();
+        // Determine the maximum call depth across all closures and functions.
+        let max_call_depth = closures
+            .keys()
+            .chain(functions.keys())
+            .try_fold(0, |max_depth, name| Ok::<_, Error>(max_depth.max(self.get_call_depth(name)?)))?;
+
+        Ok(ProgramComplexity::new(
+            functions.len(),
+            closures.len(),
+            self.program.structs().len(),
+            self.program.records().len(),
+            self.program.mappings().len(),
+            num_instructions,
+            num_commands,
+            max_call_depth,
+        ))
+    }
+
+    /// Returns the closures and functions in this program that are reachable from the given entry, including itself.
+    #[inline]
+    fn reachable_resources(&self, entry: &Identifier<N>) -> Result<IndexSet<Identifier<N>>> {
+        // Initialize the set of reachable resources, and the queue of resources to visit.
+        let mut reachable = IndexSet::new();
+        let mut queue = vec![*entry];
+        while let Some(name) = queue.pop() {
+            // Skip the resource, if it has already been visited.
+            if !reachable.insert(name) {
+                continue;
+            }
+            // Enqueue the resources called from this program.
+            for instruction in self.get_instructions(&name)? {
+                if let Instruction::Call(call) = instruction {
+                    if let CallOperator::Resource(resource) = call.operator() {
+                        queue.push(*resource);
+                    }
+                }
+            }
+        }
+        Ok(reachable)
+    }
+
+    /// Checks that no register in the given closure or function is read before it is written.
+    /// Note that stack initialization already rejects such programs; this check reports the offending instruction index.
+    #[inline]
+    fn verify_no_read_before_write(&self, function_name: &Identifier<N>) -> Result<()> {
+        Self::verify_no_read_before_write_in(&self.program, function_name)
+    }
+
+    /// Returns the byte range of the given function in the source text of the program, if the function exists.
+    /// As the original source is not retained, the range is relative to the canonical source, `program.to_string()`.
+    #[inline]
+    fn function_source_span(&self, function_name: &Identifier<N>) -> Option<(usize, usize)> {
+        // Retrieve the function.
+        let function = self.program.functions().get(function_name)?;
+        // Locate the function in the canonical source, which contains each definition verbatim.
+        let function = function.to_string();
+        let start = self.program.to_string().find(&function)?;
+        Some((start, start + function.len()))
+    }
+
+    /// Returns every literal used as an operand in the closures, functions, and finalize scopes of the program.
+    #[inline]
+    fn constant_literals(&self) -> Vec<Literal<N>> {
+        // Returns the literals in the given operands.
+        let literals = |operands: &[Operand<N>]| {
+            operands
+                .iter()
+                .filter_map(|operand| match operand {
+                    Operand::Literal(literal) => Some(literal.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut constants = Vec::new();
+        // Collect the literals in the closures.
+        for closure in self.program.closures().values() {
+            for instruction in closure.instructions() {
+                constants.extend(literals(instruction.operands()));
+            }
+            for output in closure.outputs() {
+                constants.extend(literals(&[output.operand().clone()]));
+            }
+        }
+        // Collect the literals in the functions and their finalize scopes.
+        for function in self.program.functions().values() {
+            for instruction in function.instructions() {
+                constants.extend(literals(instruction.operands()));
+            }
+            for output in function.outputs() {
+                constants.extend(literals(&[output.operand().clone()]));
+            }
+            if let Some(finalize) = function.finalize_logic() {
+                for command in finalize.commands() {
+                    let operands = match command {
+                        Command::Instruction(instruction) => instruction.operands().to_vec(),
+                        Command::Contains(contains) => contains.operands(),
+                        Command::Get(get) => get.operands(),
+                        Command::GetOrUse(get_or_use) => get_or_use.operands(),
+                        Command::RandChaCha(rand_chacha) => rand_chacha.operands(),
+                        Command::Remove(remove) => remove.operands(),
+                        Command::Set(set) => set.operands(),
+                        Command::BranchEq(branch) => vec![branch.first().clone(), branch.second().clone()],
+                        Command::BranchNeq(branch) => vec![branch.first().clone(), branch.second().clone()],
+                        Command::Await(..) | Command::Position(..) => vec![],
+                    };
+                    constants.extend(literals(&operands));
+                }
+            }
+        }
+        constants
+    }
+
+    /// Returns the `(caller, callee)` pairs for every call to a closure or function in this program.
+    #[inline]
+    fn call_graph(&self) -> Vec<(Identifier<N>, Identifier<N>)> {
+        self.calls()
+            .filter_map(|(caller, operator)| match operator {
+                CallOperator::Resource(resource) => Some((*caller, *resource)),
+                CallOperator::Locator(..) => None,
+            })
+            .collect()
+    }
+
+    /// Returns the `(caller, callee)` pairs for every call to a function in an external program.
+    #[inline]
+    fn external_call_graph(&self) -> Vec<(Identifier<N>, Locator<N>)> {
+        self.calls()
+            .filter_map(|(caller, operator)| match operator {
+                CallOperator::Locator(locator) => Some((*caller, *locator)),
+                CallOperator::Resource(..) => None,
+            })
+            .collect()
+    }
+
+    /// Checks that no struct in the program refers to itself, either directly or through other structs.
+    /// Note that the parser requires structs to be defined before use, which already rules out cycles in parsed programs.
+    #[inline]
+    fn verify_acyclic_structs(&self) -> Result<()> {
+        Self::verify_acyclic_struct_types(self.program.structs())
+    }
+
+    /// Returns `true` if the finalize scope of the given function consumes randomness, such as `rand.chacha`.
+    /// If the function does not have a finalize scope, this method returns `false`.
+    #[inline]
+    fn finalize_uses_randomness(&self, function_name: &Identifier<N>) -> Result<bool> {
+        // Retrieve the finalize scope of the function.
+        let uses_randomness = match self.get_function_ref(function_name)?.finalize_logic() {
+            Some(finalize) => finalize.commands().iter().any(|command| matches!(command, Command::RandChaCha(..))),
+            None => false,
+        };
+        Ok(uses_randomness)
+    }
+
+    /// Returns the number of instructions in each function, paired with the number of commands in its finalize scope.
+    /// Functions without a finalize scope have a finalize count of zero.
+    #[inline]
+    fn instruction_counts(&self) -> IndexMap<Identifier<N>, (usize, usize)> {
+        self.program
+            .functions()
+            .iter()
+            .map(|(name, function)| {
+                let num_finalize_commands = function.finalize_logic().map_or(0, |finalize| finalize.commands().len());
+                (*name, (function.instructions().len(), num_finalize_commands))
+            })
+            .collect()
+    }
+
+    /// Returns the programs reachable through the imports of this program, in dependency order,
+    /// such that each program appears after the programs it imports.
+    #[inline]
+    fn transitive_imports(&self) -> Result<Vec<ProgramID<N>>> {
+        let mut imports = IndexMap::new();
+        self.visit_imports(&mut Vec::new(), &mut imports)?;
+        Ok(imports.into_keys().collect())
+    }
+
+    /// Checks that the program only uses the opcodes, call depth, and randomness permitted by the given schema.
+    #[inline]
+    fn validate_against_schema(&self, schema: &ProgramSchema) -> Result<()> {
+        // Ensure the closures and functions only use permitted opcodes.
+        let closures = self.program.closures().iter().map(|(name, closure)| (name, closure.instructions()));
+        let functions = self.program.functions().iter().map(|(name, function)| (name, function.instructions()));
+        for (name, instructions) in closures.chain(functions) {
+            for instruction in instructions {
+                let opcode = *instruction.opcode();
+                ensure!(schema.is_allowed_opcode(opcode), "Opcode '{opcode}' in '{name}' is not permitted");
+            }
+        }
+        for (name, function) in self.program.functions() {
+            if let Some(finalize) = function.finalize_logic() {
+                // Ensure the finalize scope only uses permitted opcodes.
+                for command in finalize.commands() {
+                    let opcode = *command.opcode();
+                    ensure!(
+                        schema.is_allowed_opcode(opcode),
+                        "Opcode '{opcode}' in the finalize scope of '{name}' is not permitted"
+                    );
+                }
+                // Ensure the finalize scope does not consume randomness, if it is not permitted.
+                ensure!(
+                    schema.allow_randomness() || !self.finalize_uses_randomness(name)?,
+                    "The finalize scope of '{name}' consumes randomness, which is not permitted"
+                );
+            }
+        }
+        // Ensure the call depth is within the limit.
+        let call_depth = self.complexity_summary()?.max_call_depth();
+        ensure!(
+            call_depth <= schema.max_call_depth(),
+            "The call depth of {call_depth} exceeds the permitted maximum of {}",
+            schema.max_call_depth()
+        );
+        Ok(())
+    }
+
+    /// Returns, for each output of the given function, the indices of the inputs that flow into it.
+    /// An input flows into an output if the output register is computed, transitively through the
+    /// instructions of the function, from the input register. Calls are treated conservatively,
+    /// such that every argument of a call flows into every destination of the call.
+    #[inline]
+    fn output_input_dependencies(&self, function_name: &Identifier<N>) -> Result<Vec<BTreeSet<usize>>> {
+        // Retrieve the function.
+        let function = self.get_function_ref(function_name)?;
+
+        // Initialize a map of register locators to the indices of the inputs that flow into them.
+        let mut dependencies = IndexMap::<u64, BTreeSet<usize>>::new();
+        for (index, input) in function.inputs().iter().enumerate() {
+            dependencies.insert(input.register().locator(), [index].into_iter().collect());
+        }
+
+        // Returns the indices of the inputs that flow into the given operand.
+        let operand_dependencies = |dependencies: &IndexMap<u64, BTreeSet<usize>>, operand: &Operand<N>| {
+            match operand {
+                Operand::Register(register) => dependencies.get(&register.locator()).cloned().unwrap_or_default(),
+                _ => BTreeSet::new(),
+            }
+        };
+
+        // Propagate the dependencies through the instructions.
+        for instruction in function.instructions() {
+            let instruction_dependencies = instruction
+                .operands()
+                .iter()
+                .flat_map(|operand| operand_dependencies(&dependencies, operand))
+                .collect::<BTreeSet<_>>();
+            for destination in instruction.destinations() {
+                dependencies.insert(destination.locator(), instruction_dependencies.clone());
+            }
+        }
+
+        // Return the dependencies of each output.
+        Ok(function.outputs().iter().map(|output| operand_dependencies(&dependencies, output.operand())).collect())
+    }
+
+    /// Returns a hash of the semantic content of the program, which is independent of its source formatting.
+    /// The hash is computed as the BHP-1024 hash of the byte encoding of the program, which encodes the
+    /// imports, mappings, structs, records, closures, and functions in declaration order, without the
+    /// whitespace or comments of the source.
+    #[inline]
+    fn semantic_hash(&self) -> Result<Field<N>> {
+        N::hash_bhp1024(&self.program.to_bytes_le()?.to_bits_le())
+    }
+
+    /// Returns the external record types consumed by the inputs of the given function,
+    /// in the order of the inputs and without duplicates.
+    #[inline]
+    fn external_record_dependencies(&self, function_name: &Identifier<N>) -> Result<Vec<Locator<N>>> {
+        let dependencies = self
+            .get_function_ref(function_name)?
+            .inputs()
+            .iter()
+            .filter_map(|input| match input.value_type() {
+                ValueType::ExternalRecord(locator) => Some(*locator),
+                _ => None,
+            })
+            .collect::<IndexSet<_>>();
+        Ok(dependencies.into_iter().collect())
+    }
+
+    /// Returns the identifiers of the proving and verifying keys required to execute the given function,
+    /// including the keys of the functions it calls, transitively.
+    ///
+    /// The keys of a function are identified by its function ID, `Hash(network_id, program_id, function_name)`,
+    /// which is the ID used to look up its verifying key during verification. The identifiers are returned
+    /// in call order, starting with the given function, and without duplicates.
+    #[inline]
+    fn required_key_ids(&self, function_name: &Identifier<N>) -> Result<Vec<Field<N>>> {
+        // Collect the function and the functions it calls.
+        let mut functions = IndexSet::new();
+        self.visit_functions(function_name, &mut functions)?;
+        // Compute the function ID of each function.
+        functions
+            .iter()
+            .map(|locator| {
+                N::hash_bhp1024(
+                    &(U16::<N>::new(N::ID), locator.program_id().name(), locator.program_id().network(), locator.resource())
+                        .to_bits_le(),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the call sites in the given function, with the callee, inputs, and outputs of each call.
+    /// Calls to closures in this program are located in this program.
+    #[inline]
+    fn call_sites(&self, function_name: &Identifier<N>) -> Result<Vec<CallSite<N>>> {
+        let call_sites = self
+            .get_function_ref(function_name)?
+            .instructions()
+            .iter()
+            .filter_map(|instruction| match instruction {
+                Instruction::Call(call) => {
+                    let callee = match call.operator() {
+                        CallOperator::Locator(locator) => *locator,
+                        CallOperator::Resource(resource) => Locator::new(*self.program_id(), *resource),
+                    };
+                    Some(CallSite::new(callee, call.operands().to_vec(), call.destinations()))
+                }
+                _ => None,
+            })
+            .collect();
+        Ok(call_sites)
+    }
+
+    /// Returns the mappings, structs, records, closures, and functions that differ between this program and the given program.
+    /// This program is treated as the old edition, and the given program as the new edition.
+    #[inline]
+    fn diff(&self, other: &Self) -> ProgramDiff<N> {
+        let (old, new) = (&self.program, &other.program);
+        ProgramDiff::new(
+            DeclarationDiff::new(old.mappings(), new.mappings()),
+            DeclarationDiff::new(old.structs(), new.structs()),
+            DeclarationDiff::new(old.records(), new.records()),
+            DeclarationDiff::new(old.closures(), new.closures()),
+            DeclarationDiff::new(old.functions(), new.functions()),
+        )
+    }
+
+    /// Returns the register locators of the given closure or function, grouped by their register type.
+    /// The types are in the order of their first definition, and the locators of each type are in ascending order.
+    #[inline]
+    fn registers_by_type(&self, function_name: &Identifier<N>) -> Result<IndexMap<RegisterType<N>, Vec<u64>>> {
+        Ok(self.get_register_types(function_name)?.registers_by_type())
+    }
+
+    /// Runs the structural checks of the program, and returns the warnings and errors they report.
+    /// The checks cover cyclic structs, registers read before they are written, inconsistent futures,
+    /// functions making more calls than fit in a transaction, and unused imports.
+    ///
+    /// The call limit is checked on the number of calls rather than their depth, as every call produces
+    /// a transition, and a transaction holds a bounded number of transitions. A chain of nested calls
+    /// is therefore rejected by the number of transitions it produces, before any bound on its depth.
+    ///
+    /// As stack initialization rejects most of these programs, use `Process::verify_program`
+    /// to check a program before its stack is built.
+    #[inline]
+    fn verify_program(&self) -> Result<Vec<ProgramWarning<N>>> {
+        Ok(Self::program_warnings(&self.program, |program_id| self.get_external_stack(program_id)))
+    }
+
+    /// Returns the number of bytes in the serialized form of one key-value pair of the given mapping.
+    /// The key is stored as a plaintext and the value as a plaintext value, so the value has one more byte for its variant.
+    /// Mappings with a `string` key or value are not supported, as their size is not determined by their type.
+    #[inline]
+    fn mapping_entry_size(&self, mapping_name: &Identifier<N>) -> Result<u64> {
+        // Retrieve the mapping.
+        let mapping = self.program.get_mapping(mapping_name)?;
+        // Compute the size of the key and value.
+        let num_key_bytes = self.plaintext_size_in_bytes(mapping.key().plaintext_type())?;
+        let num_value_bytes = 1 + self.plaintext_size_in_bytes(mapping.value().plaintext_type())?;
+        Ok(num_key_bytes + num_value_bytes)
+    }
+
+    /// Checks that no mapping key in the finalize scope of the given function is derived from `rand.chacha`.
+    /// A register is derived from `rand.chacha` if it is its destination, or the destination of an instruction
+    /// with such an operand. Values that only depend on randomness through a branch are not tracked.
+    #[inline]
+    fn verify_mapping_keys_deterministic(&self, function_name: &Identifier<N>) -> Result<()> {
+        // Retrieve the finalize scope, if it exists.
+        let Some(finalize) = self.get_function_ref(function_name)?.finalize_logic() else {
+            return Ok(());
+        };
+
+        // Initialize the set of registers derived from randomness.
+        let mut random = IndexSet::new();
+        let is_random = |random: &IndexSet<u64>, operand: &Operand<N>| match operand {
+            Operand::Register(register) => random.contains(&register.locator()),
+            _ => false,
+        };
+
+        for (index, command) in finalize.commands().iter().enumerate() {
+            // Ensure the mapping key, if any, is not derived from randomness.
+            let key = match command {
+                Command::Contains(contains) => Some(contains.key()),
+                Command::Get(get) => Some(get.key()),
+                Command::GetOrUse(get_or_use) => Some(get_or_use.key()),
+                Command::Remove(remove) => Some(remove.key()),
+                Command::Set(set) => Some(set.key()),
+                _ => None,
+            };
+            if let Some(key) = key {
+                if is_random(&random, key) {
+                    bail!("Command {index} ('{command}') in the finalize scope of '{function_name}' uses a key derived from 'rand.chacha'")
+                }
+            }
+            // Propagate the randomness to the destinations.
+            let is_derived = match command {
+                Command::RandChaCha(..) => true,
+                Command::Instruction(instruction) => {
+                    instruction.operands().iter().any(|operand| is_random(&random, operand))
+                }
+                Command::GetOrUse(get_or_use) => get_or_use.operands().iter().any(|operand| is_random(&random, operand)),
+                _ => false,
+            };
+            if is_derived {
+                random.extend(command.destinations().iter().map(|register| register.locator()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the entries of every record type in the program, including the owner, with their visibility.
+    #[inline]
+    fn record_type_layouts(&self) -> Result<IndexMap<Identifier<N>, RecordTypeLayout<N>>> {
+        self.program
+            .records()
+            .iter()
+            .map(|(name, record_type)| {
+                // Start with the owner, which is either public or private.
+                let owner = match record_type.owner().is_public() {
+                    true => EntryVisibility::Public,
+                    false => EntryVisibility::Private,
+                };
+                let mut layout = vec![(Identifier::from_str("owner")?, owner)];
+                // Add the entries, in the order they are declared.
+                layout.extend(record_type.entries().iter().map(|(entry, entry_type)| (*entry, entry_type.into())));
+                Ok((*name, layout))
+            })
+            .collect()
+    }
+
+    /// Returns the maximum number of registers of the given function that are live at the same time.
+    /// A register is live from the input or instruction that assigns it, through the last instruction or output that reads it.
+    #[inline]
+    fn peak_live_registers(&self, function_name: &Identifier<N>) -> Result<usize> {
+        // Compute the live range of each register.
+        let ranges = self.live_ranges(function_name)?;
+        // Count the live registers at each position, and return the maximum.
+        let num_positions = self.get_function_ref(function_name)?.instructions().len() + 2;
+        Ok((0..num_positions)
+            .map(|position| ranges.values().filter(|(start, end)| (*start..=*end).contains(&position)).count())
+            .max()
+            .unwrap_or(0))
+    }
+
+    /// Returns the index of the instruction in the given function that assigns the given register,
+    /// or `None` if the register is an input register.
+    #[inline]
+    fn register_definition(&self, function_name: &Identifier<N>, locator: u64) -> Result<Option<usize>> {
+        // Retrieve the function.
+        let function = self.get_function_ref(function_name)?;
+        // Return `None` if the register is an input register.
+        if function.inputs().iter().any(|input| input.register().locator() == locator) {
+            return Ok(None);
+        }
+        // Find the instruction that assigns the register.
+        match function
+            .instructions()
+            .iter()
+            .position(|instruction| instruction.destinations().iter().any(|register| register.locator() == locator))
+        {
+            Some(index) => Ok(Some(index)),
+            None => bail!("Register 'r{locator}' is not defined in '{function_name}'"),
+        }
+    }
+
+    /// Returns the declared type of the member at the given path in the given struct, where each member
+    /// of the path is a member of the struct before it, as in the access `r0.a.b`.
+    #[inline]
+    fn struct_member_path_type(&self, struct_name: &Identifier<N>, path: &[Identifier<N>]) -> Result<PlaintextType<N>> {
+        ensure!(!path.is_empty(), "The path to a member of '{struct_name}' must not be empty");
+        // Start from the given struct.
+        let mut plaintext_type = PlaintextType::Struct(*struct_name);
+        for member in path {
+            // Ensure the current type is a struct.
+            let PlaintextType::Struct(current) = &plaintext_type else {
+                bail!("Cannot access member '{member}' of '{plaintext_type}', as it is not a struct")
+            };
+            // Retrieve the type of the member.
+            plaintext_type = match self.program.get_struct(current)?.members().get(member) {
+                Some(member_type) => member_type.clone(),
+                None => bail!("Member '{member}' is not found in struct '{current}'"),
+            };
+        }
+        Ok(plaintext_type)
+    }
+
+    /// Returns the locators of the external finalize scopes awaited by the finalize scope of the given function,
+    /// in the order they are awaited. A function without a finalize scope does not await any finalize scopes.
+    #[inline]
+    fn finalize_awaits(&self, function_name: &Identifier<N>) -> Result<Vec<Locator<N>>> {
+        // Retrieve the finalize scope, if it exists.
+        let Some(finalize) = self.get_function_ref(function_name)?.finalize_logic() else {
+            return Ok(vec![]);
+        };
+        let finalize_types = self.get_finalize_types(function_name)?;
+        // Resolve the future awaited by each `await` command.
+        finalize
+            .commands()
+            .iter()
+            .filter_map(|command| match command {
+                Command::Await(await_) => Some(await_.register()),
+                _ => None,
+            })
+            .map(|register| match finalize_types.get_type(self, register)? {
+                FinalizeType::Future(locator) => Ok(locator),
+                FinalizeType::Plaintext(..) => {
+                    bail!("Register '{register}' in '{function_name}' is awaited, but is not a future")
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the number of bytes in the serialization of the instructions of the given function.
+    /// The inputs, outputs, and finalize scope of the function are not counted.
+    #[inline]
+    fn function_bytecode_size(&self, function_name: &Identifier<N>) -> Result<usize> {
+        self.get_function_ref(function_name)?
+            .instructions()
+            .iter()
+            .try_fold(0usize, |size, instruction| Ok(size + instruction.to_bytes_le()?.len()))
+    }
+
+    /// Returns the struct and record names that are defined in more than one program in the import closure
+    /// of this program, including this program, along with the programs that define each name.
+    #[inline]
+    fn detect_name_collisions(&self) -> Result<Vec<(Identifier<N>, Vec<ProgramID<N>>)>> {
+        // Retrieve the programs in the import closure, through the stacks that import them.
+        let mut imports = IndexMap::new();
+        self.visit_imports(&mut Vec::new(), &mut imports)?;
+        // Collect the programs that define each struct and record name.
+        let mut definitions = IndexMap::<Identifier<N>, Vec<ProgramID<N>>>::new();
+        for program in imports.into_values().chain([&self.program]) {
+            for name in program.structs().keys().chain(program.records().keys()) {
+                definitions.entry(*name).or_default().push(*program.id());
+            }
+        }
+        Ok(definitions.into_iter().filter(|(_, program_ids)| program_ids.len() > 1).collect())
+    }
+
+    /// Returns the index and the two operands of each `assert.eq` and `assert.neq` instruction in the given function,
+    /// in the order they appear.
+    #[inline]
+    fn assertions(&self, function_name: &Identifier<N>) -> Result<Vec<(usize, Operand<N>, Operand<N>)>> {
+        let function = self.get_function_ref(function_name)?;
+        Ok(function
+            .instructions()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, instruction)| match instruction {
+                Instruction::AssertEq(assert) => Some((index, assert.operands())),
+                Instruction::AssertNeq(assert) => Some((index, assert.operands())),
+                _ => None,
+            })
+            .map(|(index, operands)| (index, operands[0].clone(), operands[1].clone()))
+            .collect())
+    }
+
+    /// Returns the index and the cost in microcredits of each command in the finalize scope of the given function,
+    /// as the finalize scope is priced per command. A function without a finalize scope has no costs.
+    #[inline]
+    fn instruction_costs(&self, function_name: &Identifier<N>) -> Result<Vec<(usize, u64)>> {
+        // Retrieve the finalize scope, if it exists.
+        let Some(finalize) = self.get_function_ref(function_name)?.finalize_logic() else {
+            return Ok(vec![]);
+        };
+        finalize.commands().iter().enumerate().map(|(index, command)| Ok((index, cost_per_command(command)?))).collect()
+    }
 }
 
 impl<N: Network> StackProgramTypes<N> for Stack<N> {
@@ -308,6 +958,25 @@ impl<N: Network> StackProgramTypes<N> for Stack<N> {
         // Retrieve the finalize types.
         self.finalize_types.get(name).ok_or_else(|| anyhow!("Finalize types for '{name}' do not exist"))
     }
+
+    /// Derives the register types of every closure and function in the program, in parallel.
+    /// The derivations are independent, as each closure or function only depends on the declared
+    /// signatures of its callees, and not on their register types.
+    #[inline]
+    fn derive_all_register_types_parallel(&self) -> Result<IndexMap<Identifier<N>, RegisterTypes<N>>> {
+        // Derive the register types of the closures.
+        let closures = self.program.closures().values().collect::<Vec<_>>();
+        let closure_types = cfg_iter!(closures)
+            .map(|closure| Ok((*closure.name(), RegisterTypes::from_closure(self, closure)?)))
+            .collect::<Result<Vec<_>>>()?;
+        // Derive the register types of the functions.
+        let functions = self.program.functions().values().collect::<Vec<_>>();
+        let function_types = cfg_iter!(functions)
+            .map(|function| Ok((*function.name(), RegisterTypes::from_function(self, function)?)))
+            .collect::<Result<Vec<_>>>()?;
+        // Return the register types, in program order.
+        Ok(closure_types.into_iter().chain(function_types).collect())
+    }
 }
 
 impl<N: Network> Stack<N> {
@@ -143,7 +143,7 @@ impl<N: Network> RegisterTypes<N> {
             ensure!(
                 match function.outputs().last().map(|output| output.value_type()) {
                     Some(ValueType::Future(locator)) =>
-                        locator.program_id() == stack.program_id() && locator.resource() == function.name(),
+                        stack.is_current_program(locator.program_id()) && locator.resource() == function.name(),
                     _ => false,
                 },
                 "The last output of function '{}' must be a future associated with itself",
@@ -338,7 +338,7 @@ impl<N: Network> RegisterTypes<N> {
             }
             RegisterType::Future(locator) => {
                 // Ensure that the locator is defined.
-                match locator.program_id() == stack.program_id() {
+                match stack.is_current_program(locator.program_id()) {
                     true => stack.get_function(locator.resource())?,
                     false => stack.get_external_program(locator.program_id())?.get_function(locator.resource())?,
                 };
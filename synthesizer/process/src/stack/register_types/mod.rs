@@ -84,6 +84,15 @@ impl<N: Network> RegisterTypes<N> {
         self.inputs.contains_key(&register.locator())
     }
 
+    /// Returns the register locators grouped by their register type, in the order the registers are defined.
+    pub fn registers_by_type(&self) -> IndexMap<RegisterType<N>, Vec<u64>> {
+        let mut registers = IndexMap::<_, Vec<_>>::new();
+        for (locator, register_type) in self.inputs.iter().chain(&self.destinations) {
+            registers.entry(register_type.clone()).or_default().push(*locator);
+        }
+        registers
+    }
+
     /// Returns the register type of the given operand.
     pub fn get_type_from_operand(
         &self,
@@ -223,7 +232,7 @@ impl<N: Network> RegisterTypes<N> {
                 // Access the input to the future to output the register type and check that it is in bounds.
                 (RegisterRefType::Future(locator), Access::Index(index)) => {
                     // Retrieve the associated function.
-                    let function = match locator.program_id() == stack.program_id() {
+                    let function = match stack.is_current_program(locator.program_id()) {
                         true => stack.get_function_ref(locator.resource())?,
                         false => {
                             stack.get_external_program(locator.program_id())?.get_function_ref(locator.resource())?
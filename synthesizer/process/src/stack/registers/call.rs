@@ -20,4 +20,177 @@ impl<N: Network, A: circuit::Aleo<Network = N>> RegistersCall<N> for Registers<N
     fn call_stack(&self) -> CallStack<N> {
         self.call_stack.clone()
     }
+
+    /// Returns the options that the registers are run with.
+    #[inline]
+    fn options(&self) -> &ExecutionOptions<N> {
+        &self.options
+    }
+
+    /// Returns the estimated cost of calling the given closure or function on the given inputs.
+    /// The inputs are checked against the declared input types of the callee, but the callee is not
+    /// evaluated and no transition is produced.
+    #[inline]
+    fn simulate_call(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        callee: &Locator<N>,
+        inputs: &[Value<N>],
+    ) -> Result<CallCost<N>> {
+        // Retrieve the stack of the callee.
+        let substack = match stack.is_current_program(callee.program_id()) {
+            true => stack,
+            false => stack.get_external_stack(callee.program_id())?,
+        };
+        let resource = callee.resource();
+
+        // If the callee is a closure, check the inputs against the closure input types.
+        if let Some(closure) = substack.program().closures().get(resource) {
+            // Ensure the number of inputs matches the number of input statements.
+            ensure!(
+                closure.inputs().len() == inputs.len(),
+                "Expected {} inputs, found {}",
+                closure.inputs().len(),
+                inputs.len()
+            );
+            for (input, statement) in inputs.iter().zip_eq(closure.inputs()) {
+                substack.matches_register_type(input, statement.register_type())?;
+            }
+        }
+        // If the callee is a function, check the inputs against the function input types.
+        else if let Some(function) = substack.program().functions().get(resource) {
+            // Ensure the number of inputs matches the number of input statements.
+            ensure!(
+                function.inputs().len() == inputs.len(),
+                "Expected {} inputs, found {}",
+                function.inputs().len(),
+                inputs.len()
+            );
+            for (input, statement) in inputs.iter().zip_eq(function.inputs()) {
+                substack.matches_value_type(input, statement.value_type())?;
+            }
+        }
+        // Else, throw an error.
+        else {
+            bail!("'{callee}' is not a closure or function")
+        }
+
+        // Estimate the cost of the callee.
+        let (num_transitions, num_instructions, finalize_cost) =
+            estimate_cost(substack, callee.program_id(), resource, &mut HashMap::new())?;
+        Ok(CallCost::new(*callee.program_id(), *resource, num_transitions, num_instructions, finalize_cost))
+    }
+
+    /// Restricts the external calls, including those of nested calls, to the given set of programs.
+    #[inline]
+    fn set_call_allowlist(&mut self, allowed: BTreeSet<ProgramID<N>>) {
+        self.options.set_call_allowlist(allowed);
+    }
+
+    /// Checks that an external call to the given program is permitted by the call allowlist, if one is set.
+    /// As the callee may in turn call the programs it imports, these must also be permitted.
+    #[inline]
+    fn check_call_permitted(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        program_id: &ProgramID<N>,
+    ) -> Result<()> {
+        if let Some(allowed) = self.options.call_allowlist() {
+            // Ensure the program, and every program reachable through its imports, is permitted.
+            let imports = stack.get_external_stack(program_id)?.transitive_imports()?;
+            for program_id in imports.iter().chain([program_id]) {
+                ensure!(allowed.contains(program_id), "call to {program_id} not permitted");
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the maximum depth of nested calls, including the call itself.
+    #[inline]
+    fn set_max_call_depth(&mut self, max: usize) {
+        self.options.set_max_call_depth(max);
+    }
+
+    /// Checks that a call to the given closure or function does not exceed the maximum call depth, if one is set.
+    /// As nested calls are evaluated with fresh registers, the depth is measured from this call,
+    /// and includes the longest chain of calls made by the callee.
+    #[inline]
+    fn check_call_depth(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        resource: &Identifier<N>,
+    ) -> Result<()> {
+        if let Some(max) = self.options.max_call_depth() {
+            // The depth includes the call itself, in addition to the nested calls of the callee.
+            let call_depth = stack.get_call_depth(resource)? + 1;
+            ensure!(call_depth <= max, "max call depth {max} exceeded");
+        }
+        Ok(())
+    }
+
+    /// Sets the interceptor that is consulted before every call, including nested calls, once the call is permitted.
+    /// When evaluating, if the interceptor returns outputs, the callee is not evaluated and the outputs are used instead.
+    /// When executing, a call that the interceptor would intercept halts, as the callee must be executed.
+    #[inline]
+    fn set_call_interceptor(&mut self, interceptor: CallInterceptor<N>) {
+        self.options.set_call_interceptor(interceptor);
+    }
+
+    /// Returns the outputs of the call interceptor for the given callee and inputs, if one is set and intercepts the call.
+    #[inline]
+    fn intercept_call(&self, callee: &Locator<N>, inputs: &[Value<N>]) -> Option<Vec<Value<N>>> {
+        self.options.call_interceptor().and_then(|interceptor| (interceptor.lock())(callee, inputs))
+    }
+}
+
+/// Returns the number of transitions, the number of instructions, and the finalize cost in microcredits
+/// for the given closure or function, including those of nested calls.
+/// The cost of each callee is memoized by its locator, so that a callee reached by several calls is only estimated once.
+fn estimate_cost<N: Network>(
+    stack: &impl StackProgram<N>,
+    program_id: &ProgramID<N>,
+    resource: &Identifier<N>,
+    costs: &mut HashMap<Locator<N>, (usize, usize, u64)>,
+) -> Result<(usize, usize, u64)> {
+    // Return the memoized cost, if the callee was already estimated.
+    let locator = Locator::new(*program_id, *resource);
+    if let Some(cost) = costs.get(&locator) {
+        return Ok(*cost);
+    }
+
+    // A closure does not produce a transition, and may not contain calls.
+    if let Some(closure) = stack.program().closures().get(resource) {
+        let cost = (0, closure.instructions().len(), 0);
+        costs.insert(locator, cost);
+        return Ok(cost);
+    }
+
+    // Retrieve the function.
+    let function = stack.get_function_ref(resource)?;
+    // Compute the cost of the finalize scope, if one exists.
+    let finalize_cost = match function.finalize_logic() {
+        Some(finalize) => finalize.commands().iter().map(cost_per_command).sum::<Result<u64>>()?,
+        None => 0,
+    };
+    let mut cost = (1, function.instructions().len(), finalize_cost);
+
+    // Add the cost of each nested call.
+    for instruction in function.instructions() {
+        if let Instruction::Call(call) = instruction {
+            let (num_transitions, num_instructions, finalize_cost) = match call.operator() {
+                CallOperator::Locator(locator) => estimate_cost(
+                    stack.get_external_stack(locator.program_id())?,
+                    locator.program_id(),
+                    locator.resource(),
+                    costs,
+                )?,
+                CallOperator::Resource(resource) => estimate_cost(stack, program_id, resource, costs)?,
+            };
+            cost.0 += num_transitions;
+            cost.1 += num_instructions;
+            cost.2 = cost.2.saturating_add(finalize_cost);
+        }
+    }
+    costs.insert(locator, cost);
+    Ok(cost)
 }
@@ -0,0 +1,83 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network> FromBytes for TraceStep<N> {
+    /// Reads the trace step from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        match u8::read_le(&mut reader)? {
+            0 => Ok(Self::Load(Operand::read_le(&mut reader)?, Value::read_le(&mut reader)?)),
+            1 => Ok(Self::Store(Register::read_le(&mut reader)?, Value::read_le(&mut reader)?)),
+            2 => {
+                // Read the instruction.
+                let instruction = Instruction::read_le(&mut reader)?;
+                // Read the number of steps.
+                let num_steps = u64::read_le(&mut reader)?;
+                // Read the steps, through a trait object to bound the recursion of the reader type.
+                let reader: &mut dyn Read = &mut reader;
+                let steps = (0..num_steps).map(|_| TraceStep::read_le(&mut *reader)).collect::<IoResult<Vec<_>>>()?;
+                Ok(Self::Instruction(instruction, steps))
+            }
+            variant => Err(error(format!("Failed to deserialize trace step variant {variant}"))),
+        }
+    }
+}
+
+impl<N: Network> ToBytes for TraceStep<N> {
+    /// Writes the trace step to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        match self {
+            Self::Load(operand, value) => {
+                0u8.write_le(&mut writer)?;
+                operand.write_le(&mut writer)?;
+                value.write_le(&mut writer)
+            }
+            Self::Store(register, value) => {
+                1u8.write_le(&mut writer)?;
+                register.write_le(&mut writer)?;
+                value.write_le(&mut writer)
+            }
+            Self::Instruction(instruction, steps) => {
+                2u8.write_le(&mut writer)?;
+                instruction.write_le(&mut writer)?;
+                (steps.len() as u64).write_le(&mut writer)?;
+                // Write the steps, through a trait object to bound the recursion of the writer type.
+                let writer: &mut dyn Write = &mut writer;
+                steps.iter().try_for_each(|step| step.write_le(&mut *writer))
+            }
+        }
+    }
+}
+
+impl<N: Network> FromBytes for ExecutionTrace<N> {
+    /// Reads the execution trace from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the number of steps.
+        let num_steps = u64::read_le(&mut reader)?;
+        // Read the steps.
+        let steps = (0..num_steps).map(|_| TraceStep::read_le(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        Ok(Self::new(steps))
+    }
+}
+
+impl<N: Network> ToBytes for ExecutionTrace<N> {
+    /// Writes the execution trace to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the number of steps.
+        (self.steps.len() as u64).write_le(&mut writer)?;
+        // Write the steps.
+        self.steps.iter().try_for_each(|step| step.write_le(&mut writer))
+    }
+}
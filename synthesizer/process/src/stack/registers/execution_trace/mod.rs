@@ -0,0 +1,54 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod bytes;
+mod serialize;
+
+use console::{
+    network::prelude::*,
+    program::{Register, Value},
+};
+use synthesizer_program::{Instruction, Operand};
+
+/// A load or store performed by the registers, or an instruction with the loads and stores it performed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TraceStep<N: Network> {
+    /// The value loaded from the operand.
+    Load(Operand<N>, Value<N>),
+    /// The value stored in the register.
+    Store(Register<N>, Value<N>),
+    /// The instruction, with the values it loaded from its operands and stored in its destinations, in order.
+    Instruction(Instruction<N>, Vec<TraceStep<N>>),
+}
+
+/// The loads and stores performed by the registers, in execution order.
+/// The loads and stores of each instruction are grouped under the instruction, while those outside an instruction,
+/// such as the stores of the inputs and the loads of the outputs, are recorded as steps of their own.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecutionTrace<N: Network> {
+    /// The steps of the trace.
+    steps: Vec<TraceStep<N>>,
+}
+
+impl<N: Network> ExecutionTrace<N> {
+    /// Initializes a new execution trace from the given steps.
+    pub const fn new(steps: Vec<TraceStep<N>>) -> Self {
+        Self { steps }
+    }
+
+    /// Returns the steps of the trace.
+    pub fn steps(&self) -> &[TraceStep<N>] {
+        &self.steps
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network> Serialize for TraceStep<N> {
+    /// Serializes the trace step into string or bytes.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => {
+                let mut step = serializer.serialize_struct("TraceStep", 3)?;
+                match self {
+                    Self::Load(operand, value) => {
+                        step.serialize_field("kind", "load")?;
+                        step.serialize_field("operand", &operand.to_string())?;
+                        step.serialize_field("value", value)?;
+                    }
+                    Self::Store(register, value) => {
+                        step.serialize_field("kind", "store")?;
+                        step.serialize_field("register", register)?;
+                        step.serialize_field("value", value)?;
+                    }
+                    Self::Instruction(instruction, steps) => {
+                        step.serialize_field("kind", "instruction")?;
+                        step.serialize_field("instruction", &instruction.to_string())?;
+                        step.serialize_field("steps", steps)?;
+                    }
+                }
+                step.end()
+            }
+            false => ToBytesSerializer::serialize_with_size_encoding(self, serializer),
+        }
+    }
+}
+
+impl<'de, N: Network> Deserialize<'de> for TraceStep<N> {
+    /// Deserializes the trace step from a string or bytes.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => {
+                // Parse the trace step from a string into a value.
+                let mut step = serde_json::Value::deserialize(deserializer)?;
+                // Retrieve the kind.
+                let kind: String = DeserializeExt::take_from_value::<D>(&mut step, "kind")?;
+                match kind.as_str() {
+                    "load" => {
+                        // Retrieve the operand.
+                        let operand: String = DeserializeExt::take_from_value::<D>(&mut step, "operand")?;
+                        let operand = Operand::from_str(&operand).map_err(de::Error::custom)?;
+                        Ok(Self::Load(operand, DeserializeExt::take_from_value::<D>(&mut step, "value")?))
+                    }
+                    "store" => Ok(Self::Store(
+                        DeserializeExt::take_from_value::<D>(&mut step, "register")?,
+                        DeserializeExt::take_from_value::<D>(&mut step, "value")?,
+                    )),
+                    "instruction" => {
+                        // Retrieve the instruction.
+                        let instruction: String = DeserializeExt::take_from_value::<D>(&mut step, "instruction")?;
+                        let instruction = Instruction::from_str(&instruction).map_err(de::Error::custom)?;
+                        Ok(Self::Instruction(instruction, DeserializeExt::take_from_value::<D>(&mut step, "steps")?))
+                    }
+                    _ => Err(de::Error::custom(format!("Invalid trace step kind '{kind}'"))),
+                }
+            }
+            false => FromBytesDeserializer::<Self>::deserialize_with_size_encoding(deserializer, "trace step"),
+        }
+    }
+}
+
+impl<N: Network> Serialize for ExecutionTrace<N> {
+    /// Serializes the execution trace into string or bytes.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => {
+                let mut trace = serializer.serialize_struct("ExecutionTrace", 1)?;
+                trace.serialize_field("steps", &self.steps)?;
+                trace.end()
+            }
+            false => ToBytesSerializer::serialize_with_size_encoding(self, serializer),
+        }
+    }
+}
+
+impl<'de, N: Network> Deserialize<'de> for ExecutionTrace<N> {
+    /// Deserializes the execution trace from a string or bytes.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => {
+                // Parse the execution trace from a string into a value.
+                let mut trace = serde_json::Value::deserialize(deserializer)?;
+                // Retrieve the steps.
+                Ok(Self::new(DeserializeExt::take_from_value::<D>(&mut trace, "steps")?))
+            }
+            false => FromBytesDeserializer::<Self>::deserialize_with_size_encoding(deserializer, "execution trace"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::network::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    /// Returns a sample execution trace.
+    fn sample_trace() -> ExecutionTrace<CurrentNetwork> {
+        ExecutionTrace::new(vec![
+            TraceStep::Load(Operand::from_str("r0").unwrap(), Value::from_str("1field").unwrap()),
+            TraceStep::Load(Operand::from_str("2field").unwrap(), Value::from_str("2field").unwrap()),
+            TraceStep::Store(Register::from_str("r1").unwrap(), Value::from_str("{ x: 3field }").unwrap()),
+            TraceStep::Instruction(Instruction::from_str("add r0 2field into r2;").unwrap(), vec![
+                TraceStep::Load(Operand::from_str("r0").unwrap(), Value::from_str("1field").unwrap()),
+                TraceStep::Load(Operand::from_str("2field").unwrap(), Value::from_str("2field").unwrap()),
+                TraceStep::Store(Register::from_str("r2").unwrap(), Value::from_str("3field").unwrap()),
+            ]),
+        ])
+    }
+
+    #[test]
+    fn test_serde_json() -> Result<()> {
+        let expected = sample_trace();
+
+        // Serialize
+        let candidate_string = serde_json::to_string(&expected)?;
+        assert!(candidate_string.contains(r#""kind":"load","operand":"r0""#));
+
+        // Deserialize
+        assert_eq!(expected, serde_json::from_str(&candidate_string)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bincode() -> Result<()> {
+        let expected = sample_trace();
+
+        // Serialize
+        let expected_bytes = expected.to_bytes_le()?;
+        let expected_bytes_with_size_encoding = bincode::serialize(&expected)?;
+        assert_eq!(&expected_bytes[..], &expected_bytes_with_size_encoding[8..]);
+
+        // Deserialize
+        assert_eq!(expected, ExecutionTrace::read_le(&expected_bytes[..])?);
+        assert_eq!(expected, bincode::deserialize(&expected_bytes_with_size_encoding[..])?);
+
+        Ok(())
+    }
+}
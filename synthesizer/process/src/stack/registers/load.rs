@@ -22,6 +22,64 @@ impl<N: Network, A: circuit::Aleo<Network = N>> RegistersLoad<N> for Registers<N
     /// In the case of register accesses, this method will halt if the access is not found.
     #[inline]
     fn load(&self, stack: &(impl StackMatches<N> + StackProgram<N>), operand: &Operand<N>) -> Result<Value<N>> {
+        // Load the value.
+        let value = self.load_internal(stack, operand)?;
+        // If recording, record the loaded value.
+        if let Some(recording) = self.trace.borrow_mut().as_mut() {
+            recording.push(TraceStep::Load(operand.clone(), value.clone()));
+        }
+        Ok(value)
+    }
+
+    /// Loads the value of a given operand, and returns it with whether it is an input, computed, or literal value.
+    ///
+    /// # Errors
+    /// This method will halt if the register locator is not found.
+    /// In the case of register accesses, this method will halt if the access is not found.
+    #[inline]
+    fn load_with_origin(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+    ) -> Result<(Value<N>, RegisterOrigin)> {
+        let origin = RegisterOrigin::from_operand(operand, |register| self.register_types.is_input(register));
+        Ok((self.load(stack, operand)?, origin))
+    }
+
+    /// Returns the plaintext type of a given operand, without loading its value.
+    ///
+    /// # Errors
+    /// This method will halt if the register type is not found.
+    /// This method will halt if the operand is not a plaintext.
+    #[inline]
+    fn operand_plaintext_type(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+    ) -> Result<PlaintextType<N>> {
+        // Retrieve the register type of the operand.
+        match self.register_types.get_type_from_operand(stack, operand)? {
+            RegisterType::Plaintext(plaintext_type) => Ok(plaintext_type),
+            register_type => bail!("Operand '{operand}' is a '{register_type}', not a plaintext"),
+        }
+    }
+}
+
+impl<N: Network, A: circuit::Aleo<Network = N>> Registers<N, A> {
+    /// Loads the value of a given operand from the registers, without recording it.
+    ///
+    /// # Errors
+    /// This method will halt if the register locator is not found.
+    /// In the case of register accesses, this method will halt if the access is not found.
+    #[inline]
+    fn load_internal(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+    ) -> Result<Value<N>> {
+        // Consume a step from the step budget.
+        self.consume_step()?;
+
         // Retrieve the register.
         let register = match operand {
             // If the operand is a literal, return the literal.
@@ -89,6 +147,9 @@ impl<N: Network, A: circuit::Aleo<Network = N>> RegistersLoadCircuit<N, A> for R
         stack: &(impl StackMatches<N> + StackProgram<N>),
         operand: &Operand<N>,
     ) -> Result<circuit::Value<A>> {
+        // Consume a step from the step budget.
+        self.consume_step()?;
+
         use circuit::Inject;
 
         // Retrieve the register.
@@ -163,4 +224,37 @@ impl<N: Network, A: circuit::Aleo<Network = N>> RegistersLoadCircuit<N, A> for R
 
         Ok(circuit_value)
     }
+
+    /// Loads the value of a given operand, sharing the bit decomposition of a plaintext register across loads.
+    ///
+    /// The bit decomposition is computed once on the stored register, and reused by every subsequent load of
+    /// the register (or its members) in these registers. As this changes the synthesized constraints, callers
+    /// must use it consistently when synthesizing the circuit keys and when proving.
+    ///
+    /// The decomposition is computed eagerly, on the first shared load of the register, and covers the whole
+    /// stored plaintext. This adds the constraints of decomposing every member of the register (e.g. a field
+    /// member is decomposed into its bits), even if only one member is loaded, or if the loaded value is never
+    /// decomposed. It should only be used for operands of bit-level instructions, such as the hash and commit
+    /// instructions, which decompose the whole value anyway.
+    ///
+    /// # Errors
+    /// This method will halt if the register locator is not found.
+    /// In the case of register accesses, this method will halt if the access is not found.
+    #[inline]
+    fn load_circuit_shared(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+    ) -> Result<circuit::Value<A>> {
+        use circuit::prelude::ToBits;
+
+        // Populate the cached bit decomposition of the stored plaintext, so that loaded copies inherit it.
+        if let Operand::Register(register) = operand {
+            if let Some(circuit::Value::Plaintext(plaintext)) = self.circuit_registers.get(&register.locator()) {
+                let _ = plaintext.to_bits_le();
+            }
+        }
+        // Load the value.
+        self.load_circuit(stack, operand)
+    }
 }
@@ -12,19 +12,48 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod execution_trace;
+pub use execution_trace::*;
+
 mod call;
 mod caller;
 mod load;
+mod recorder;
+use recorder::TraceRecording;
 mod store;
 
-use crate::{CallStack, RegisterTypes, RegistersCall};
+use crate::{
+    cost_per_command,
+    CallCost,
+    CallInterceptor,
+    CallStack,
+    ExecutionOptions,
+    RegisterTypes,
+    RegistersCall,
+    RegistersRecorder,
+};
 use console::{
     network::prelude::*,
-    program::{Entry, Literal, Plaintext, Register, Value},
+    program::{
+        Entry,
+        Identifier,
+        Literal,
+        Locator,
+        Plaintext,
+        PlaintextType,
+        ProgramID,
+        Register,
+        RegisterType,
+        Value,
+    },
     types::{Address, Field},
 };
 use synthesizer_program::{
+    CallOperator,
+    Instruction,
     Operand,
+    RecordObserver,
+    RegisterOrigin,
     RegistersLoad,
     RegistersLoadCircuit,
     RegistersSigner,
@@ -36,17 +65,26 @@ use synthesizer_program::{
 };
 
 use indexmap::IndexMap;
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeSet, HashMap},
+    rc::Rc,
+};
 
+/// The registers of a closure or function scope.
+///
+/// Note that the registers are not `Send`, as the circuit values that they hold are not `Send`.
+/// The state that is shared with the registers of nested calls is held in the execution options, which are `Send`.
 #[derive(Clone)]
 pub struct Registers<N: Network, A: circuit::Aleo<Network = N>> {
     /// The current call stack.
     call_stack: CallStack<N>,
     /// The mapping of all registers to their defined types.
     register_types: RegisterTypes<N>,
-    /// The mapping of assigned console registers to their values.
-    console_registers: IndexMap<u64, Value<N>>,
-    /// The mapping of assigned circuit registers to their values.
-    circuit_registers: IndexMap<u64, circuit::Value<A>>,
+    /// The mapping of assigned console registers to their values, shared with forks until written.
+    console_registers: Rc<IndexMap<u64, Value<N>>>,
+    /// The mapping of assigned circuit registers to their values, shared with forks until written.
+    circuit_registers: Rc<IndexMap<u64, circuit::Value<A>>>,
     /// The transition signer.
     signer: Option<Address<N>>,
     /// The transition signer, as a circuit.
@@ -59,6 +97,12 @@ pub struct Registers<N: Network, A: circuit::Aleo<Network = N>> {
     tvk: Option<Field<N>>,
     /// The transition view key, as a circuit.
     tvk_circuit: Option<circuit::Field<A>>,
+    /// The options that the registers are run with, which are inherited by the registers of nested calls.
+    options: ExecutionOptions<N>,
+    /// The remaining number of loads and stores, if a step budget is set.
+    step_budget: Cell<Option<u64>>,
+    /// The loads and stores performed by the registers, if recording.
+    trace: RefCell<Option<TraceRecording<N>>>,
 }
 
 impl<N: Network, A: circuit::Aleo<Network = N>> Registers<N, A> {
@@ -68,24 +112,63 @@ impl<N: Network, A: circuit::Aleo<Network = N>> Registers<N, A> {
         Self {
             call_stack,
             register_types,
-            console_registers: IndexMap::new(),
-            circuit_registers: IndexMap::new(),
+            console_registers: Rc::new(IndexMap::new()),
+            circuit_registers: Rc::new(IndexMap::new()),
             signer: None,
             signer_circuit: None,
             caller: None,
             caller_circuit: None,
             tvk: None,
             tvk_circuit: None,
+            options: ExecutionOptions::default(),
+            step_budget: Cell::new(None),
+            trace: RefCell::new(None),
         }
     }
 
+    /// Sets the options that the registers are run with, which are inherited by the registers of nested calls.
+    #[inline]
+    pub fn set_options(&mut self, options: &ExecutionOptions<N>) {
+        self.step_budget.set(options.step_budget());
+        self.options = options.clone();
+    }
+
+    /// Sets the step budget, which is the number of loads and stores allowed before the registers halt.
+    /// The budget is inherited by the registers of nested calls, each of which is allowed the same number of steps.
+    #[inline]
+    pub fn set_step_budget(&mut self, steps: u64) {
+        self.options.set_step_budget(steps);
+        self.step_budget.set(Some(steps));
+    }
+
+    /// Returns a fork of the registers, which shares the assigned registers with these registers until either is written.
+    /// Writes to the fork do not affect these registers, and writes to these registers do not affect the fork.
+    /// The copy-on-write is of the whole map, so the first write to either copies every assigned register.
+    ///
+    /// The fork has an independent replica of the call stack, so calls in the fork do not consume the requests
+    /// of these registers. The options are shared, as are the observers and interceptors that they hold.
+    #[inline]
+    pub fn fork(&self) -> Self {
+        Self { call_stack: self.call_stack.replicate(), ..self.clone() }
+    }
+
+    /// Consumes one step from the step budget, if one is set.
+    #[inline]
+    fn consume_step(&self) -> Result<()> {
+        if let Some(steps) = self.step_budget.get() {
+            ensure!(steps > 0, "step budget exhausted");
+            self.step_budget.set(Some(steps - 1));
+        }
+        Ok(())
+    }
+
     /// Ensure the console and circuit registers match.
     #[inline]
     pub fn ensure_console_and_circuit_registers_match(&self) -> Result<()> {
         use circuit::Eject;
 
         for ((console_index, console_register), (circuit_index, circuit_register)) in
-            self.console_registers.iter().zip_eq(&self.circuit_registers)
+            self.console_registers.iter().zip_eq(self.circuit_registers.iter())
         {
             // Ensure the console and circuit index match (executed in same order).
             if *console_index != *circuit_index {
@@ -98,4 +181,22 @@ impl<N: Network, A: circuit::Aleo<Network = N>> Registers<N, A> {
         }
         Ok(())
     }
+
+    /// Returns `true` if the given registers have the same assigned locators, with the same values.
+    /// Circuit registers are compared by their ejected values, as circuit values do not implement equality.
+    #[inline]
+    pub fn registers_equal(&self, other: &Self) -> bool {
+        use circuit::Eject;
+
+        // Ensure the console registers match.
+        if self.console_registers != other.console_registers {
+            return false;
+        }
+        // Ensure the circuit registers match.
+        self.circuit_registers.len() == other.circuit_registers.len()
+            && self.circuit_registers.iter().all(|(locator, circuit_register)| {
+                other.circuit_registers.get(locator).map(|other| other.eject_value())
+                    == Some(circuit_register.eject_value())
+            })
+    }
 }
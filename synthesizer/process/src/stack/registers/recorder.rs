@@ -0,0 +1,77 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// The steps recorded by the registers, and whether the last step is an instruction that is still being evaluated.
+#[derive(Clone)]
+pub(super) struct TraceRecording<N: Network> {
+    /// The steps recorded so far.
+    steps: Vec<TraceStep<N>>,
+    /// Whether the last step is an instruction, to which loads and stores are recorded.
+    in_instruction: bool,
+}
+
+impl<N: Network> TraceRecording<N> {
+    /// Initializes a new recording, without any steps.
+    pub(super) const fn new() -> Self {
+        Self { steps: Vec::new(), in_instruction: false }
+    }
+
+    /// Records the given load or store, under the current instruction, if any.
+    pub(super) fn push(&mut self, step: TraceStep<N>) {
+        match (self.in_instruction, self.steps.last_mut()) {
+            (true, Some(TraceStep::Instruction(_, steps))) => steps.push(step),
+            _ => self.steps.push(step),
+        }
+    }
+
+    /// Discards the recorded steps.
+    pub(super) fn clear(&mut self) {
+        self.steps.clear();
+        self.in_instruction = false;
+    }
+}
+
+impl<N: Network, A: circuit::Aleo<Network = N>> RegistersRecorder<N> for Registers<N, A> {
+    /// Starts recording the loads and stores performed by the registers, discarding any previous trace.
+    #[inline]
+    fn start_recording(&mut self) {
+        *self.trace.get_mut() = Some(TraceRecording::new());
+    }
+
+    /// Records the given instruction, if recording. The subsequent loads and stores are recorded under the instruction,
+    /// until `end_instruction` is called.
+    #[inline]
+    fn begin_instruction(&mut self, instruction: &Instruction<N>) {
+        if let Some(recording) = self.trace.get_mut() {
+            recording.steps.push(TraceStep::Instruction(instruction.clone(), Vec::new()));
+            recording.in_instruction = true;
+        }
+    }
+
+    /// Ends the current instruction, if recording, so that the subsequent loads and stores are recorded as steps of their own.
+    #[inline]
+    fn end_instruction(&mut self) {
+        if let Some(recording) = self.trace.get_mut() {
+            recording.in_instruction = false;
+        }
+    }
+
+    /// Returns the trace recorded since recording started, and stops recording.
+    #[inline]
+    fn take_trace(&mut self) -> ExecutionTrace<N> {
+        ExecutionTrace::new(self.trace.get_mut().take().map(|recording| recording.steps).unwrap_or_default())
+    }
+}
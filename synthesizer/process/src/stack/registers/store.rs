@@ -28,6 +28,9 @@ impl<N: Network, A: circuit::Aleo<Network = N>> RegistersStore<N> for Registers<
         register: &Register<N>,
         stack_value: Value<N>,
     ) -> Result<()> {
+        // Consume a step from the step budget.
+        self.consume_step()?;
+
         match register {
             Register::Locator(locator) => {
                 // Ensure the register assignments are monotonically increasing.
@@ -48,17 +51,147 @@ impl<N: Network, A: circuit::Aleo<Network = N>> RegistersStore<N> for Registers<
                 };
 
                 // Store the stack value.
-                match self.console_registers.insert(*locator, stack_value) {
+                let console_registers = Rc::make_mut(&mut self.console_registers);
+                if console_registers.insert(*locator, stack_value).is_some() {
                     // Ensure the register has not been previously stored.
-                    Some(..) => bail!("Attempted to write to register '{register}' again"),
-                    // Return on success.
-                    None => Ok(()),
+                    bail!("Attempted to write to register '{register}' again")
+                }
+
+                // If recording, record the stored value.
+                if let (Some(recording), Some(stack_value)) = (self.trace.get_mut(), console_registers.get(locator)) {
+                    recording.push(TraceStep::Store(register.clone(), stack_value.clone()));
                 }
+
+                // If the stored value is a record, notify the record observer.
+                if let (Some(observer), Some(Value::Record(record))) =
+                    (self.options.record_observer(), console_registers.get(locator))
+                {
+                    (observer.lock())(record);
+                }
+                Ok(())
             }
             // Ensure the register is not a register access.
             Register::Access(..) => bail!("Cannot store to a register access: '{register}'"),
         }
     }
+
+    /// Assigns the given value to the given register, assuming the register is not already assigned,
+    /// and returns a reference to the stored value.
+    ///
+    /// # Errors
+    /// This method will halt if the given register is a register access.
+    /// This method will halt if the given register is an input register.
+    /// This method will halt if the register is already used.
+    #[inline]
+    fn store_and_return(
+        &mut self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        register: &Register<N>,
+        stack_value: Value<N>,
+    ) -> Result<&Value<N>> {
+        // Store the value.
+        self.store(stack, register, stack_value)?;
+        // Return the stored value.
+        self.console_registers.get(&register.locator()).ok_or_else(|| anyhow!("'{register}' does not exist"))
+    }
+
+    /// Sets the observer that is invoked with every record stored in the registers, after the record is stored.
+    /// The observer is also invoked with the records stored in the registers of nested calls.
+    #[inline]
+    fn set_record_observer(&mut self, observer: RecordObserver<N>) {
+        self.options.set_record_observer(observer);
+    }
+
+    /// Shrinks the storage of the registers to fit the assigned registers.
+    /// This method does not change the value of any assigned register.
+    #[inline]
+    fn compact(&mut self) {
+        Rc::make_mut(&mut self.console_registers).shrink_to_fit();
+        Rc::make_mut(&mut self.circuit_registers).shrink_to_fit();
+    }
+
+    /// Exchanges the values assigned to the given registers, which must be assigned destination registers of the same type.
+    /// The circuit values are exchanged along with the console values, if they are assigned.
+    ///
+    /// # Errors
+    /// This method will halt if either register is an input register.
+    /// This method will halt if either register is not assigned.
+    /// This method will halt if the registers are not of the same type.
+    #[inline]
+    fn swap_registers(&mut self, stack: &(impl StackMatches<N> + StackProgram<N>), a: u64, b: u64) -> Result<()> {
+        // Ensure neither register is an input register.
+        for locator in [a, b] {
+            ensure!(
+                !self.register_types.is_input(&Register::Locator(locator)),
+                "Cannot swap input register 'r{locator}'"
+            );
+        }
+        // Ensure the registers are of the same type.
+        let type_a = self.register_types.get_type(stack, &Register::Locator(a))?;
+        let type_b = self.register_types.get_type(stack, &Register::Locator(b))?;
+        ensure!(type_a == type_b, "Cannot swap 'r{a}' of type '{type_a}' with 'r{b}' of type '{type_b}'");
+        // Retrieve the console values.
+        let (Some(value_a), Some(value_b)) = (self.console_registers.get(&a), self.console_registers.get(&b)) else {
+            bail!("Cannot swap 'r{a}' and 'r{b}', as both must be assigned")
+        };
+        let (value_a, value_b) = (value_a.clone(), value_b.clone());
+        // Exchange the console values, without changing the order of assignment.
+        let console_registers = Rc::make_mut(&mut self.console_registers);
+        console_registers.insert(a, value_b);
+        console_registers.insert(b, value_a);
+        // Exchange the circuit values, if they are assigned.
+        if let (Some(value_a), Some(value_b)) = (self.circuit_registers.get(&a), self.circuit_registers.get(&b)) {
+            let (value_a, value_b) = (value_a.clone(), value_b.clone());
+            let circuit_registers = Rc::make_mut(&mut self.circuit_registers);
+            circuit_registers.insert(a, value_b);
+            circuit_registers.insert(b, value_a);
+        }
+        Ok(())
+    }
+
+    /// Clears every assigned register, including the input registers, so the registers can be reused.
+    /// The signer, caller, and transition view key are cleared with the registers, and the step budget is restored
+    /// to its configured value, while the call stack, register types, and options are retained.
+    #[inline]
+    fn reset(&mut self) {
+        Rc::make_mut(&mut self.console_registers).clear();
+        Rc::make_mut(&mut self.circuit_registers).clear();
+        self.signer = None;
+        self.signer_circuit = None;
+        self.caller = None;
+        self.caller_circuit = None;
+        self.tvk = None;
+        self.tvk_circuit = None;
+        // Restore the step budget.
+        self.step_budget.set(self.options.step_budget());
+        // Discard the recorded steps, if recording.
+        if let Some(recording) = self.trace.get_mut() {
+            recording.clear();
+        }
+    }
+
+    /// Checks that every output register of the given function is assigned. Outputs that are not registers are skipped.
+    ///
+    /// Note that the outputs may be assigned in any order, as a function may output its registers in a different order
+    /// than it assigns them, such as `output r3 as u8.private; output r2 as u8.private;`.
+    ///
+    /// # Errors
+    /// This method will halt if an output register is not assigned.
+    #[inline]
+    fn assert_outputs_complete(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        function_name: &Identifier<N>,
+    ) -> Result<()> {
+        for (index, output) in stack.get_function_ref(function_name)?.outputs().iter().enumerate() {
+            let Operand::Register(register) = output.operand() else { continue };
+            // Ensure the output register is assigned.
+            if !self.console_registers.contains_key(&register.locator()) {
+                bail!("Output {index} ('{register}') of '{function_name}' is not assigned")
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<N: Network, A: circuit::Aleo<Network = N>> RegistersStoreCircuit<N, A> for Registers<N, A> {
@@ -75,6 +208,9 @@ impl<N: Network, A: circuit::Aleo<Network = N>> RegistersStoreCircuit<N, A> for
         register: &Register<N>,
         circuit_value: circuit::Value<A>,
     ) -> Result<()> {
+        // Consume a step from the step budget.
+        self.consume_step()?;
+
         match register {
             Register::Locator(locator) => {
                 // Ensure the register assignments are monotonically increasing.
@@ -97,7 +233,7 @@ impl<N: Network, A: circuit::Aleo<Network = N>> RegistersStoreCircuit<N, A> for
                 };
 
                 // Store the stack value.
-                match self.circuit_registers.insert(*locator, circuit_value) {
+                match Rc::make_mut(&mut self.circuit_registers).insert(*locator, circuit_value) {
                     // Ensure the register has not been previously stored.
                     Some(..) => bail!("Attempted to write to register '{register}' again"),
                     // Return on success.
@@ -14,3 +14,4 @@
 
 pub mod test_credits;
 pub mod test_execute;
+pub mod test_stack;
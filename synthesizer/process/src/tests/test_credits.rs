@@ -1502,7 +1502,7 @@ fn test_bonding_to_closed_fails() {
 
 mod sanity_checks {
     use super::*;
-    use crate::{Assignments, CallStack, Stack, StackExecute};
+    use crate::{Assignments, CallStack, ExecutionOptions, Stack, StackExecute};
     use circuit::Assignment;
     use console::{program::Request, types::Field};
     use synthesizer_program::StackProgram;
@@ -1527,7 +1527,7 @@ mod sanity_checks {
         // Initialize the call stack.
         let call_stack = CallStack::CheckDeployment(vec![request], *private_key, assignments.clone());
         // Synthesize the circuit.
-        let _response = stack.execute_function::<A, _>(call_stack, None, rng).unwrap();
+        let _response = stack.execute_function::<A, _>(call_stack, &ExecutionOptions::default(), None, rng).unwrap();
         // Retrieve the assignment.
         let assignment = assignments.read().last().unwrap().0.clone();
         assignment
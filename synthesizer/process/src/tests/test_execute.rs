@@ -15,6 +15,7 @@
 use crate::{
     traits::{StackEvaluate, StackExecute},
     CallStack,
+    ExecutionOptions,
     Process,
     Trace,
 };
@@ -141,14 +142,25 @@ function foo:
     let expected = Value::Plaintext(Plaintext::<CurrentNetwork>::from_str("5field").unwrap());
 
     // Run the function.
-    let response =
-        stack.evaluate_function::<CurrentAleo>(CallStack::evaluate(authorization.replicate()).unwrap(), None).unwrap();
+    let response = stack
+        .evaluate_function::<CurrentAleo>(
+            CallStack::evaluate(authorization.replicate()).unwrap(),
+            &ExecutionOptions::default(),
+            None,
+        )
+        .unwrap();
     let candidate = response.outputs();
     assert_eq!(1, candidate.len());
     assert_eq!(expected, candidate[0]);
 
     // Re-run to ensure state continues to work.
-    let response = stack.evaluate_function::<CurrentAleo>(CallStack::evaluate(authorization).unwrap(), None).unwrap();
+    let response = stack
+        .evaluate_function::<CurrentAleo>(
+            CallStack::evaluate(authorization).unwrap(),
+            &ExecutionOptions::default(),
+            None,
+        )
+        .unwrap();
     let candidate = response.outputs();
     assert_eq!(1, candidate.len());
     assert_eq!(expected, candidate[0]);
@@ -203,14 +215,25 @@ output r1 as field.private;",
     let stack = process.get_stack(program.id()).unwrap();
 
     // Compute the output value.
-    let response =
-        stack.evaluate_function::<CurrentAleo>(CallStack::evaluate(authorization.replicate()).unwrap(), None).unwrap();
+    let response = stack
+        .evaluate_function::<CurrentAleo>(
+            CallStack::evaluate(authorization.replicate()).unwrap(),
+            &ExecutionOptions::default(),
+            None,
+        )
+        .unwrap();
     let candidate = response.outputs();
     assert_eq!(1, candidate.len());
     assert_eq!(expected, candidate[0]);
 
     // Re-run to ensure state continues to work.
-    let response = stack.evaluate_function::<CurrentAleo>(CallStack::evaluate(authorization).unwrap(), None).unwrap();
+    let response = stack
+        .evaluate_function::<CurrentAleo>(
+            CallStack::evaluate(authorization).unwrap(),
+            &ExecutionOptions::default(),
+            None,
+        )
+        .unwrap();
     let candidate = response.outputs();
     assert_eq!(1, candidate.len());
     assert_eq!(expected, candidate[0]);
@@ -268,14 +291,25 @@ output r1 as u64.private;",
     let stack = process.get_stack(program.id()).unwrap();
 
     // Compute the output value.
-    let response =
-        stack.evaluate_function::<CurrentAleo>(CallStack::evaluate(authorization.replicate()).unwrap(), None).unwrap();
+    let response = stack
+        .evaluate_function::<CurrentAleo>(
+            CallStack::evaluate(authorization.replicate()).unwrap(),
+            &ExecutionOptions::default(),
+            None,
+        )
+        .unwrap();
     let candidate = response.outputs();
     assert_eq!(1, candidate.len());
     assert_eq!(expected, candidate[0]);
 
     // Re-run to ensure state continues to work.
-    let response = stack.evaluate_function::<CurrentAleo>(CallStack::evaluate(authorization).unwrap(), None).unwrap();
+    let response = stack
+        .evaluate_function::<CurrentAleo>(
+            CallStack::evaluate(authorization).unwrap(),
+            &ExecutionOptions::default(),
+            None,
+        )
+        .unwrap();
     let candidate = response.outputs();
     assert_eq!(1, candidate.len());
     assert_eq!(expected, candidate[0]);
@@ -358,8 +392,13 @@ output r4 as field.private;",
     let stack = process.get_stack(program.id()).unwrap();
 
     // Compute the output value.
-    let response =
-        stack.evaluate_function::<CurrentAleo>(CallStack::evaluate(authorization.replicate()).unwrap(), None).unwrap();
+    let response = stack
+        .evaluate_function::<CurrentAleo>(
+            CallStack::evaluate(authorization.replicate()).unwrap(),
+            &ExecutionOptions::default(),
+            None,
+        )
+        .unwrap();
     let candidate = response.outputs();
     assert_eq!(3, candidate.len());
     assert_eq!(r2, candidate[0]);
@@ -367,7 +406,13 @@ output r4 as field.private;",
     assert_eq!(r4, candidate[2]);
 
     // Re-run to ensure state continues to work.
-    let response = stack.evaluate_function::<CurrentAleo>(CallStack::evaluate(authorization).unwrap(), None).unwrap();
+    let response = stack
+        .evaluate_function::<CurrentAleo>(
+            CallStack::evaluate(authorization).unwrap(),
+            &ExecutionOptions::default(),
+            None,
+        )
+        .unwrap();
     let candidate = response.outputs();
     assert_eq!(3, candidate.len());
     assert_eq!(r2, candidate[0]);
@@ -395,7 +440,7 @@ output r4 as field.private;",
     // Re-run to ensure state continues to work.
     let trace = Arc::new(RwLock::new(Trace::new()));
     let call_stack = CallStack::execute(authorization, trace).unwrap();
-    let response = stack.execute_function::<CurrentAleo, _>(call_stack, None, rng).unwrap();
+    let response = stack.execute_function::<CurrentAleo, _>(call_stack, &ExecutionOptions::default(), None, rng).unwrap();
     let candidate = response.outputs();
     assert_eq!(3, candidate.len());
     assert_eq!(r2, candidate[0]);
@@ -463,14 +508,25 @@ output r1 as token.record;",
     let stack = process.get_stack(program.id()).unwrap();
 
     // Compute the output value.
-    let response =
-        stack.evaluate_function::<CurrentAleo>(CallStack::evaluate(authorization.replicate()).unwrap(), None).unwrap();
+    let response = stack
+        .evaluate_function::<CurrentAleo>(
+            CallStack::evaluate(authorization.replicate()).unwrap(),
+            &ExecutionOptions::default(),
+            None,
+        )
+        .unwrap();
     let candidate = response.outputs();
     assert_eq!(1, candidate.len());
     assert_eq!(expected, candidate[0]);
 
     // Re-run to ensure state continues to work.
-    let response = stack.evaluate_function::<CurrentAleo>(CallStack::evaluate(authorization).unwrap(), None).unwrap();
+    let response = stack
+        .evaluate_function::<CurrentAleo>(
+            CallStack::evaluate(authorization).unwrap(),
+            &ExecutionOptions::default(),
+            None,
+        )
+        .unwrap();
     let candidate = response.outputs();
     assert_eq!(1, candidate.len());
     assert_eq!(expected, candidate[0]);
@@ -0,0 +1,3977 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    Authorization,
+    CallStack,
+    CallTrait,
+    ExecutionOptions,
+    FinalizeRegisters,
+    MappingSnapshot,
+    Registers,
+    RegistersCall,
+    RegistersRecorder,
+    Stack,
+    StackProgramTypes,
+    TraceStep,
+};
+use circuit::{network::AleoV0, prelude::ToBits, Eject, Environment, Inject, Mode};
+use console::{
+    account::PrivateKey,
+    network::{prelude::*, Testnet3},
+    program::{
+        Argument,
+        Future,
+        Identifier,
+        Literal,
+        Locator,
+        Plaintext,
+        PlaintextType,
+        ProgramID,
+        Record,
+        Register,
+        RegisterType,
+        Request,
+        StructType,
+        Value,
+        ValueType,
+    },
+    types::{Address, Field, Group, Scalar},
+};
+use ledger_store::{helpers::memory::FinalizeMemory, FinalizeStore};
+use synthesizer_program::{
+    Command,
+    EntryVisibility,
+    FinalizeGlobalState,
+    Instruction,
+    Operand,
+    Program,
+    ProgramSchema,
+    ProgramWarning,
+    RegisterOrigin,
+    RegistersLoad,
+    RegistersLoadCircuit,
+    RegistersSigner,
+    RegistersStore,
+    RegistersStoreCircuit,
+    StackMatches,
+    StackProgram,
+    StackProgramMut,
+};
+
+use indexmap::IndexMap;
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+type CurrentNetwork = Testnet3;
+type CurrentAleo = AleoV0;
+
+/// Returns a sample program used to test the stack helpers.
+fn sample_program() -> Program<CurrentNetwork> {
+    Program::from_str(
+        r"
+program stack_helpers.aleo;
+
+struct point:
+    x as field;
+    y as field;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+mapping balances:
+    key as address.public;
+    value as u64.public;
+
+closure twice:
+    input r0 as field;
+    add r0 r0 into r1;
+    output r1 as field;
+
+closure fourfold:
+    input r0 as field;
+    add r0 r0 into r1;
+    add r1 r1 into r2;
+    output r2 as field;
+
+function foo:
+    input r0 as field.public;
+    input r1 as field.private;
+    add r0 r1 into r2;
+    output r2 as field.private;
+
+function bar:
+    input r0 as field.private;
+    call twice r0 into r1;
+    call fourfold r1 into r2;
+    output r2 as field.private;
+
+function deposit:
+    input r0 as u64.public;
+    async deposit self.caller r0 into r1;
+    output r1 as stack_helpers.aleo/deposit.future;
+
+finalize deposit:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use balances[r0] 0u64 into r2;
+    add r2 r1 into r3;
+    set r3 into balances[r0];
+",
+    )
+    .unwrap()
+}
+
+/// Returns the stack of the given program, in a process with the given program.
+fn sample_stack(program: &Program<CurrentNetwork>) -> Stack<CurrentNetwork> {
+    crate::test_helpers::sample_process(program).get_stack(program.id()).unwrap().clone()
+}
+
+/// Returns registers for evaluating `bar` in the sample program.
+fn sample_registers(stack: &Stack<CurrentNetwork>, rng: &mut TestRng) -> Registers<CurrentNetwork, CurrentAleo> {
+    sample_registers_for(stack, "bar", &["1field"], rng)
+}
+
+/// Returns registers for evaluating the given function in the given stack, on the given inputs.
+/// The inputs are signed in the request of the call stack, but are not stored in the registers.
+fn sample_registers_for(
+    stack: &Stack<CurrentNetwork>,
+    function_name: &str,
+    inputs: &[&str],
+    rng: &mut TestRng,
+) -> Registers<CurrentNetwork, CurrentAleo> {
+    let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let function_name = Identifier::from_str(function_name).unwrap();
+    let input_types = stack.get_function_ref(&function_name).unwrap().input_types();
+    let request =
+        Request::sign(&private_key, *stack.program_id(), function_name, inputs.iter().copied(), &input_types, rng)
+            .unwrap();
+    let call_stack = CallStack::evaluate(Authorization::new(request)).unwrap();
+    let register_types = stack.get_register_types(&function_name).unwrap().clone();
+    Registers::new(call_stack, register_types)
+}
+
+#[test]
+fn test_matches_request_inputs() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let program = sample_program();
+    let stack = &sample_stack(&program);
+
+    // Sample a private key.
+    let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let function_name = Identifier::from_str("foo").unwrap();
+
+    // Sign a well-typed request.
+    let input_types = stack.get_function_ref(&function_name).unwrap().input_types();
+    let request =
+        Request::sign(&private_key, *program.id(), function_name, ["1field", "2field"].into_iter(), &input_types, rng)
+            .unwrap();
+    assert!(stack.matches_request_inputs(&request, &function_name).is_ok());
+
+    // Sign a request with mistyped inputs.
+    let input_types = [ValueType::from_str("u32.public").unwrap(), ValueType::from_str("u32.private").unwrap()];
+    let request =
+        Request::sign(&private_key, *program.id(), function_name, ["1u32", "2u32"].into_iter(), &input_types, rng)
+            .unwrap();
+    let error = stack.matches_request_inputs(&request, &function_name).unwrap_err();
+    assert!(error.to_string().starts_with("Input 0 of 'foo'"));
+}
+
+#[test]
+fn test_input_and_output_register_types() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let register_type = |register_type: &str| RegisterType::<CurrentNetwork>::from_str(register_type).unwrap();
+
+    // Ensure the input registers of `foo` are returned in order, with their register types.
+    let foo = Identifier::from_str("foo").unwrap();
+    assert_eq!(stack.input_register_types(&foo).unwrap(), vec![
+        (Register::Locator(0), register_type("field")),
+        (Register::Locator(1), register_type("field")),
+    ]);
+    assert_eq!(stack.output_register_types(&foo).unwrap(), vec![(
+        Operand::from_str("r2").unwrap(),
+        register_type("field")
+    )]);
+
+    // Ensure the register types of `deposit` are returned, including the future output.
+    let deposit = Identifier::from_str("deposit").unwrap();
+    assert_eq!(stack.input_register_types(&deposit).unwrap(), vec![(Register::Locator(0), register_type("u64"))]);
+    assert_eq!(stack.output_register_types(&deposit).unwrap(), vec![(
+        Operand::from_str("r1").unwrap(),
+        register_type("stack_helpers.aleo/deposit.future")
+    )]);
+
+    // Ensure an unknown function fails.
+    assert!(stack.input_register_types(&Identifier::from_str("unknown").unwrap()).is_err());
+    assert!(stack.output_register_types(&Identifier::from_str("unknown").unwrap()).is_err());
+}
+
+#[test]
+fn test_complexity_summary() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    // Check the complexity summary.
+    let complexity = stack.complexity_summary().unwrap();
+    assert_eq!(complexity.num_functions(), 3);
+    assert_eq!(complexity.num_closures(), 2);
+    assert_eq!(complexity.num_structs(), 1);
+    assert_eq!(complexity.num_records(), 1);
+    assert_eq!(complexity.num_mappings(), 1);
+    assert_eq!(complexity.num_instructions(), 7);
+    assert_eq!(complexity.num_commands(), 3);
+    // The only calls are from `bar` into closures.
+    assert_eq!(complexity.max_call_depth(), 1);
+}
+
+#[test]
+fn test_reachable_resources() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    // Check the resources reachable from `bar`.
+    let reachable = stack.reachable_resources(&Identifier::from_str("bar").unwrap()).unwrap();
+    let expected = ["bar", "fourfold", "twice"].map(|name| Identifier::from_str(name).unwrap());
+    assert_eq!(reachable.len(), expected.len());
+    assert!(expected.iter().all(|name| reachable.contains(name)));
+
+    // Check the resources reachable from `foo`.
+    let reachable = stack.reachable_resources(&Identifier::from_str("foo").unwrap()).unwrap();
+    assert_eq!(reachable.into_iter().collect::<Vec<_>>(), vec![Identifier::from_str("foo").unwrap()]);
+
+    // Ensure an unknown entry fails.
+    assert!(stack.reachable_resources(&Identifier::from_str("unknown").unwrap()).is_err());
+}
+
+#[test]
+fn test_canonicalize_value() {
+    let stack = &sample_stack(&sample_program());
+
+    // Populate the bit cache of a struct, and ensure canonicalization preserves the value.
+    let plaintext = Plaintext::<CurrentNetwork>::from_str("{ x: 1field, y: [2field, 3field] }").unwrap();
+    let _ = plaintext.to_bits_le();
+    let value = Value::Plaintext(plaintext.clone());
+    let canonical = stack.canonicalize_value(value.clone()).unwrap();
+    assert_eq!(canonical, value);
+    assert_eq!(canonical.to_bits_le(), value.to_bits_le());
+
+    // Ensure canonicalization is idempotent.
+    assert_eq!(stack.canonicalize_value(canonical.clone()).unwrap(), canonical);
+
+    // Ensure an array is not coerced into a literal.
+    let array = Value::Plaintext(Plaintext::from_str("[1field]").unwrap());
+    let canonical = stack.canonicalize_value(array.clone()).unwrap();
+    assert!(matches!(canonical, Value::Plaintext(Plaintext::Array(..))));
+    assert_eq!(canonical, array);
+}
+
+#[test]
+fn test_simulate_call() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let program = sample_program();
+    let stack = &sample_stack(&program);
+
+    // Initialize the registers for `bar`.
+    let registers = sample_registers(stack, rng);
+
+    // Simulate a call to a closure.
+    let callee = Locator::new(*program.id(), Identifier::from_str("twice").unwrap());
+    let inputs = [Value::from_str("1field").unwrap()];
+    let cost = registers.simulate_call(stack, &callee, &inputs).unwrap();
+    assert_eq!(cost.resource(), callee.resource());
+    assert_eq!(cost.num_transitions(), 0);
+    assert_eq!(cost.num_instructions(), 1);
+    assert_eq!(cost.finalize_cost(), 0);
+
+    // Simulate a call to a function with a finalize scope, priced as `get.or_use`, `add`, and `set`.
+    let callee = Locator::new(*program.id(), Identifier::from_str("deposit").unwrap());
+    let cost = registers.simulate_call(stack, &callee, &[Value::from_str("5u64").unwrap()]).unwrap();
+    assert_eq!(cost.num_transitions(), 1);
+    assert_eq!(cost.num_instructions(), 1);
+    assert_eq!(cost.finalize_cost(), 25_000 + 2_000 + 100_000);
+
+    // Simulate a call to a function with nested calls.
+    let callee = Locator::new(*program.id(), Identifier::from_str("bar").unwrap());
+    let cost = registers.simulate_call(stack, &callee, &inputs).unwrap();
+    assert_eq!(cost.num_transitions(), 1);
+    assert_eq!(cost.num_instructions(), 5);
+    assert_eq!(cost.finalize_cost(), 0);
+
+    // Ensure mistyped or missing inputs fail.
+    assert!(registers.simulate_call(stack, &callee, &[Value::from_str("1u32").unwrap()]).is_err());
+    assert!(registers.simulate_call(stack, &callee, &[]).is_err());
+}
+
+#[test]
+fn test_verify_no_read_before_write() {
+    // Initialize the stack.
+    let program = sample_program();
+    let stack = &sample_stack(&program);
+
+    // Ensure each closure and function in the program passes.
+    for name in program.closures().keys().chain(program.functions().keys()) {
+        assert!(stack.verify_no_read_before_write(name).is_ok());
+    }
+
+    // Ensure unknown names fail.
+    assert!(stack.verify_no_read_before_write(&Identifier::from_str("unknown").unwrap()).is_err());
+}
+
+#[test]
+fn test_step_budget() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    // Initialize the registers for `bar`.
+    let mut registers = sample_registers(stack, rng);
+
+    // Allow one store and one load.
+    registers.set_step_budget(2);
+    let register = Register::Locator(0);
+    registers.store(stack, &register, Value::from_str("1field").unwrap()).unwrap();
+    let operand = Operand::Register(register);
+    assert_eq!(registers.load(stack, &operand).unwrap(), Value::from_str("1field").unwrap());
+
+    // Ensure the next load halts.
+    let error = registers.load(stack, &operand).unwrap_err();
+    assert_eq!(error.to_string(), "step budget exhausted");
+}
+
+#[test]
+fn test_step_budget_options() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the programs, where `parent` calls `child`.
+    let child = Program::<CurrentNetwork>::from_str(
+        r"
+program child.aleo;
+
+function grow:
+    input r0 as field.private;
+    add r0 r0 into r1;
+    output r1 as field.private;",
+    )
+    .unwrap();
+    let parent = Program::<CurrentNetwork>::from_str(
+        r"
+import child.aleo;
+
+program parent.aleo;
+
+function quadruple:
+    input r0 as field.private;
+    call child.aleo/grow r0 into r1;
+    add r1 r1 into r2;
+    output r2 as field.private;",
+    )
+    .unwrap();
+    let mut process = crate::test_helpers::sample_process(&child);
+    process.add_program(&parent).unwrap();
+
+    // Authorize the call.
+    let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let function_name = Identifier::from_str("quadruple").unwrap();
+    let authorization = process
+        .authorize::<CurrentAleo, _>(&private_key, parent.id(), function_name, ["1field"].into_iter(), rng)
+        .unwrap();
+
+    // Ensure the evaluation succeeds with a sufficient budget.
+    let mut options = ExecutionOptions::default();
+    options.set_step_budget(100);
+    let response = process.evaluate_with_options::<CurrentAleo>(authorization.replicate(), &options).unwrap();
+    assert_eq!(response.outputs(), [Value::from_str("4field").unwrap()]);
+
+    // Ensure the budget applies to the registers of the callee, which halt before those of the caller.
+    options.set_step_budget(4);
+    let error = process.evaluate_with_options::<CurrentAleo>(authorization, &options).unwrap_err();
+    assert!(error.to_string().contains("call child.aleo/grow"), "{error}");
+    assert!(error.to_string().ends_with("step budget exhausted"), "{error}");
+}
+
+#[test]
+fn test_value_type_schema() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    // Check the schema of a literal.
+    let schema = stack.value_type_schema(&ValueType::from_str("u64.public").unwrap());
+    assert_eq!(schema, serde_json::json!({ "kind": "literal", "type": "u64", "visibility": "public" }));
+
+    // Check the schema of an array of structs.
+    let schema = stack.value_type_schema(&ValueType::from_str("[point; 2u32].private").unwrap());
+    let point = serde_json::json!({
+        "kind": "struct",
+        "name": "point",
+        "members": [
+            { "name": "x", "type": { "kind": "literal", "type": "field" } },
+            { "name": "y", "type": { "kind": "literal", "type": "field" } },
+        ],
+    });
+    assert_eq!(schema, serde_json::json!({ "kind": "array", "length": 2, "element": point, "visibility": "private" }));
+
+    // Check the schema of a record.
+    let schema = stack.value_type_schema(&ValueType::from_str("token.record").unwrap());
+    assert_eq!(
+        schema,
+        serde_json::json!({
+            "kind": "record",
+            "name": "token",
+            "owner": "private",
+            "entries": [
+                { "name": "amount", "type": { "kind": "literal", "type": "u64", "visibility": "private" } },
+            ],
+        })
+    );
+
+    // Check the schema of a future.
+    let schema = stack.value_type_schema(&ValueType::from_str("stack_helpers.aleo/deposit.future").unwrap());
+    assert_eq!(
+        schema,
+        serde_json::json!({ "kind": "future", "locator": "stack_helpers.aleo/deposit", "visibility": "public" })
+    );
+}
+
+#[test]
+fn test_load_circuit_shared() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    // Returns the number of constraints for decomposing two loads of `r0` into bits.
+    let mut num_constraints = |shared: bool| {
+        let mut registers = sample_registers(stack, rng);
+        let register = Register::Locator(0);
+        let value = circuit::Value::<CurrentAleo>::new(Mode::Private, Value::from_str("1field").unwrap());
+        registers.store_circuit(stack, &register, value).unwrap();
+
+        let operand = Operand::Register(register);
+        let start = CurrentAleo::num_constraints();
+        for _ in 0..2 {
+            let value = match shared {
+                true => registers.load_circuit_shared(stack, &operand).unwrap(),
+                false => registers.load_circuit(stack, &operand).unwrap(),
+            };
+            let _ = value.to_bits_le();
+        }
+        let num_constraints = CurrentAleo::num_constraints() - start;
+        CurrentAleo::reset();
+        num_constraints
+    };
+
+    // Ensure the shared loads decompose the register once.
+    let unshared = num_constraints(false);
+    let shared = num_constraints(true);
+    assert!(shared > 0);
+    assert_eq!(shared * 2, unshared);
+}
+
+#[test]
+fn test_function_source_span() {
+    // Initialize the stack.
+    let program = sample_program();
+    let stack = &sample_stack(&program);
+
+    // Ensure the span covers the function in the canonical source, including its finalize scope.
+    let source = program.to_string();
+    let (start, end) = stack.function_source_span(&Identifier::from_str("deposit").unwrap()).unwrap();
+    assert!(source[start..end].starts_with("function deposit:"));
+    assert!(source[start..end].ends_with("set r3 into balances[r0];"));
+
+    // Ensure closures and unknown names have no span.
+    assert!(stack.function_source_span(&Identifier::from_str("twice").unwrap()).is_none());
+    assert!(stack.function_source_span(&Identifier::from_str("unknown").unwrap()).is_none());
+}
+
+#[test]
+fn test_load_literal_within() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let registers = sample_registers(stack, rng);
+
+    // Returns the result of loading the given literal within the given number of bits.
+    let load = |literal: &str, bits: usize| {
+        registers.load_literal_within(stack, &Operand::Literal(Literal::from_str(literal).unwrap()), bits)
+    };
+
+    // Check unsigned integers.
+    assert!(load("255u16", 8).is_ok());
+    assert!(load("256u16", 8).is_err());
+    assert!(load("0u8", 0).is_ok());
+    assert!(load("340282366920938463463374607431768211455u128", 128).is_ok());
+
+    // Check signed integers.
+    assert!(load("127i16", 8).is_ok());
+    assert!(load("-128i16", 8).is_ok());
+    assert!(load("128i16", 8).is_err());
+    assert!(load("-129i16", 8).is_err());
+
+    // Ensure non-integers fail.
+    assert!(load("1field", 8).is_err());
+}
+
+#[test]
+fn test_load_group_and_scalar() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let registers = sample_registers(stack, rng);
+    let operand = |literal: &str| Operand::Literal(Literal::from_str(literal).unwrap());
+
+    // Ensure a group element is loaded.
+    let group = registers.load_group(stack, &operand("0group")).unwrap();
+    assert_eq!(group, Group::<CurrentNetwork>::zero());
+    // Ensure a literal that is not a group element fails.
+    let error = registers.load_group(stack, &operand("1field")).unwrap_err();
+    assert_eq!(error.to_string(), "Operand must be a group element, found '1field'");
+
+    // Ensure a scalar is loaded.
+    let scalar = registers.load_scalar(stack, &operand("2scalar")).unwrap();
+    assert_eq!(scalar, Scalar::<CurrentNetwork>::from_str("2scalar").unwrap());
+    // Ensure a literal that is not a scalar fails.
+    let error = registers.load_scalar(stack, &operand("2u8")).unwrap_err();
+    assert_eq!(error.to_string(), "Operand must be a scalar, found '2u8'");
+}
+
+#[test]
+fn test_load_tuple() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let registers = sample_registers(stack, rng);
+
+    // Returns the literal operands for the given literals.
+    let operands = |literals: &[&str]| {
+        literals.iter().map(|literal| Operand::Literal(Literal::from_str(literal).unwrap())).collect::<Vec<_>>()
+    };
+
+    // Ensure the values are converted into the elements of the tuple, in order.
+    let (a, b): (u64, u64) = registers.load_tuple(stack, &operands(&["1u64", "2u64"])).unwrap();
+    assert_eq!((a, b), (1, 2));
+    let (a, b, c): (bool, Field<CurrentNetwork>, Plaintext<CurrentNetwork>) =
+        registers.load_tuple(stack, &operands(&["true", "3field", "4u8"])).unwrap();
+    assert_eq!((a, b, c), (true, Field::from_str("3field").unwrap(), Plaintext::from_str("4u8").unwrap()));
+
+    // Ensure a value of the wrong type fails.
+    assert!(registers.load_tuple::<(u64, u64)>(stack, &operands(&["1u64", "2u32"])).is_err());
+    // Ensure the wrong number of operands fails.
+    assert!(registers.load_tuple::<(u64, u64)>(stack, &operands(&["1u64"])).is_err());
+    assert!(registers.load_tuple::<(u64,)>(stack, &operands(&["1u64", "2u64"])).is_err());
+}
+
+#[test]
+fn test_load_assert_eq() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let mut registers = sample_registers(stack, rng);
+    registers.store(stack, &Register::Locator(0), Value::from_str("1field").unwrap()).unwrap();
+    let operand = Operand::Register(Register::Locator(0));
+
+    // Ensure an equal value passes.
+    assert!(registers.load_assert_eq(stack, &operand, &Value::from_str("1field").unwrap()).is_ok());
+
+    // Ensure an unequal value fails, and reports both values.
+    let error = registers.load_assert_eq(stack, &operand, &Value::from_str("2field").unwrap()).unwrap_err();
+    assert_eq!(error.to_string(), "Operand 'r0' is '1field', expected '2field'");
+
+    // Ensure an unassigned register fails.
+    let unassigned = Operand::Register(Register::Locator(1));
+    assert!(registers.load_assert_eq(stack, &unassigned, &Value::from_str("1field").unwrap()).is_err());
+}
+
+#[test]
+fn test_store_and_return() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let mut registers = sample_registers(stack, rng);
+
+    // Ensure the stored value is returned.
+    let value = Value::from_str("1field").unwrap();
+    let register = Register::Locator(0);
+    assert_eq!(registers.store_and_return(stack, &register, value.clone()).unwrap(), &value);
+
+    // Ensure the register cannot be written again.
+    assert!(registers.store_and_return(stack, &register, value).is_err());
+}
+
+#[test]
+fn test_fork_registers() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let mut registers = sample_registers(stack, rng);
+    registers.store(stack, &Register::Locator(0), Value::from_str("1field").unwrap()).unwrap();
+
+    // Ensure the fork shares the registers assigned before the fork.
+    let mut fork = registers.fork();
+    assert!(fork.registers_equal(&registers));
+
+    // Ensure writes to the fork do not affect the registers, and vice versa.
+    fork.store(stack, &Register::Locator(1), Value::from_str("2field").unwrap()).unwrap();
+    registers.store(stack, &Register::Locator(1), Value::from_str("3field").unwrap()).unwrap();
+    let r1 = Operand::Register(Register::Locator(1));
+    assert_eq!(fork.load(stack, &r1).unwrap(), Value::from_str("2field").unwrap());
+    assert_eq!(registers.load(stack, &r1).unwrap(), Value::from_str("3field").unwrap());
+
+    // Ensure the register assigned before the fork is unchanged in both.
+    let r0 = Operand::Register(Register::Locator(0));
+    assert_eq!(fork.load(stack, &r0).unwrap(), registers.load(stack, &r0).unwrap());
+}
+
+#[test]
+fn test_fork_registers_call() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the programs, where `parent` calls `child`.
+    let child = Program::<CurrentNetwork>::from_str(
+        r"
+program fork_child.aleo;
+
+function grow:
+    input r0 as field.private;
+    add r0 r0 into r1;
+    output r1 as field.private;",
+    )
+    .unwrap();
+    let parent = Program::<CurrentNetwork>::from_str(
+        r"
+import fork_child.aleo;
+
+program fork_parent.aleo;
+
+function quadruple:
+    input r0 as field.private;
+    call fork_child.aleo/grow r0 into r1;
+    add r1 r1 into r2;
+    output r2 as field.private;",
+    )
+    .unwrap();
+    let mut process = crate::test_helpers::sample_process(&child);
+    process.add_program(&parent).unwrap();
+    let stack = process.get_stack(parent.id()).unwrap();
+
+    // Authorize a call to `quadruple`, and consume its request, so only the request of 'grow' remains.
+    let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let function_name = Identifier::from_str("quadruple").unwrap();
+    let authorization = process
+        .authorize::<CurrentAleo, _>(&private_key, parent.id(), function_name, ["1field"].into_iter(), rng)
+        .unwrap();
+    authorization.next().unwrap();
+
+    // Initialize the registers, with an interceptor that mocks 'grow'.
+    let mut options = ExecutionOptions::default();
+    options.set_call_interceptor(Box::new(|_, _| Some(vec![Value::from_str("5field").unwrap()])));
+    let register_types = stack.get_register_types(&function_name).unwrap().clone();
+    let call_stack = CallStack::evaluate(authorization.clone()).unwrap();
+    let mut registers = Registers::<CurrentNetwork, CurrentAleo>::new(call_stack, register_types);
+    registers.set_options(&options);
+    registers.store(stack, &Register::Locator(0), Value::from_str("1field").unwrap()).unwrap();
+    let function = stack.get_function_ref(&function_name).unwrap();
+    let Instruction::Call(call) = &function.instructions()[0] else { panic!("Expected a call instruction") };
+
+    // Ensure a call in the fork consumes the request of its own call stack, not that of the registers.
+    let mut fork = registers.fork();
+    CallTrait::evaluate(call, stack, &mut fork).unwrap();
+    assert_eq!(authorization.len(), 1);
+
+    // Ensure the same call in the registers still finds its request, and both assign the outputs.
+    CallTrait::evaluate(call, stack, &mut registers).unwrap();
+    assert!(authorization.is_empty());
+    let r1 = Operand::Register(Register::Locator(1));
+    assert_eq!(fork.load(stack, &r1).unwrap(), Value::from_str("5field").unwrap());
+    assert_eq!(registers.load(stack, &r1).unwrap(), Value::from_str("5field").unwrap());
+}
+
+#[test]
+fn test_finalize_checkpoint() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    // Initialize the finalize registers for `deposit`.
+    let function_name = Identifier::from_str("deposit").unwrap();
+    let mut registers = FinalizeRegisters::<CurrentNetwork>::new(
+        FinalizeGlobalState::from(1, 1, [0; 32]),
+        <CurrentNetwork as Network>::TransitionID::default(),
+        function_name,
+        stack.get_finalize_types(&function_name).unwrap().clone(),
+    );
+
+    // Store the inputs, and take a checkpoint.
+    let caller = Value::from_str("aleo1qr2ha4pfs5l28aze88yn6fhleeythklkczrule2v838uwj65n5gqxt9djx").unwrap();
+    registers.store(stack, &Register::Locator(0), caller).unwrap();
+    registers.store(stack, &Register::Locator(1), Value::from_str("5u64").unwrap()).unwrap();
+    let checkpoint = registers.checkpoint();
+
+    // Store a register, and restore the checkpoint.
+    let register = Register::Locator(2);
+    registers.store(stack, &register, Value::from_str("0u64").unwrap()).unwrap();
+    registers.restore(checkpoint.clone()).unwrap();
+
+    // Ensure the register is unassigned, and can be stored again.
+    assert!(registers.load(stack, &Operand::Register(register.clone())).is_err());
+    registers.store(stack, &register, Value::from_str("1u64").unwrap()).unwrap();
+    assert_eq!(registers.load(stack, &Operand::Register(register)).unwrap(), Value::from_str("1u64").unwrap());
+
+    // Ensure a checkpoint from another finalize scope is rejected.
+    let mut other = FinalizeRegisters::<CurrentNetwork>::new(
+        FinalizeGlobalState::from(1, 1, [0; 32]),
+        <CurrentNetwork as Network>::TransitionID::from(Field::from_u64(1)),
+        function_name,
+        stack.get_finalize_types(&function_name).unwrap().clone(),
+    );
+    assert!(other.restore(checkpoint).is_err());
+}
+
+#[test]
+fn test_constant_literals() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    // Ensure the default value in the finalize scope of `deposit` is the only literal.
+    assert_eq!(stack.constant_literals(), vec![Literal::from_str("0u64").unwrap()]);
+}
+
+#[test]
+fn test_load_with_offset() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let mut registers = sample_registers(stack, rng);
+
+    // Store `r0` and `r1`.
+    registers.store(stack, &Register::Locator(0), Value::from_str("1field").unwrap()).unwrap();
+    registers.store(stack, &Register::Locator(1), Value::from_str("2field").unwrap()).unwrap();
+
+    // Ensure the register is relocated.
+    let operand = Operand::Register(Register::Locator(0));
+    assert_eq!(registers.load_with_offset(stack, &operand, 0).unwrap(), Value::from_str("1field").unwrap());
+    assert_eq!(registers.load_with_offset(stack, &operand, 1).unwrap(), Value::from_str("2field").unwrap());
+    assert!(registers.load_with_offset(stack, &operand, 2).is_err());
+
+    // Ensure literals are unaffected.
+    let operand = Operand::Literal(Literal::from_str("3field").unwrap());
+    assert_eq!(registers.load_with_offset(stack, &operand, 1).unwrap(), Value::from_str("3field").unwrap());
+
+    // Ensure the relocation does not overflow.
+    let operand = Operand::Register(Register::Locator(u64::MAX));
+    assert!(registers.load_with_offset(stack, &operand, 1).is_err());
+}
+
+#[test]
+fn test_unify_value_types() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    let unify =
+        |a: &str, b: &str| stack.unify_value_types(&ValueType::from_str(a).unwrap(), &ValueType::from_str(b).unwrap());
+
+    // Ensure identical types unify.
+    assert_eq!(unify("u64.public", "u64.public").unwrap(), ValueType::from_str("u64.public").unwrap());
+    assert_eq!(unify("point.private", "point.private").unwrap(), ValueType::from_str("point.private").unwrap());
+
+    // Ensure a record unifies with an external record that refers to it.
+    let record = ValueType::from_str("token.record").unwrap();
+    assert_eq!(unify("token.record", "stack_helpers.aleo/token.record").unwrap(), record);
+    assert_eq!(unify("stack_helpers.aleo/token.record", "token.record").unwrap(), record);
+
+    // Ensure distinct types, or distinct visibilities, do not unify.
+    assert!(unify("u64.public", "u32.public").is_err());
+    assert!(unify("u64.public", "u64.private").is_err());
+    assert!(unify("token.record", "other.aleo/token.record").is_err());
+}
+
+#[test]
+fn test_types_structurally_equal() {
+    // Initialize the program, where `coordinate` has the same layout as `point`, but `pair` does not.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program structural.aleo;
+
+struct point:
+    x as field;
+    y as field;
+
+struct coordinate:
+    x as field;
+    y as field;
+
+struct pair:
+    y as field;
+    x as field;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function noop:
+    input r0 as point.private;
+    output r0 as point.private;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+
+    let equal = |a: &str, b: &str| {
+        stack.types_structurally_equal(&ValueType::from_str(a).unwrap(), &ValueType::from_str(b).unwrap())
+    };
+
+    // Ensure types that differ only in visibility are equal.
+    assert!(equal("u64.public", "u64.private"));
+    assert!(equal("[point; 2u32].constant", "[point; 2u32].public"));
+    // Ensure structs with the same members are equal, regardless of their names.
+    assert!(equal("point.public", "coordinate.private"));
+    assert!(equal("token.record", "structural.aleo/token.record"));
+
+    // Ensure types with different structures are not equal.
+    assert!(!equal("u64.public", "u32.public"));
+    assert!(!equal("point.public", "pair.public"));
+    assert!(!equal("[point; 2u32].public", "[point; 3u32].public"));
+    assert!(!equal("u64.public", "token.record"));
+}
+
+#[test]
+fn test_get_record_type() {
+    // Initialize the stack.
+    let program = sample_program();
+    let stack = &sample_stack(&program);
+
+    // Ensure the local record type is returned.
+    let record_name = Identifier::from_str("token").unwrap();
+    assert_eq!(&stack.get_record_type(&record_name).unwrap(), program.get_record(&record_name).unwrap());
+
+    // Ensure structs and unknown names fail.
+    assert!(stack.get_record_type(&Identifier::from_str("point").unwrap()).is_err());
+    assert!(stack.get_record_type(&Identifier::from_str("unknown").unwrap()).is_err());
+}
+
+#[test]
+fn test_call_graph() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    // Check the internal call graph.
+    let edge =
+        |caller: &str, callee: &str| (Identifier::from_str(caller).unwrap(), Identifier::from_str(callee).unwrap());
+    assert_eq!(stack.call_graph(), vec![edge("bar", "twice"), edge("bar", "fourfold")]);
+
+    // Check the external call graph.
+    assert!(stack.external_call_graph().is_empty());
+}
+
+#[test]
+fn test_external_call_graph() {
+    // Initialize the programs, where `parent` calls `child` from two functions, and calls its own closure.
+    let child = Program::<CurrentNetwork>::from_str(
+        r"
+program child.aleo;
+
+function grow:
+    input r0 as field.private;
+    add r0 r0 into r1;
+    output r1 as field.private;
+
+function shrink:
+    input r0 as field.private;
+    div r0 2field into r1;
+    output r1 as field.private;",
+    )
+    .unwrap();
+    let parent = Program::<CurrentNetwork>::from_str(
+        r"
+import child.aleo;
+
+program parent.aleo;
+
+closure scale:
+    input r0 as field;
+    mul r0 r0 into r1;
+    output r1 as field;
+
+function quadruple:
+    input r0 as field.private;
+    call child.aleo/grow r0 into r1;
+    call child.aleo/grow r1 into r2;
+    output r2 as field.private;
+
+function resize:
+    input r0 as field.private;
+    call scale r0 into r1;
+    call child.aleo/shrink r1 into r2;
+    output r2 as field.private;",
+    )
+    .unwrap();
+    let mut process = crate::test_helpers::sample_process(&child);
+    process.add_program(&parent).unwrap();
+    let stack = process.get_stack(parent.id()).unwrap();
+
+    // Ensure every external call is an edge, in program order, and the closure call is not.
+    let edge = |caller: &str, callee: &str| (Identifier::from_str(caller).unwrap(), Locator::from_str(callee).unwrap());
+    assert_eq!(stack.external_call_graph(), vec![
+        edge("quadruple", "child.aleo/grow"),
+        edge("quadruple", "child.aleo/grow"),
+        edge("resize", "child.aleo/shrink"),
+    ]);
+    let closure_call = (Identifier::from_str("resize").unwrap(), Identifier::from_str("scale").unwrap());
+    assert_eq!(stack.call_graph(), vec![closure_call]);
+
+    // Ensure the callee has no external calls.
+    assert!(process.get_stack(child.id()).unwrap().external_call_graph().is_empty());
+}
+
+#[test]
+fn test_assert_input_registers_unchanged() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let mut registers = sample_registers(stack, rng);
+    let function_name = Identifier::from_str("bar").unwrap();
+
+    // Ensure an unassigned input register fails.
+    assert!(registers.assert_input_registers_unchanged(stack, &function_name).is_err());
+
+    // Ensure an assigned input register passes.
+    registers.store(stack, &Register::Locator(0), Value::from_str("1field").unwrap()).unwrap();
+    assert!(registers.assert_input_registers_unchanged(stack, &function_name).is_ok());
+}
+
+#[test]
+fn test_matches_mapping_entry() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    let mapping_name = Identifier::from_str("balances").unwrap();
+    let address = Plaintext::from_str("aleo1qr2ha4pfs5l28aze88yn6fhleeythklkczrule2v838uwj65n5gqxt9djx").unwrap();
+    let amount = Plaintext::from_str("5u64").unwrap();
+
+    // Ensure a well-typed entry passes.
+    assert!(stack.matches_mapping_entry(&mapping_name, &address, &amount).is_ok());
+
+    // Ensure mistyped keys and values fail, naming the mapping.
+    let error = stack.matches_mapping_entry(&mapping_name, &amount, &amount).unwrap_err();
+    assert!(error.to_string().contains("key type of mapping 'balances'"));
+    let error = stack.matches_mapping_entry(&mapping_name, &address, &address).unwrap_err();
+    assert!(error.to_string().contains("value type of mapping 'balances'"));
+
+    // Ensure an unknown mapping fails.
+    assert!(stack.matches_mapping_entry(&Identifier::from_str("unknown").unwrap(), &address, &amount).is_err());
+}
+
+#[test]
+fn test_validate_or_use_default() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    let mapping_name = Identifier::from_str("balances").unwrap();
+
+    // Ensure a default of the value type passes.
+    assert!(stack.validate_or_use_default(&mapping_name, &Plaintext::from_str("0u64").unwrap()).is_ok());
+
+    // Ensure a default of another type fails, naming the mapping.
+    let error = stack.validate_or_use_default(&mapping_name, &Plaintext::from_str("0u32").unwrap()).unwrap_err();
+    assert!(error.to_string().contains("value type of mapping 'balances'"));
+
+    // Ensure an unknown mapping fails.
+    let unknown = Identifier::from_str("unknown").unwrap();
+    assert!(stack.validate_or_use_default(&unknown, &Plaintext::from_str("0u64").unwrap()).is_err());
+}
+
+#[test]
+fn test_set_record_observer() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let program = Program::from_str(
+        r"
+program record_observer.aleo;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function mint:
+    input r0 as address.private;
+    input r1 as u64.private;
+    cast r0 r1 into r2 as token.record;
+    output r2 as token.record;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+
+    // Initialize the registers.
+    let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+    let mut registers = sample_registers_for(stack, "mint", &[&address.to_string(), "5u64"], rng);
+
+    // Set an observer that collects the stored records.
+    let observed = Arc::new(Mutex::new(Vec::new()));
+    let observed_clone = observed.clone();
+    registers.set_record_observer(Box::new(move |record| observed_clone.lock().push(record.clone())));
+
+    // Ensure storing non-record values does not notify the observer.
+    registers.store(stack, &Register::Locator(0), Value::from_str(&address.to_string()).unwrap()).unwrap();
+    registers.store(stack, &Register::Locator(1), Value::from_str("5u64").unwrap()).unwrap();
+    assert!(observed.lock().is_empty());
+
+    // Ensure storing a record notifies the observer.
+    let record =
+        Value::from_str(&format!("{{ owner: {address}.private, amount: 5u64.private, _nonce: 0group.public }}"))
+            .unwrap();
+    registers.store(stack, &Register::Locator(2), record.clone()).unwrap();
+    assert_eq!(observed.lock().len(), 1);
+    assert_eq!(Value::Record(observed.lock()[0].clone()), record);
+
+    // Ensure a failed store of a record does not notify the observer.
+    assert!(registers.store(stack, &Register::Locator(2), record).is_err());
+    assert_eq!(observed.lock().len(), 1);
+
+    // Ensure an observer set in the options is notified of the records stored by an evaluation.
+    let process = crate::test_helpers::sample_process(&program);
+    let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let mint = Identifier::from_str("mint").unwrap();
+    let inputs = [address.to_string(), "5u64".to_string()];
+    let authorization =
+        process.authorize::<CurrentAleo, _>(&private_key, program.id(), mint, inputs.iter(), rng).unwrap();
+    let observed = Arc::new(Mutex::new(Vec::new()));
+    let observed_clone = observed.clone();
+    let mut options = ExecutionOptions::default();
+    options.set_record_observer(Box::new(move |record| observed_clone.lock().push(record.clone())));
+    process.evaluate_with_options::<CurrentAleo>(authorization, &options).unwrap();
+    assert_eq!(observed.lock().len(), 1);
+    assert_eq!(**observed.lock()[0].owner(), address);
+}
+
+#[test]
+fn test_verify_acyclic_structs() {
+    // Initialize the stack.
+    let program = Program::from_str(
+        r"
+program acyclic_structs.aleo;
+
+struct point:
+    x as field;
+    y as field;
+
+struct segment:
+    start as point;
+    end as point;
+
+struct path:
+    segments as [segment; 4u32];
+
+function foo:
+    input r0 as path.private;
+    output r0 as path.private;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+
+    // Ensure nested and repeated struct references are not reported as cycles.
+    assert!(stack.verify_acyclic_structs().is_ok());
+
+    // Initialize structs with a cycle through an array, which the parser rejects, as each refers to a later struct.
+    let structs = [
+        "struct point:\n    x as field;\n    y as field;",
+        "struct node:\n    origin as point;\n    next as edge;",
+        "struct edge:\n    targets as [node; 2u32];",
+    ]
+    .into_iter()
+    .map(|struct_| {
+        let struct_ = StructType::<CurrentNetwork>::from_str(struct_).unwrap();
+        (*struct_.name(), struct_)
+    })
+    .collect::<IndexMap<_, _>>();
+
+    // Ensure the cycle is reported, starting from the first struct on the cycle.
+    let error = Stack::verify_acyclic_struct_types(&structs).unwrap_err();
+    assert_eq!(error.to_string(), "Struct 'node' is recursive: node -> edge -> node");
+
+    // Ensure a struct that refers to itself is reported.
+    let struct_ = StructType::<CurrentNetwork>::from_str("struct list:\n    head as field;\n    tail as list;").unwrap();
+    let structs = IndexMap::from([(*struct_.name(), struct_)]);
+    let error = Stack::verify_acyclic_struct_types(&structs).unwrap_err();
+    assert_eq!(error.to_string(), "Struct 'list' is recursive: list -> list");
+}
+
+#[test]
+fn test_operand_plaintext_type() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let registers = sample_registers(stack, rng);
+    let plaintext_type = |operand: &str| registers.operand_plaintext_type(stack, &Operand::from_str(operand).unwrap());
+
+    // Ensure the types resolve without any values in the registers.
+    assert_eq!(plaintext_type("r0").unwrap(), PlaintextType::from_str("field").unwrap());
+    assert_eq!(plaintext_type("r2").unwrap(), PlaintextType::from_str("field").unwrap());
+    assert_eq!(plaintext_type("5u8").unwrap(), PlaintextType::from_str("u8").unwrap());
+    assert_eq!(plaintext_type("self.caller").unwrap(), PlaintextType::from_str("address").unwrap());
+
+    // Ensure an undefined register fails.
+    assert!(plaintext_type("r9").is_err());
+
+    // Ensure the finalize types are consulted in a finalize scope.
+    let function_name = Identifier::from_str("deposit").unwrap();
+    let finalize_registers = FinalizeRegisters::<CurrentNetwork>::new(
+        FinalizeGlobalState::from(1, 1, [0; 32]),
+        <CurrentNetwork as Network>::TransitionID::default(),
+        function_name,
+        stack.get_finalize_types(&function_name).unwrap().clone(),
+    );
+    let plaintext_type =
+        |operand: &str| finalize_registers.operand_plaintext_type(stack, &Operand::from_str(operand).unwrap());
+    assert_eq!(plaintext_type("r0").unwrap(), PlaintextType::from_str("address").unwrap());
+    assert_eq!(plaintext_type("r3").unwrap(), PlaintextType::from_str("u64").unwrap());
+    assert_eq!(plaintext_type("block.height").unwrap(), PlaintextType::from_str("u32").unwrap());
+}
+
+#[test]
+fn test_matches_value_types() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    let value_types = [ValueType::from_str("field.public").unwrap(), ValueType::from_str("u64.private").unwrap()];
+
+    // Ensure well-typed values pass.
+    let values = [Value::from_str("1field").unwrap(), Value::from_str("2u64").unwrap()];
+    assert!(stack.matches_value_types(&values, &value_types).is_ok());
+    assert!(stack.matches_value_types(&[], &[]).is_ok());
+
+    // Ensure a count mismatch fails.
+    let error = stack.matches_value_types(&values[..1], &value_types).unwrap_err();
+    assert_eq!(error.to_string(), "Expected 2 values, found 1");
+
+    // Ensure a mistyped value reports its index.
+    let values = [Value::from_str("1field").unwrap(), Value::from_str("2u32").unwrap()];
+    let error = stack.matches_value_types(&values, &value_types).unwrap_err();
+    assert!(error.to_string().starts_with("Value 1 does not match 'u64.private'"));
+}
+
+#[test]
+fn test_finalize_uses_randomness() {
+    // Initialize the stack.
+    let program = Program::from_str(
+        r"
+program finalize_randomness.aleo;
+
+function roll:
+    async roll into r0;
+    output r0 as finalize_randomness.aleo/roll.future;
+
+finalize roll:
+    rand.chacha into r0 as u8;
+
+function count:
+    async count into r0;
+    output r0 as finalize_randomness.aleo/count.future;
+
+finalize count:
+    add block.height 1u32 into r0;
+
+function noop:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+    let uses_randomness = |name: &str| stack.finalize_uses_randomness(&Identifier::from_str(name).unwrap());
+
+    assert!(uses_randomness("roll").unwrap());
+    assert!(!uses_randomness("count").unwrap());
+    assert!(!uses_randomness("noop").unwrap());
+    assert!(uses_randomness("unknown").is_err());
+}
+
+#[test]
+fn test_display_value() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let display = |value: &str, value_type: &str| {
+        stack.display_value(&Value::from_str(value).unwrap(), &ValueType::from_str(value_type).unwrap())
+    };
+
+    // Check literals, structs, and arrays.
+    assert_eq!(display("1field", "field.public").unwrap(), "1field");
+    assert_eq!(display("{ x: 1field, y: 2field }", "point.private").unwrap(), "point { x: 1field, y: 2field }");
+    assert_eq!(
+        display("[{ x: 1field, y: 2field }]", "[point; 1u32].private").unwrap(),
+        "[point { x: 1field, y: 2field }]"
+    );
+
+    // Check records.
+    let address = "aleo1qr2ha4pfs5l28aze88yn6fhleeythklkczrule2v838uwj65n5gqxt9djx";
+    let record = format!("{{ owner: {address}.private, amount: 5u64.private, _nonce: 0group.public }}");
+    assert_eq!(display(&record, "token.record").unwrap(), format!("token {{ owner: {address}, amount: 5u64 }}"));
+
+    // Ensure a mistyped value fails.
+    assert!(display("1field", "point.private").is_err());
+}
+
+#[test]
+fn test_load_program_address() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let program = sample_program();
+    let stack = &sample_stack(&program);
+    let registers = sample_registers(stack, rng);
+
+    // Ensure the address is derived from the program ID.
+    let expected = program.id().to_address().unwrap();
+    assert_eq!(registers.load_program_address(stack).unwrap(), expected);
+
+    // Ensure the finalize registers resolve the same address.
+    let function_name = Identifier::from_str("deposit").unwrap();
+    let finalize_registers = FinalizeRegisters::<CurrentNetwork>::new(
+        FinalizeGlobalState::from(1, 1, [0; 32]),
+        <CurrentNetwork as Network>::TransitionID::default(),
+        function_name,
+        stack.get_finalize_types(&function_name).unwrap().clone(),
+    );
+    assert_eq!(finalize_registers.load_program_address(stack).unwrap(), expected);
+}
+
+#[test]
+fn test_instruction_counts() {
+    // Initialize the stack.
+    let program = sample_program();
+    let stack = &sample_stack(&program);
+
+    // Ensure the functions are counted in program order, and closures are excluded.
+    let counts = stack.instruction_counts();
+    let expected = [("foo", (1, 0)), ("bar", (2, 0)), ("deposit", (1, 3))];
+    assert_eq!(counts.len(), expected.len());
+    for ((name, count), (expected_name, expected_count)) in counts.iter().zip(expected) {
+        assert_eq!(name.to_string(), expected_name);
+        assert_eq!(*count, expected_count);
+    }
+}
+
+#[test]
+fn test_substitute_operand() {
+    // Initialize the stack.
+    let program = sample_program();
+    let process = crate::test_helpers::sample_process(&program);
+    let mut stack = process.get_stack(program.id()).unwrap().clone();
+    let foo = Identifier::from_str("foo").unwrap();
+    let bar = Identifier::from_str("bar").unwrap();
+
+    // Substitute a register with another register of the same type.
+    stack.substitute_operand(&foo, 0, 1, Operand::from_str("r0").unwrap()).unwrap();
+    assert_eq!(stack.get_function_ref(&foo).unwrap().instructions()[0].to_string(), "add r0 r0 into r2;");
+
+    // Substitute a register with a literal of the same type.
+    stack.substitute_operand(&foo, 0, 0, Operand::from_str("3field").unwrap()).unwrap();
+    assert_eq!(stack.get_function_ref(&foo).unwrap().instructions()[0].to_string(), "add 3field r0 into r2;");
+
+    // Substitute the argument of a call.
+    stack.substitute_operand(&bar, 1, 0, Operand::from_str("r0").unwrap()).unwrap();
+    assert_eq!(stack.get_function_ref(&bar).unwrap().instructions()[1].to_string(), "call fourfold r0 into r2;");
+
+    // Ensure substitutions that change the register type fail.
+    assert!(stack.substitute_operand(&foo, 0, 1, Operand::from_str("3u8").unwrap()).is_err());
+    // Ensure substitutions of registers that are not yet written fail.
+    assert!(stack.substitute_operand(&bar, 0, 0, Operand::from_str("r2").unwrap()).is_err());
+    // Ensure out-of-bounds substitutions fail.
+    assert!(stack.substitute_operand(&foo, 1, 0, Operand::from_str("r0").unwrap()).is_err());
+    assert!(stack.substitute_operand(&foo, 0, 2, Operand::from_str("r0").unwrap()).is_err());
+
+    // Ensure the original stack is unchanged.
+    let original = process.get_stack(program.id()).unwrap();
+    assert_eq!(original.get_function_ref(&foo).unwrap().instructions()[0].to_string(), "add r0 r1 into r2;");
+}
+
+#[test]
+fn test_substitute_operand_string_literal() {
+    // Initialize a program with string literal operands.
+    let program = Program::<CurrentNetwork>::from_str(
+        r#"
+program strings.aleo;
+
+function compare:
+    input r0 as field.private;
+    is.eq "hello world" "good bye" into r1;
+    output r1 as boolean.private;"#,
+    )
+    .unwrap();
+    let mut stack = sample_stack(&program);
+    let compare = Identifier::from_str("compare").unwrap();
+
+    // Substitute a string literal containing spaces with another.
+    stack.substitute_operand(&compare, 0, 1, Operand::from_str(r#""hello world""#).unwrap()).unwrap();
+    let instruction = &stack.get_function_ref(&compare).unwrap().instructions()[0];
+    assert_eq!(instruction.to_string(), r#"is.eq "hello world" "hello world" into r1;"#);
+    assert_eq!(instruction.operands()[1], Operand::from_str(r#""hello world""#).unwrap());
+}
+
+#[test]
+fn test_substitute_operand_identical_functions() {
+    // Initialize a program with two functions of identical bodies.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program twins.aleo;
+
+function first:
+    input r0 as field.private;
+    input r1 as field.private;
+    add r0 r1 into r2;
+    output r2 as field.private;
+
+function second:
+    input r0 as field.private;
+    input r1 as field.private;
+    add r0 r1 into r2;
+    output r2 as field.private;",
+    )
+    .unwrap();
+    let mut stack = sample_stack(&program);
+    let first = Identifier::from_str("first").unwrap();
+    let second = Identifier::from_str("second").unwrap();
+
+    // Substitute an operand in the second function.
+    stack.substitute_operand(&second, 0, 1, Operand::from_str("r0").unwrap()).unwrap();
+
+    // Ensure only the second function is changed.
+    assert_eq!(stack.get_function_ref(&first).unwrap().instructions()[0].to_string(), "add r0 r1 into r2;");
+    assert_eq!(stack.get_function_ref(&second).unwrap().instructions()[0].to_string(), "add r0 r0 into r2;");
+}
+
+#[test]
+fn test_assert_array_elements_unique() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let assert_unique = |plaintext: &str| stack.assert_array_elements_unique(&Plaintext::from_str(plaintext).unwrap());
+
+    // Ensure distinct elements pass.
+    assert!(assert_unique("[1u8, 2u8, 3u8]").is_ok());
+    assert!(assert_unique("[{ x: 1field, y: 2field }, { x: 2field, y: 1field }]").is_ok());
+
+    // Ensure duplicate elements fail, naming the duplicate.
+    let error = assert_unique("[1u8, 2u8, 1u8]").unwrap_err();
+    assert_eq!(error.to_string(), "Array element '1u8' is duplicated");
+    assert!(assert_unique("[[1u8, 2u8], [1u8, 2u8]]").is_err());
+
+    // Ensure non-arrays fail.
+    assert!(assert_unique("1u8").is_err());
+}
+
+#[test]
+fn test_transitive_imports() {
+    // Initialize the programs, where `third` imports `first` both directly and through `second`.
+    let first = Program::<CurrentNetwork>::from_str(
+        r"
+program first.aleo;
+
+function foo:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let second = Program::<CurrentNetwork>::from_str(
+        r"
+import first.aleo;
+
+program second.aleo;
+
+function bar:
+    input r0 as field.private;
+    call first.aleo/foo r0 into r1;
+    output r1 as field.private;",
+    )
+    .unwrap();
+    let third = Program::<CurrentNetwork>::from_str(
+        r"
+import first.aleo;
+import second.aleo;
+
+program third.aleo;
+
+function baz:
+    input r0 as field.private;
+    call second.aleo/bar r0 into r1;
+    call first.aleo/foo r1 into r2;
+    output r2 as field.private;",
+    )
+    .unwrap();
+
+    // Initialize the process.
+    let mut process = crate::test_helpers::sample_process(&first);
+    process.add_program(&second).unwrap();
+    process.add_program(&third).unwrap();
+
+    // Ensure the imports are returned in dependency order, without duplicates.
+    let transitive_imports = |program: &Program<CurrentNetwork>| {
+        let imports = process.get_stack(program.id()).unwrap().transitive_imports().unwrap();
+        imports.iter().map(ToString::to_string).collect::<Vec<_>>()
+    };
+    assert_eq!(transitive_imports(&third), ["first.aleo", "second.aleo"]);
+    assert_eq!(transitive_imports(&second), ["first.aleo"]);
+    assert!(transitive_imports(&first).is_empty());
+}
+
+#[test]
+fn test_store_with_commitment() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let mut registers = sample_registers(stack, rng);
+
+    // Store a value, and ensure the commitment is to the stored value.
+    let value = Value::from_str("1field").unwrap();
+    let commitment = registers.store_with_commitment(stack, &Register::Locator(0), value.clone()).unwrap();
+    assert_eq!(commitment, CurrentNetwork::hash_bhp1024(&value.to_bits_le()).unwrap());
+    assert_eq!(registers.load(stack, &Operand::from_str("r0").unwrap()).unwrap(), value);
+
+    // Ensure a different value has a different commitment.
+    let other = registers.store_with_commitment(stack, &Register::Locator(1), Value::from_str("2field").unwrap());
+    assert_ne!(other.unwrap(), commitment);
+
+    // Ensure an invalid store fails.
+    assert!(registers.store_with_commitment(stack, &Register::Locator(1), value).is_err());
+}
+
+#[test]
+fn test_validate_against_schema() {
+    // Initialize the stack.
+    let program = sample_program();
+    let stack = &sample_stack(&program);
+    let schema = |opcodes: &[&str], max_call_depth: usize| {
+        ProgramSchema::new(opcodes.iter().map(ToString::to_string).collect(), max_call_depth, false)
+    };
+    let opcodes = ["add", "call", "async", "get.or_use", "set"];
+
+    // Ensure a schema that permits the program passes.
+    assert!(stack.validate_against_schema(&schema(&opcodes, 1)).is_ok());
+
+    // Ensure a disallowed instruction fails.
+    let error = stack.validate_against_schema(&schema(&["add", "async", "get.or_use", "set"], 1)).unwrap_err();
+    assert_eq!(error.to_string(), "Opcode 'call' in 'bar' is not permitted");
+    // Ensure a disallowed finalize command fails.
+    let error = stack.validate_against_schema(&schema(&["add", "call", "async", "get.or_use"], 1)).unwrap_err();
+    assert_eq!(error.to_string(), "Opcode 'set' in the finalize scope of 'deposit' is not permitted");
+    // Ensure an excessive call depth fails.
+    assert!(stack.validate_against_schema(&schema(&opcodes, 0)).is_err());
+}
+
+#[test]
+fn test_const_value() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let mut registers = sample_registers(stack, rng);
+    registers.store(stack, &Register::Locator(0), Value::from_str("1field").unwrap()).unwrap();
+    let const_value = |operand: &str| registers.const_value(&Operand::from_str(operand).unwrap());
+
+    // Ensure literals are constant.
+    assert_eq!(const_value("5u8"), Some(Value::from_str("5u8").unwrap()));
+    // Ensure registers are not constant, even if they are assigned.
+    assert_eq!(const_value("r0"), None);
+    assert_eq!(const_value("self.caller"), None);
+}
+
+#[test]
+fn test_extract_record_field() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    let address = "aleo1qr2ha4pfs5l28aze88yn6fhleeythklkczrule2v838uwj65n5gqxt9djx";
+    let Value::Record(record) =
+        Value::from_str(&format!("{{ owner: {address}.private, amount: 5u64.private, _nonce: 0group.public }}"))
+            .unwrap()
+    else {
+        unreachable!()
+    };
+    let extract = |field: &str| stack.extract_record_field(&record, &Identifier::from_str(field).unwrap());
+
+    // Ensure the entries and owner are extracted.
+    assert_eq!(extract("amount").unwrap(), Plaintext::from_str("5u64").unwrap());
+    assert_eq!(extract("owner").unwrap(), Plaintext::from_str(address).unwrap());
+    // Ensure a missing entry fails.
+    assert_eq!(extract("balance").unwrap_err().to_string(), "Record does not contain an entry named 'balance'");
+}
+
+#[test]
+fn test_derive_all_register_types_parallel() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    // Ensure the parallel derivation matches the register types computed when the stack was initialized.
+    let register_types = stack.derive_all_register_types_parallel().unwrap();
+    let names = ["twice", "fourfold", "foo", "bar", "deposit"];
+    assert_eq!(register_types.keys().map(ToString::to_string).collect::<Vec<_>>(), names);
+    for (name, types) in &register_types {
+        assert!(types == stack.get_register_types(name).unwrap());
+    }
+}
+
+#[test]
+fn test_call_allowlist() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the programs, where `caller` calls `middle`, which in turn calls `inner`.
+    let inner = Program::<CurrentNetwork>::from_str(
+        r"
+program inner.aleo;
+
+function foo:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let middle = Program::<CurrentNetwork>::from_str(
+        r"
+import inner.aleo;
+
+program middle.aleo;
+
+function bar:
+    input r0 as field.private;
+    call inner.aleo/foo r0 into r1;
+    output r1 as field.private;",
+    )
+    .unwrap();
+    let caller = Program::<CurrentNetwork>::from_str(
+        r"
+import inner.aleo;
+import middle.aleo;
+
+program caller.aleo;
+
+function baz:
+    input r0 as field.private;
+    call middle.aleo/bar r0 into r1;
+    output r1 as field.private;",
+    )
+    .unwrap();
+
+    // Initialize the process.
+    let mut process = crate::test_helpers::sample_process(&inner);
+    process.add_program(&middle).unwrap();
+    process.add_program(&caller).unwrap();
+    let stack = process.get_stack(caller.id()).unwrap();
+
+    // Initialize the registers.
+    let mut registers = sample_registers_for(stack, "baz", &["1field"], rng);
+
+    // Ensure every call is permitted without an allowlist.
+    assert!(registers.check_call_permitted(stack, middle.id()).is_ok());
+
+    // Ensure a call is not permitted if the callee can reach a program outside the allowlist.
+    registers.set_call_allowlist([*middle.id()].into_iter().collect());
+    let error = registers.check_call_permitted(stack, middle.id()).unwrap_err();
+    assert_eq!(error.to_string(), "call to inner.aleo not permitted");
+
+    // Ensure a call is permitted if the callee and its imports are in the allowlist.
+    registers.set_call_allowlist([*middle.id(), *inner.id()].into_iter().collect());
+    assert!(registers.check_call_permitted(stack, middle.id()).is_ok());
+    assert!(registers.check_call_permitted(stack, inner.id()).is_ok());
+
+    // Authorize a call to `baz`.
+    let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let function_name = Identifier::from_str("baz").unwrap();
+    let authorization = process
+        .authorize::<CurrentAleo, _>(&private_key, caller.id(), function_name, ["1field"].into_iter(), rng)
+        .unwrap();
+
+    // Ensure an allowlist set in the options is enforced, as `middle` can reach `inner`.
+    let mut options = ExecutionOptions::default();
+    options.set_call_allowlist([*middle.id()].into_iter().collect());
+    let error = process.evaluate_with_options::<CurrentAleo>(authorization.replicate(), &options).unwrap_err();
+    assert!(error.to_string().contains("call to inner.aleo not permitted"), "{error}");
+
+    // Ensure the evaluation succeeds if every reachable program is in the allowlist.
+    options.set_call_allowlist([*middle.id(), *inner.id()].into_iter().collect());
+    let response = process.evaluate_with_options::<CurrentAleo>(authorization, &options).unwrap();
+    assert_eq!(response.outputs(), [Value::from_str("1field").unwrap()]);
+}
+
+#[test]
+fn test_registers_recorder() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let mut registers = sample_registers(stack, rng);
+
+    // Ensure nothing is recorded before recording starts.
+    registers.store(stack, &Register::Locator(0), Value::from_str("1field").unwrap()).unwrap();
+    assert!(registers.take_trace().steps().is_empty());
+
+    // Record a load from a register, a load of a literal, and a store.
+    registers.start_recording();
+    let (r0, literal) = (Operand::from_str("r0").unwrap(), Operand::from_str("2field").unwrap());
+    registers.load(stack, &r0).unwrap();
+    registers.load(stack, &literal).unwrap();
+    registers.store(stack, &Register::Locator(1), Value::from_str("3field").unwrap()).unwrap();
+
+    // Ensure the steps are recorded in execution order.
+    let trace = registers.take_trace();
+    assert_eq!(trace.steps(), [
+        TraceStep::Load(r0.clone(), Value::from_str("1field").unwrap()),
+        TraceStep::Load(literal.clone(), Value::from_str("2field").unwrap()),
+        TraceStep::Store(Register::Locator(1), Value::from_str("3field").unwrap()),
+    ]);
+
+    // Ensure taking the trace stops recording.
+    registers.load(stack, &r0).unwrap();
+    assert!(registers.take_trace().steps().is_empty());
+
+    // Ensure the loads and stores of an instruction are recorded under the instruction, and a failed store is not recorded.
+    registers.start_recording();
+    let instruction = Instruction::from_str("add r0 r1 into r2;").unwrap();
+    registers.begin_instruction(&instruction);
+    registers.load(stack, &r0).unwrap();
+    assert!(registers.store(stack, &Register::Locator(0), Value::from_str("5field").unwrap()).is_err());
+    registers.store(stack, &Register::Locator(2), Value::from_str("5field").unwrap()).unwrap();
+    registers.end_instruction();
+    registers.load(stack, &literal).unwrap();
+    assert_eq!(registers.take_trace().steps(), [
+        TraceStep::Instruction(instruction, vec![
+            TraceStep::Load(r0, Value::from_str("1field").unwrap()),
+            TraceStep::Store(Register::Locator(2), Value::from_str("5field").unwrap()),
+        ]),
+        TraceStep::Load(literal, Value::from_str("2field").unwrap()),
+    ]);
+}
+
+#[test]
+fn test_trace_sink() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the process.
+    let program = sample_program();
+    let process = crate::test_helpers::sample_process(&program);
+
+    // Authorize a call to `bar`, which calls the closures `twice` and `fourfold`.
+    let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let bar = Identifier::from_str("bar").unwrap();
+    let authorization =
+        process.authorize::<CurrentAleo, _>(&private_key, program.id(), bar, ["1field"].into_iter(), rng).unwrap();
+
+    // Evaluate `bar`, with a trace sink.
+    let trace_sink = Arc::new(Mutex::new(Vec::new()));
+    let mut options = ExecutionOptions::default();
+    options.set_trace_sink(trace_sink.clone());
+    process.evaluate_with_options::<CurrentAleo>(authorization, &options).unwrap();
+
+    // Ensure the trace of each callee is added before that of its caller.
+    let traces = trace_sink.lock().clone();
+    let locators = traces.iter().map(|(locator, _)| locator.to_string()).collect::<Vec<_>>();
+    assert_eq!(locators, ["stack_helpers.aleo/twice", "stack_helpers.aleo/fourfold", "stack_helpers.aleo/bar"]);
+
+    // Ensure the trace of a closure records its inputs, instructions, and outputs.
+    let value = |value: &str| Value::<CurrentNetwork>::from_str(value).unwrap();
+    let (r0, r1, r2) = (Register::Locator(0), Register::Locator(1), Register::Locator(2));
+    assert_eq!(traces[0].1.steps(), [
+        TraceStep::Store(r0.clone(), value("1field")),
+        TraceStep::Instruction(Instruction::from_str("add r0 r0 into r1;").unwrap(), vec![
+            TraceStep::Load(Operand::Register(r0.clone()), value("1field")),
+            TraceStep::Load(Operand::Register(r0.clone()), value("1field")),
+            TraceStep::Store(r1.clone(), value("2field")),
+        ]),
+        TraceStep::Load(Operand::Register(r1.clone()), value("2field")),
+    ]);
+
+    // Ensure the trace of the function records the operands and destinations of its calls.
+    assert_eq!(traces[2].1.steps(), [
+        TraceStep::Store(r0.clone(), value("1field")),
+        TraceStep::Instruction(Instruction::from_str("call twice r0 into r1;").unwrap(), vec![
+            TraceStep::Load(Operand::Register(r0), value("1field")),
+            TraceStep::Store(r1.clone(), value("2field")),
+        ]),
+        TraceStep::Instruction(Instruction::from_str("call fourfold r1 into r2;").unwrap(), vec![
+            TraceStep::Load(Operand::Register(r1), value("2field")),
+            TraceStep::Store(r2.clone(), value("8field")),
+        ]),
+        TraceStep::Load(Operand::Register(r2), value("8field")),
+    ]);
+}
+
+#[test]
+fn test_output_input_dependencies() {
+    // Initialize the stack.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program dependencies.aleo;
+
+closure twice:
+    input r0 as field;
+    add r0 r0 into r1;
+    output r1 as field;
+
+function flows:
+    input r0 as field.private;
+    input r1 as field.private;
+    input r2 as field.public;
+    add r0 r1 into r3;
+    call twice r3 into r4;
+    mul r2 2field into r5;
+    output r4 as field.public;
+    output r5 as field.public;
+    output r2 as field.public;
+    output 1field as field.public;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+
+    // Ensure each output depends on exactly the inputs that flow into it.
+    let dependencies = stack.output_input_dependencies(&Identifier::from_str("flows").unwrap()).unwrap();
+    let expected: [&[usize]; 4] = [&[0, 1], &[2], &[2], &[]];
+    assert_eq!(dependencies.len(), expected.len());
+    for (dependencies, expected) in dependencies.iter().zip(expected) {
+        assert!(dependencies.iter().eq(expected));
+    }
+
+    // Ensure an unknown function fails.
+    assert!(stack.output_input_dependencies(&Identifier::from_str("unknown").unwrap()).is_err());
+}
+
+#[test]
+fn test_semantic_hash() {
+    let semantic_hash = |source: &str| {
+        let program = Program::<CurrentNetwork>::from_str(source).unwrap();
+        sample_stack(&program).semantic_hash().unwrap()
+    };
+
+    // Initialize the program.
+    let hash = semantic_hash(
+        r"
+program semantic_hash.aleo;
+
+function foo:
+    input r0 as field.private;
+    add r0 r0 into r1;
+    output r1 as field.private;",
+    );
+
+    // Ensure a reformatting of the program does not change the hash.
+    let reformatted = semantic_hash(
+        r"// A reformatted program.
+program semantic_hash.aleo;
+function foo:
+  input r0 as field.private; // The input.
+  add   r0 r0   into r1;
+  output r1 as field.private;",
+    );
+    assert_eq!(hash, reformatted);
+
+    // Ensure a change to an instruction changes the hash.
+    let modified = semantic_hash(
+        r"
+program semantic_hash.aleo;
+
+function foo:
+    input r0 as field.private;
+    mul r0 r0 into r1;
+    output r1 as field.private;",
+    );
+    assert_ne!(hash, modified);
+}
+
+#[test]
+fn test_load_array_slice() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program array_slice.aleo;
+
+function window:
+    input r0 as [u8; 4u32].private;
+    input r1 as u8.private;
+    output r0[1u32] as u8.private;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+
+    // Initialize the registers.
+    let inputs = ["[1u8, 2u8, 3u8, 4u8]", "5u8"];
+    let mut registers = sample_registers_for(stack, "window", &inputs, rng);
+    registers.store(stack, &Register::Locator(0), Value::from_str(inputs[0]).unwrap()).unwrap();
+    registers.store(stack, &Register::Locator(1), Value::from_str(inputs[1]).unwrap()).unwrap();
+    let array = Operand::from_str("r0").unwrap();
+    let slice = |range| registers.load_array_slice(stack, &array, range);
+
+    // Ensure the requested elements are loaded.
+    let expected = [Plaintext::from_str("2u8").unwrap(), Plaintext::from_str("3u8").unwrap()];
+    assert_eq!(slice(1..3).unwrap(), expected);
+    assert_eq!(slice(0..4).unwrap().len(), 4);
+    assert!(slice(4..4).unwrap().is_empty());
+
+    // Ensure out-of-bounds ranges fail, reporting the length of the array.
+    assert_eq!(slice(2..5).unwrap_err().to_string(), "Range 2..5 is out of bounds for an array of length 4");
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = slice(3..1);
+    assert!(reversed.is_err());
+
+    // Ensure non-arrays fail.
+    assert!(registers.load_array_slice(stack, &Operand::from_str("r1").unwrap(), 0..1).is_err());
+}
+
+#[test]
+fn test_external_record_dependencies() {
+    // Initialize the programs, where `wallet` consumes the records of `coins`.
+    let token = Program::<CurrentNetwork>::from_str(
+        r"
+program coins.aleo;
+
+record coin:
+    owner as address.private;
+    amount as u64.private;
+
+record note:
+    owner as address.private;
+    memo as field.private;
+
+function mint:
+    input r0 as u64.private;
+    cast self.caller r0 into r1 as coin.record;
+    output r1 as coin.record;",
+    )
+    .unwrap();
+    let wallet = Program::<CurrentNetwork>::from_str(
+        r"
+import coins.aleo;
+
+program wallet.aleo;
+
+function merge:
+    input r0 as coins.aleo/coin.record;
+    input r1 as field.private;
+    input r2 as coins.aleo/note.record;
+    input r3 as coins.aleo/coin.record;
+    output r1 as field.private;
+
+function noop:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let mut process = crate::test_helpers::sample_process(&token);
+    process.add_program(&wallet).unwrap();
+    let stack = process.get_stack(wallet.id()).unwrap();
+    let dependencies = |name: &str| stack.external_record_dependencies(&Identifier::from_str(name).unwrap());
+
+    // Ensure the external record types are returned in input order, without duplicates.
+    let expected = [Locator::from_str("coins.aleo/coin").unwrap(), Locator::from_str("coins.aleo/note").unwrap()];
+    assert_eq!(dependencies("merge").unwrap(), expected);
+    assert!(dependencies("noop").unwrap().is_empty());
+    assert!(dependencies("unknown").is_err());
+}
+
+#[test]
+fn test_max_call_depth() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the programs, where `top` calls `middle`, which in turn calls `inner`.
+    let inner = Program::<CurrentNetwork>::from_str(
+        r"
+program inner.aleo;
+
+function foo:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let middle = Program::<CurrentNetwork>::from_str(
+        r"
+import inner.aleo;
+
+program middle.aleo;
+
+closure twice:
+    input r0 as field;
+    add r0 r0 into r1;
+    output r1 as field;
+
+function bar:
+    input r0 as field.private;
+    call twice r0 into r1;
+    call inner.aleo/foo r1 into r2;
+    output r2 as field.private;",
+    )
+    .unwrap();
+    let top = Program::<CurrentNetwork>::from_str(
+        r"
+import inner.aleo;
+import middle.aleo;
+
+program top.aleo;
+
+function baz:
+    input r0 as field.private;
+    call middle.aleo/bar r0 into r1;
+    output r1 as field.private;",
+    )
+    .unwrap();
+
+    // Initialize the process.
+    let mut process = crate::test_helpers::sample_process(&inner);
+    process.add_program(&middle).unwrap();
+    process.add_program(&top).unwrap();
+    let stack = process.get_stack(top.id()).unwrap();
+    let middle_stack = process.get_stack(middle.id()).unwrap();
+
+    // Initialize the registers.
+    let mut registers = sample_registers_for(stack, "baz", &["1field"], rng);
+    let bar = Identifier::from_str("bar").unwrap();
+    let twice = Identifier::from_str("twice").unwrap();
+
+    // Ensure every call is permitted without a limit.
+    assert!(registers.check_call_depth(middle_stack, &bar).is_ok());
+
+    // Ensure a call that nests deeper than the limit fails.
+    registers.set_max_call_depth(1);
+    assert_eq!(registers.check_call_depth(middle_stack, &bar).unwrap_err().to_string(), "max call depth 1 exceeded");
+    assert!(registers.check_call_depth(middle_stack, &twice).is_ok());
+
+    // Ensure a call within the limit passes.
+    registers.set_max_call_depth(2);
+    assert!(registers.check_call_depth(middle_stack, &bar).is_ok());
+
+    // Ensure the call depths are cached when the stacks are initialized.
+    assert_eq!(middle_stack.get_call_depth(&twice).unwrap(), 0);
+    assert_eq!(middle_stack.get_call_depth(&bar).unwrap(), 1);
+    assert_eq!(stack.get_call_depth(&Identifier::from_str("baz").unwrap()).unwrap(), 2);
+    assert!(stack.get_call_depth(&bar).is_err());
+
+    // Authorize a call to `baz`.
+    let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let function_name = Identifier::from_str("baz").unwrap();
+    let authorization = process
+        .authorize::<CurrentAleo, _>(&private_key, top.id(), function_name, ["1field"].into_iter(), rng)
+        .unwrap();
+
+    // Ensure a limit set in the options is enforced.
+    let mut options = ExecutionOptions::default();
+    options.set_max_call_depth(1);
+    let error = process.evaluate_with_options::<CurrentAleo>(authorization.replicate(), &options).unwrap_err();
+    assert!(error.to_string().contains("max call depth 1 exceeded"), "{error}");
+
+    // Ensure the evaluation, including the nested calls, succeeds within the limit.
+    options.set_max_call_depth(2);
+    let response = process.evaluate_with_options::<CurrentAleo>(authorization, &options).unwrap();
+    assert_eq!(response.outputs(), [Value::from_str("2field").unwrap()]);
+}
+
+#[test]
+fn test_matches_response_outputs() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let program = sample_program();
+    let process = crate::test_helpers::sample_process(&program);
+    let stack = process.get_stack(program.id()).unwrap();
+
+    // Evaluate `foo`, to produce a response.
+    let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let foo = Identifier::from_str("foo").unwrap();
+    let input_types = stack.get_function_ref(&foo).unwrap().input_types();
+    let request =
+        Request::sign(&private_key, *program.id(), foo, ["1field", "2field"].into_iter(), &input_types, rng).unwrap();
+    let response = process.evaluate::<CurrentAleo>(Authorization::new(request)).unwrap();
+
+    // Ensure the outputs match the output types of `foo`, and of `bar`, which has the same output types.
+    assert!(stack.matches_response_outputs(&response, &foo).is_ok());
+    assert!(stack.matches_response_outputs(&response, &Identifier::from_str("bar").unwrap()).is_ok());
+
+    // Ensure the outputs do not match the output types of `deposit`, reporting the output index.
+    let error = stack.matches_response_outputs(&response, &Identifier::from_str("deposit").unwrap()).unwrap_err();
+    assert!(error.to_string().starts_with("Output 0 of 'deposit' does not match"));
+}
+
+#[test]
+fn test_load_as() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let program = Program::from_str(
+        r"
+program load_as.aleo;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function mint:
+    input r0 as address.private;
+    input r1 as u64.private;
+    cast r0 r1 into r2 as token.record;
+    output r2 as token.record;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+
+    // Initialize the registers, and assign a record.
+    let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+    let mut registers = sample_registers_for(stack, "mint", &[&address.to_string(), "5u64"], rng);
+    let record =
+        Value::from_str(&format!("{{ owner: {address}.private, amount: 5u64.private, _nonce: 0group.public }}"))
+            .unwrap();
+    registers.store(stack, &Register::Locator(0), Value::from_str(&address.to_string()).unwrap()).unwrap();
+    registers.store(stack, &Register::Locator(1), Value::from_str("5u64").unwrap()).unwrap();
+    registers.store(stack, &Register::Locator(2), record.clone()).unwrap();
+    let load_as = |operand: &str, register_type: &str| {
+        registers.load_as(stack, &Operand::from_str(operand).unwrap(), &RegisterType::from_str(register_type).unwrap())
+    };
+
+    // Ensure the record is coerced to an external record of the current program.
+    assert_eq!(load_as("r2", "load_as.aleo/token.record").unwrap(), record);
+    // Ensure a value that already matches the register type is loaded as is.
+    assert_eq!(load_as("r2", "token.record").unwrap(), record);
+
+    // Ensure the record is not coerced to an external record of another program, even with the same layout.
+    let error = load_as("r2", "other.aleo/token.record").unwrap_err();
+    assert_eq!(error.to_string(), "Operand 'r2' cannot be coerced to type 'other.aleo/token.record'");
+    // Ensure a value that is not a record is not coerced to an external record.
+    assert!(load_as("r1", "load_as.aleo/token.record").is_err());
+    // Ensure the record is not coerced to a plaintext type.
+    assert!(load_as("r2", "u64").is_err());
+}
+
+#[test]
+fn test_load_as_logged() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let program = Program::from_str(
+        r"
+program load_as_logged.aleo;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function mint:
+    input r0 as address.private;
+    input r1 as u64.private;
+    cast r0 r1 into r2 as token.record;
+    output r2 as token.record;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+
+    // Initialize the registers, and assign a record.
+    let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+    let mut registers = sample_registers_for(stack, "mint", &[&address.to_string(), "5u64"], rng);
+    let record =
+        Value::from_str(&format!("{{ owner: {address}.private, amount: 5u64.private, _nonce: 0group.public }}"))
+            .unwrap();
+    registers.store(stack, &Register::Locator(0), Value::from_str(&address.to_string()).unwrap()).unwrap();
+    registers.store(stack, &Register::Locator(1), Value::from_str("5u64").unwrap()).unwrap();
+    registers.store(stack, &Register::Locator(2), record.clone()).unwrap();
+    let operand = Operand::from_str("r2").unwrap();
+    let load_as_logged = |register_type: &str| {
+        registers.load_as_logged(stack, &operand, &RegisterType::from_str(register_type).unwrap())
+    };
+
+    // Ensure a value of the same type is loaded without coercion.
+    assert_eq!(load_as_logged("token.record").unwrap(), (record.clone(), false));
+    // Ensure a record adapted to an external record of the current program is flagged as coerced.
+    assert_eq!(load_as_logged("load_as_logged.aleo/token.record").unwrap(), (record, true));
+    // Ensure an incompatible type fails.
+    assert!(load_as_logged("u64").is_err());
+}
+
+#[test]
+fn test_required_key_ids() {
+    // Initialize the programs, where `outer` calls `inner` twice, and an internal closure.
+    let inner = Program::<CurrentNetwork>::from_str(
+        r"
+program inner.aleo;
+
+function foo:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let outer = Program::<CurrentNetwork>::from_str(
+        r"
+import inner.aleo;
+
+program outer.aleo;
+
+closure twice:
+    input r0 as field;
+    add r0 r0 into r1;
+    output r1 as field;
+
+function bar:
+    input r0 as field.private;
+    call twice r0 into r1;
+    call inner.aleo/foo r1 into r2;
+    call inner.aleo/foo r2 into r3;
+    output r3 as field.private;",
+    )
+    .unwrap();
+    let mut process = crate::test_helpers::sample_process(&inner);
+    process.add_program(&outer).unwrap();
+    let stack = process.get_stack(outer.id()).unwrap();
+
+    // Computes the function ID of the given function.
+    let function_id = |program: &Program<CurrentNetwork>, function_name: &str| {
+        let bits = (
+            console::types::U16::<CurrentNetwork>::new(CurrentNetwork::ID),
+            program.id().name(),
+            program.id().network(),
+            Identifier::<CurrentNetwork>::from_str(function_name).unwrap(),
+        )
+            .to_bits_le();
+        CurrentNetwork::hash_bhp1024(&bits).unwrap()
+    };
+
+    // Ensure the keys of the function and its callee are required once each, in call order.
+    let key_ids = stack.required_key_ids(&Identifier::from_str("bar").unwrap()).unwrap();
+    assert_eq!(key_ids, [function_id(&outer, "bar"), function_id(&inner, "foo")]);
+    // Ensure closures, which do not have keys, are rejected.
+    assert!(stack.required_key_ids(&Identifier::from_str("twice").unwrap()).is_err());
+}
+
+#[test]
+fn test_registers_equal() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    // Returns the given value as a private circuit value.
+    let circuit_value =
+        |value: &str| circuit::Value::<CurrentAleo>::new(Mode::Private, Value::from_str(value).unwrap());
+
+    // Initialize two sets of registers, which are assigned the same values.
+    let mut registers = sample_registers(stack, rng);
+    let mut other = sample_registers(stack, rng);
+    assert!(registers.registers_equal(&other));
+    for registers in [&mut registers, &mut other] {
+        registers.store(stack, &Register::Locator(0), Value::from_str("1field").unwrap()).unwrap();
+        registers.store_circuit(stack, &Register::Locator(0), circuit_value("1field")).unwrap();
+    }
+    assert!(registers.registers_equal(&other));
+
+    // Ensure registers with a different value are not equal.
+    registers.store(stack, &Register::Locator(1), Value::from_str("2field").unwrap()).unwrap();
+    other.store(stack, &Register::Locator(1), Value::from_str("3field").unwrap()).unwrap();
+    assert!(!registers.registers_equal(&other));
+    assert!(!other.registers_equal(&registers));
+
+    // Ensure registers with a different circuit value are not equal.
+    let mut registers = sample_registers(stack, rng);
+    let mut other = sample_registers(stack, rng);
+    registers.store_circuit(stack, &Register::Locator(0), circuit_value("1field")).unwrap();
+    assert!(!registers.registers_equal(&other));
+    other.store_circuit(stack, &Register::Locator(0), circuit_value("2field")).unwrap();
+    assert!(!registers.registers_equal(&other));
+}
+
+#[test]
+fn test_assert_type_agreement() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let assert_type_agreement = |value: &str, circuit_value: &str| {
+        let circuit_value = circuit::Value::<CurrentAleo>::new(Mode::Private, Value::from_str(circuit_value).unwrap());
+        stack.assert_type_agreement(&Value::from_str(value).unwrap(), &circuit_value)
+    };
+
+    // Ensure values of the same type agree, regardless of the values themselves.
+    assert!(assert_type_agreement("1field", "2field").is_ok());
+    assert!(assert_type_agreement("{ x: 1field, y: 2field }", "{ x: 3field, y: 4field }").is_ok());
+    assert!(assert_type_agreement("[1u8, 2u8]", "[3u8, 4u8]").is_ok());
+
+    // Ensure values of different types do not agree.
+    assert!(assert_type_agreement("1field", "1u8").is_err());
+    assert!(assert_type_agreement("{ x: 1field, y: 2field }", "{ x: 1field, z: 2field }").is_err());
+    assert!(assert_type_agreement("{ x: 1field, y: 2field }", "{ y: 2field, x: 1field }").is_err());
+    assert!(assert_type_agreement("[1u8, 2u8]", "[1u8, 2u8, 3u8]").is_err());
+    assert!(assert_type_agreement("[1u8, 2u8]", "[1u16, 2u16]").is_err());
+
+    // Ensure records agree only if their entries have the same visibilities.
+    let address = "aleo1qr2ha4pfs5l28aze88yn6fhleeythklkczrule2v838uwj65n5gqxt9djx";
+    let record =
+        |visibility: &str| format!("{{ owner: {address}.private, amount: 5u64.{visibility}, _nonce: 0group.public }}");
+    assert!(assert_type_agreement(&record("private"), &record("private")).is_ok());
+    assert!(assert_type_agreement(&record("private"), &record("public")).is_err());
+}
+
+#[test]
+fn test_try_fold_operands() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let mut registers = sample_registers(stack, rng);
+    registers.store(stack, &Register::Locator(0), Value::from_str("1field").unwrap()).unwrap();
+    let fold = |opcode: &str, operands: &[&str]| {
+        let operands = operands.iter().map(|operand| Operand::from_str(operand).unwrap()).collect::<Vec<_>>();
+        registers.try_fold_operands(stack, opcode, &operands)
+    };
+
+    // Ensure literal operands are folded.
+    assert_eq!(fold("add", &["1u8", "2u8"]).unwrap(), Some(Value::from_str("3u8").unwrap()));
+    assert_eq!(fold("mul", &["3field", "4field"]).unwrap(), Some(Value::from_str("12field").unwrap()));
+    assert_eq!(fold("ternary", &["true", "5u64", "6u64"]).unwrap(), Some(Value::from_str("5u64").unwrap()));
+
+    // Ensure operations with a register operand are not folded.
+    assert_eq!(fold("add", &["r0", "1field"]).unwrap(), None);
+
+    // Ensure operations that fail, or are not operations, are rejected.
+    assert!(fold("add", &["1u8"]).is_err());
+    assert!(fold("add", &["1u8", "1field"]).is_err());
+    assert!(fold("call", &["1field"]).is_err());
+
+    // Ensure operations that overflow, or divide by zero, are rejected instead of halting.
+    let error = fold("add", &["255u8", "1u8"]).unwrap_err();
+    assert!(error.to_string().starts_with("Failed to fold 'add 255u8 1u8 into r0;'"), "{error}");
+    let error = fold("div", &["1u8", "0u8"]).unwrap_err();
+    assert!(error.to_string().starts_with("Failed to fold 'div 1u8 0u8 into r0;'"), "{error}");
+}
+
+#[test]
+fn test_call_sites() {
+    // Initialize the programs, where `outer` calls an internal closure and `inner`.
+    let inner = Program::<CurrentNetwork>::from_str(
+        r"
+program inner.aleo;
+
+function foo:
+    input r0 as field.private;
+    input r1 as field.private;
+    output r0 as field.private;
+    output r1 as field.private;",
+    )
+    .unwrap();
+    let outer = Program::<CurrentNetwork>::from_str(
+        r"
+import inner.aleo;
+
+program outer.aleo;
+
+closure twice:
+    input r0 as field;
+    add r0 r0 into r1;
+    output r1 as field;
+
+function bar:
+    input r0 as field.private;
+    call twice r0 into r1;
+    add r1 1field into r2;
+    call inner.aleo/foo r2 5field into r3 r4;
+    output r4 as field.private;",
+    )
+    .unwrap();
+    let mut process = crate::test_helpers::sample_process(&inner);
+    process.add_program(&outer).unwrap();
+    let stack = process.get_stack(outer.id()).unwrap();
+
+    // Ensure each call is described, in order.
+    let call_sites = stack.call_sites(&Identifier::from_str("bar").unwrap()).unwrap();
+    assert_eq!(call_sites.len(), 2);
+    assert_eq!(call_sites[0].callee().to_string(), "outer.aleo/twice");
+    assert_eq!(call_sites[0].inputs(), [Operand::from_str("r0").unwrap()]);
+    assert_eq!(call_sites[0].outputs(), [Register::Locator(1)]);
+    assert_eq!(call_sites[1].callee().to_string(), "inner.aleo/foo");
+    assert_eq!(call_sites[1].inputs(), [Operand::from_str("r2").unwrap(), Operand::from_str("5field").unwrap()]);
+    assert_eq!(call_sites[1].outputs(), [Register::Locator(3), Register::Locator(4)]);
+
+    // Ensure functions without calls have no call sites.
+    let inner_stack = process.get_stack(inner.id()).unwrap();
+    assert!(inner_stack.call_sites(&Identifier::from_str("foo").unwrap()).unwrap().is_empty());
+}
+
+#[test]
+fn test_compact() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    // Ensure compacting the registers preserves the assigned values.
+    let mut registers = sample_registers(stack, rng);
+    registers.compact();
+    registers.store(stack, &Register::Locator(0), Value::from_str("1field").unwrap()).unwrap();
+    registers.store(stack, &Register::Locator(1), Value::from_str("2field").unwrap()).unwrap();
+    registers.compact();
+    assert_eq!(registers.load(stack, &Operand::from_str("r0").unwrap()).unwrap(), Value::from_str("1field").unwrap());
+    assert_eq!(registers.load(stack, &Operand::from_str("r1").unwrap()).unwrap(), Value::from_str("2field").unwrap());
+    // Ensure the registers remain usable after compaction.
+    registers.store(stack, &Register::Locator(2), Value::from_str("3field").unwrap()).unwrap();
+    assert_eq!(registers.load(stack, &Operand::from_str("r2").unwrap()).unwrap(), Value::from_str("3field").unwrap());
+
+    // Ensure compacting the finalize registers preserves the assigned values.
+    let function_name = Identifier::from_str("deposit").unwrap();
+    let mut finalize_registers = FinalizeRegisters::<CurrentNetwork>::new(
+        FinalizeGlobalState::from(1, 1, [0; 32]),
+        <CurrentNetwork as Network>::TransitionID::default(),
+        function_name,
+        stack.get_finalize_types(&function_name).unwrap().clone(),
+    );
+    let address = "aleo1qr2ha4pfs5l28aze88yn6fhleeythklkczrule2v838uwj65n5gqxt9djx";
+    finalize_registers.store(stack, &Register::Locator(0), Value::from_str(address).unwrap()).unwrap();
+    finalize_registers.compact();
+    let value = finalize_registers.load(stack, &Operand::from_str("r0").unwrap()).unwrap();
+    assert_eq!(value, Value::from_str(address).unwrap());
+}
+
+#[test]
+fn test_matches_future_call() {
+    // Initialize the programs, where the finalize signature of `vault` uses a struct that `user` does not declare.
+    let vault = Program::<CurrentNetwork>::from_str(
+        r"
+program vault.aleo;
+
+struct point:
+    x as field;
+    y as field;
+
+function store:
+    input r0 as point.public;
+    async store r0 into r1;
+    output r1 as vault.aleo/store.future;
+
+finalize store:
+    input r0 as point.public;
+    assert.eq r0.x r0.x;",
+    )
+    .unwrap();
+    let user = Program::<CurrentNetwork>::from_str(
+        r"
+import vault.aleo;
+
+program user.aleo;
+
+function noop:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let mut process = crate::test_helpers::sample_process(&vault);
+    process.add_program(&user).unwrap();
+    let stack = process.get_stack(user.id()).unwrap();
+    let callee = Locator::from_str("vault.aleo/store").unwrap();
+    let future = |arguments: &str| {
+        Future::from_str(&format!("{{ program_id: vault.aleo, function_name: store, arguments: [{arguments}] }}"))
+            .unwrap()
+    };
+
+    // Ensure a future with well-typed arguments matches the callee.
+    assert!(stack.matches_future_call(&future("{ x: 1field, y: 2field }"), &callee).is_ok());
+    // Ensure a future with mistyped arguments does not match the callee.
+    assert!(stack.matches_future_call(&future("{ x: 1field }"), &callee).is_err());
+    assert!(stack.matches_future_call(&future("1field"), &callee).is_err());
+    // Ensure a future for a different callee does not match.
+    let other = Locator::from_str("vault.aleo/other").unwrap();
+    assert!(stack.matches_future_call(&future("{ x: 1field, y: 2field }"), &other).is_err());
+}
+
+#[test]
+fn test_effective_caller() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let program = sample_program();
+    let stack = &sample_stack(&program);
+    let mut registers = sample_registers(stack, rng);
+
+    // Ensure the effective caller is unavailable without a signer or caller.
+    assert!(registers.effective_caller().is_err());
+
+    // Ensure the effective caller is the signer, at the root.
+    let signer = Address::try_from(&PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+    registers.set_signer(signer);
+    assert_eq!(registers.effective_caller().unwrap(), signer);
+
+    // Ensure the effective caller is the caller, once it is set.
+    let caller = program.id().to_address().unwrap();
+    registers.set_caller(caller);
+    assert_eq!(registers.effective_caller().unwrap(), caller);
+}
+
+#[test]
+fn test_diff() {
+    // Initialize the editions of the program.
+    let old = Program::<CurrentNetwork>::from_str(
+        r"
+program upgrade.aleo;
+
+struct point:
+    x as field;
+    y as field;
+
+mapping balances:
+    key as address.public;
+    value as u64.public;
+
+function foo:
+    input r0 as field.private;
+    output r0 as field.private;
+
+function bar:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let new = Program::<CurrentNetwork>::from_str(
+        r"
+program upgrade.aleo;
+
+struct point:
+    x as field;
+    y as field;
+    z as field;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function foo:
+    input r0 as field.private;
+    output r0 as field.private;
+
+function baz:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let old_stack = sample_stack(&old);
+    let new_stack = sample_stack(&new);
+    let names = |names: &[Identifier<CurrentNetwork>]| names.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    // Ensure the differences are reported by declaration kind.
+    let diff = old_stack.diff(&new_stack);
+    assert!(!diff.is_empty());
+    assert_eq!(names(diff.structs().changed()), ["point"]);
+    assert!(diff.structs().added().is_empty() && diff.structs().removed().is_empty());
+    assert_eq!(names(diff.records().added()), ["token"]);
+    assert_eq!(names(diff.mappings().removed()), ["balances"]);
+    assert!(diff.closures().is_empty());
+    assert_eq!(names(diff.functions().added()), ["baz"]);
+    assert_eq!(names(diff.functions().removed()), ["bar"]);
+    assert!(diff.functions().changed().is_empty());
+
+    // Ensure a program does not differ from itself.
+    assert!(old_stack.diff(&old_stack).is_empty());
+}
+
+#[test]
+fn test_load_bits_le_width() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program bits_width.aleo;
+
+function pack:
+    input r0 as u8.private;
+    output r0 as u8.private;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+
+    // Initialize the registers.
+    let mut registers = sample_registers_for(stack, "pack", &["5u8"], rng);
+    registers.store(stack, &Register::Locator(0), Value::from_str("5u8").unwrap()).unwrap();
+    let bits = |operand: &str, width| registers.load_bits_le_width(stack, &Operand::from_str(operand).unwrap(), width);
+
+    // Ensure the bits are padded to the width.
+    assert_eq!(bits("r0", 16).unwrap(), [&[true, false, true][..], &[false; 13]].concat());
+    assert_eq!(bits("r0", 8).unwrap(), [&[true, false, true][..], &[false; 5]].concat());
+
+    // Ensure the bits are truncated to the width, as long as no set bits are discarded.
+    assert_eq!(bits("r0", 3).unwrap(), [true, false, true]);
+    assert_eq!(bits("4u8", 3).unwrap(), [false, false, true]);
+    assert_eq!(bits("r0", 2).unwrap_err().to_string(), "Operand 'r0' does not fit in 2 bits");
+    assert!(bits("255u8", 7).is_err());
+
+    // Ensure signed integers are sign-extended.
+    assert_eq!(bits("-2i8", 10).unwrap(), [&[false][..], &[true; 9]].concat());
+    assert_eq!(bits("2i8", 10).unwrap(), [&[false, true][..], &[false; 8]].concat());
+
+    // Ensure signed integers are truncated to the width, as long as the value is unchanged.
+    assert_eq!(bits("-2i8", 2).unwrap(), [false, true]);
+    assert_eq!(bits("-1i8", 1).unwrap(), [true]);
+    assert!(bits("-2i8", 1).is_err());
+    assert!(bits("2i8", 2).is_err());
+    assert!(bits("-128i8", 7).is_err());
+
+    // Ensure operands that are not literals are rejected.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program bits_struct.aleo;
+
+struct pair:
+    a as u8;
+    b as u8;
+
+function pack:
+    input r0 as pair.private;
+    output r0 as pair.private;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+    let mut registers = sample_registers_for(stack, "pack", &["{ a: 1u8, b: 2u8 }"], rng);
+    let pair = Value::from_str("{ a: 1u8, b: 2u8 }").unwrap();
+    registers.store(stack, &Register::Locator(0), pair).unwrap();
+    let error = registers.load_bits_le_width(stack, &Operand::from_str("r0").unwrap(), 16).unwrap_err();
+    assert!(error.to_string().starts_with("Operand 'r0' must be a literal"), "{error}");
+}
+
+#[test]
+fn test_registers_by_type() {
+    // Initialize the stack.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program register_groups.aleo;
+
+function compute:
+    input r0 as field.private;
+    input r1 as u64.public;
+    add r0 r0 into r2;
+    cast r1 into r3 as field;
+    add r1 r1 into r4;
+    is.eq r2 r3 into r5;
+    output r5 as boolean.private;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+
+    // Ensure the registers are grouped by type, in the order the types are first defined.
+    let registers_by_type = stack.registers_by_type(&Identifier::from_str("compute").unwrap()).unwrap();
+    let groups = registers_by_type.iter().map(|(register_type, locators)| (register_type.to_string(), locators));
+    assert_eq!(groups.collect::<Vec<_>>(), [
+        ("field".to_string(), &vec![0, 2, 3]),
+        ("u64".to_string(), &vec![1, 4]),
+        ("boolean".to_string(), &vec![5]),
+    ]);
+
+    // Ensure an unknown function fails.
+    assert!(stack.registers_by_type(&Identifier::from_str("missing").unwrap()).is_err());
+}
+
+#[test]
+fn test_matches_record_entry() {
+    // Initialize the stack.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program record_entries.aleo;
+
+struct point:
+    x as field;
+    y as field;
+
+record marker:
+    owner as address.private;
+    position as point.private;
+    path as [point; 2u32].public;
+
+function noop:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+
+    // Initialize the record.
+    let address = "aleo1qr2ha4pfs5l28aze88yn6fhleeythklkczrule2v838uwj65n5gqxt9djx";
+    let record = Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::from_str(&format!(
+        "{{ owner: {address}.private, position: {{ x: 1field.private, y: 2field.private }}, path: [{{ x: 1field.public, y: 2field.public }}, {{ x: 3field.public, y: 4field.public }}], _nonce: 0group.public }}"
+    ))
+    .unwrap();
+    let matches = |entry: &str, expected: &str| {
+        stack.matches_record_entry(
+            &record,
+            &Identifier::from_str(entry).unwrap(),
+            &PlaintextType::from_str(expected).unwrap(),
+        )
+    };
+
+    // Ensure the composite entries match, regardless of their visibility.
+    assert!(matches("position", "point").is_ok());
+    assert!(matches("path", "[point; 2u32]").is_ok());
+
+    // Ensure mismatched entries fail, naming the entry.
+    assert!(matches("position", "field").unwrap_err().to_string().starts_with("Invalid record entry 'position'"));
+    assert!(matches("path", "[point; 3u32]").is_err());
+    assert!(matches("path", "[field; 2u32]").is_err());
+
+    // Ensure a missing entry fails.
+    assert_eq!(matches("amount", "u64").unwrap_err().to_string(), "Record entry 'amount' does not exist");
+}
+
+#[test]
+fn test_verify_program() {
+    // Initialize the programs, where `importer` uses `library` in one import and ignores `unused` in another.
+    let library = Program::<CurrentNetwork>::from_str(
+        r"
+program library.aleo;
+
+record coin:
+    owner as address.private;
+    amount as u64.private;
+
+function noop:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let unused = Program::<CurrentNetwork>::from_str(
+        r"
+program unused.aleo;
+
+function noop:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let importer = Program::<CurrentNetwork>::from_str(
+        r"
+import library.aleo;
+import unused.aleo;
+
+program importer.aleo;
+
+function spend:
+    input r0 as library.aleo/coin.record;
+    output r0.amount as u64.private;",
+    )
+    .unwrap();
+    let mut process = crate::test_helpers::sample_process(&library);
+    process.add_program(&unused).unwrap();
+    process.add_program(&importer).unwrap();
+
+    // Ensure the unused import is reported, as a warning rather than an error.
+    let warnings = process.get_stack(importer.id()).unwrap().verify_program().unwrap();
+    assert_eq!(warnings, [ProgramWarning::UnusedImport(*unused.id())]);
+    assert!(!warnings[0].is_error());
+    assert_eq!(warnings[0].to_string(), "Import 'unused.aleo' is not used");
+
+    // Ensure a well-formed program without imports reports nothing.
+    assert!(process.get_stack(library.id()).unwrap().verify_program().unwrap().is_empty());
+    let program = sample_program();
+    assert!(sample_stack(&program).verify_program().unwrap().is_empty());
+    assert!(process.verify_program(&program).unwrap().is_empty());
+
+    // Initialize a program with a warning in each closure and function, which the process would reject.
+    let calls = "    call library.aleo/noop r0 into r1;\n".repeat(31);
+    let program = Program::<CurrentNetwork>::from_str(&format!(
+        r"
+import library.aleo;
+import missing.aleo;
+import unused.aleo;
+
+program warnings.aleo;
+
+closure leak:
+    input r0 as field;
+    add r0 r1 into r2;
+    output r2 as field;
+
+function early:
+    input r0 as field.private;
+    output r1 as field.private;
+
+function settle:
+    input r0 as field.public;
+
+finalize settle:
+    input r0 as field.public;
+    assert.eq r0 r0;
+
+function busy:
+    input r0 as field.private;
+{calls}    output r0 as field.private;
+
+function unresolved:
+    input r0 as field.private;
+    call missing.aleo/noop r0 into r1;
+    output r1 as field.private;"
+    ))
+    .unwrap();
+    assert!(process.clone().add_program(&program).is_err());
+
+    // Ensure each warning is reported by the process, before the stack is built.
+    let warnings = process.verify_program(&program).unwrap();
+    let [leak, early, settle, busy, unresolved, unused_import] = &warnings[..] else {
+        panic!("Expected 6 warnings, found {warnings:?}")
+    };
+    assert_eq!(
+        leak.to_string(),
+        "Read before write in 'leak': Instruction 0 ('add r0 r1 into r2;') reads 'r1' before it is written"
+    );
+    assert_eq!(
+        early.to_string(),
+        "Read before write in 'early': Output 'output r1 as field.private;' reads 'r1' before it is written"
+    );
+    assert_eq!(settle.to_string(), "Inconsistent future in 'settle': Expected 1 'async' instruction, found 0");
+    assert_eq!(*busy, ProgramWarning::CallLimitExceeded(Identifier::from_str("busy").unwrap(), 32));
+    assert!(matches!(unresolved, ProgramWarning::UnresolvedCalls(..)), "{unresolved}");
+    assert!(unresolved.to_string().starts_with("Failed to resolve the calls of 'unresolved'"), "{unresolved}");
+    assert_eq!(*unused_import, ProgramWarning::UnusedImport(*unused.id()));
+    assert!(warnings[..5].iter().all(ProgramWarning::is_error));
+
+    // Ensure a cyclic struct names the struct, as the parser rejects cyclic structs before they can be checked.
+    let warning = ProgramWarning::<CurrentNetwork>::CyclicStruct(
+        Identifier::from_str("list").unwrap(),
+        "Struct 'list' is recursive: list -> list".to_string(),
+    );
+    assert_eq!(warning.to_string(), "Cyclic struct 'list': Struct 'list' is recursive: list -> list");
+}
+
+#[test]
+fn test_set_randomness_seed() {
+    // Initialize the stack.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program seeded.aleo;
+
+function roll:
+    input r0 as u64.public;
+    async roll r0 into r1;
+    output r1 as seeded.aleo/roll.future;
+
+finalize roll:
+    input r0 as u64.public;
+    rand.chacha r0 into r1 as field;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+    let function_name = Identifier::from_str("roll").unwrap();
+    let finalize = stack.get_function_ref(&function_name).unwrap().finalize_logic().unwrap().clone();
+    let Command::RandChaCha(rand_chacha) = &finalize.commands()[0] else { panic!("Expected 'rand.chacha'") };
+
+    // Runs the `rand.chacha` command, with the given random seed in the global state and the given randomness seed.
+    let roll = |random_seed: u8, seed: Option<u64>| {
+        let mut registers = FinalizeRegisters::<CurrentNetwork>::new(
+            FinalizeGlobalState::from(1, 1, [random_seed; 32]),
+            <CurrentNetwork as Network>::TransitionID::default(),
+            function_name,
+            stack.get_finalize_types(&function_name).unwrap().clone(),
+        );
+        if let Some(seed) = seed {
+            registers.set_randomness_seed(Field::from_u64(seed));
+        }
+        registers.store(stack, &Register::Locator(0), Value::from_str("1u64").unwrap()).unwrap();
+        rand_chacha.finalize(stack, &mut registers).unwrap();
+        registers.load(stack, &Operand::from_str("r1").unwrap()).unwrap()
+    };
+
+    // Ensure the output is determined by the randomness seed, regardless of the global state.
+    assert_eq!(roll(1, Some(7)), roll(2, Some(7)));
+    assert_ne!(roll(1, Some(7)), roll(1, Some(8)));
+
+    // Ensure the output is determined by the global state, if no randomness seed is set.
+    assert_eq!(roll(1, None), roll(1, None));
+    assert_ne!(roll(1, None), roll(2, None));
+
+    // Ensure a randomness seed set in the options is applied by the registers.
+    let mut options = ExecutionOptions::default();
+    options.set_randomness_seed(Field::from_u64(7));
+    let mut registers = FinalizeRegisters::<CurrentNetwork>::new(
+        FinalizeGlobalState::from(1, 1, [2; 32]),
+        <CurrentNetwork as Network>::TransitionID::default(),
+        function_name,
+        stack.get_finalize_types(&function_name).unwrap().clone(),
+    );
+    registers.set_options(&options);
+    registers.store(stack, &Register::Locator(0), Value::from_str("1u64").unwrap()).unwrap();
+    rand_chacha.finalize(stack, &mut registers).unwrap();
+    assert_eq!(registers.load(stack, &Operand::from_str("r1").unwrap()).unwrap(), roll(1, Some(7)));
+}
+
+#[test]
+fn test_set_mapping_snapshot() {
+    // Initialize the stack, which reads a mapping of an imported program.
+    let imported = Program::<CurrentNetwork>::from_str(
+        r"
+program snapshot_rates.aleo;
+
+mapping rates:
+    key as u8.public;
+    value as u64.public;
+
+function noop:",
+    )
+    .unwrap();
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+import snapshot_rates.aleo;
+
+program snapshot.aleo;
+
+mapping balances:
+    key as u8.public;
+    value as u64.public;
+
+function read:
+    input r0 as u8.public;
+    async read r0 into r1;
+    output r1 as snapshot.aleo/read.future;
+
+finalize read:
+    input r0 as u8.public;
+    get.or_use balances[r0] 5u64 into r1;
+    get balances[r0] into r2;
+    contains balances[r0] into r3;
+    set r1 into balances[r0];
+    remove balances[r0];
+    get snapshot_rates.aleo/rates[r0] into r4;",
+    )
+    .unwrap();
+    let mut process = crate::test_helpers::sample_process(&imported);
+    process.add_program(&program).unwrap();
+    let stack = process.get_stack(program.id()).unwrap();
+    let function_name = Identifier::from_str("read").unwrap();
+    let finalize = stack.get_function_ref(&function_name).unwrap().finalize_logic().unwrap().clone();
+    let Command::GetOrUse(get_or_use) = &finalize.commands()[0] else { panic!("Expected 'get.or_use'") };
+    let Command::Get(get) = &finalize.commands()[1] else { panic!("Expected 'get'") };
+    let Command::Contains(contains) = &finalize.commands()[2] else { panic!("Expected 'contains'") };
+    let Command::Set(set) = &finalize.commands()[3] else { panic!("Expected 'set'") };
+    let Command::Remove(remove) = &finalize.commands()[4] else { panic!("Expected 'remove'") };
+    let Command::Get(get_external) = &finalize.commands()[5] else { panic!("Expected 'get'") };
+
+    // Initialize an empty store, in which the mappings are not initialized.
+    let store = FinalizeStore::<CurrentNetwork, FinalizeMemory<_>>::open(None).unwrap();
+
+    // Initialize the snapshot, with a balance and a rate for the key '1u8'.
+    let entry = |program_id: &ProgramID<CurrentNetwork>, mapping_name: &str, key: &str, value: &str| {
+        (
+            *program_id,
+            Identifier::from_str(mapping_name).unwrap(),
+            Plaintext::from_str(key).unwrap(),
+            Plaintext::from_str(value).unwrap(),
+        )
+    };
+    let snapshot = MappingSnapshot::new([
+        entry(program.id(), "balances", "1u8", "10u64"),
+        entry(imported.id(), "rates", "1u8", "3u64"),
+    ]);
+    let mut options = ExecutionOptions::default();
+    options.set_mapping_snapshot(snapshot.clone());
+
+    // Returns registers for the given key, with the given options.
+    let registers = |key: &str, options: &ExecutionOptions<CurrentNetwork>| {
+        let mut registers = FinalizeRegisters::<CurrentNetwork>::new(
+            FinalizeGlobalState::from(1, 1, [0; 32]),
+            <CurrentNetwork as Network>::TransitionID::default(),
+            function_name,
+            stack.get_finalize_types(&function_name).unwrap().clone(),
+        );
+        registers.set_options(options);
+        registers.store(stack, &Register::Locator(0), Value::from_str(key).unwrap()).unwrap();
+        registers
+    };
+    let load = |registers: &FinalizeRegisters<CurrentNetwork>, register: &str| {
+        registers.load(stack, &Operand::from_str(register).unwrap()).unwrap()
+    };
+
+    // Ensure a key in the snapshot is read from the snapshot, including for the mapping of the imported program.
+    let mut present = registers("1u8", &options);
+    get_or_use.finalize(stack, &store, &mut present).unwrap();
+    get.finalize(stack, &store, &mut present).unwrap();
+    assert_eq!(load(&present, "r1"), Value::from_str("10u64").unwrap());
+    assert_eq!(load(&present, "r2"), Value::from_str("10u64").unwrap());
+    contains.finalize(stack, &store, &mut present).unwrap();
+    assert_eq!(load(&present, "r3"), Value::from_str("true").unwrap());
+    get_external.finalize(stack, &store, &mut present).unwrap();
+    assert_eq!(load(&present, "r4"), Value::from_str("3u64").unwrap());
+
+    // Ensure a key absent from the snapshot uses the default, or fails for 'get', without falling through to the store.
+    let mut absent = registers("2u8", &options);
+    get_or_use.finalize(stack, &store, &mut absent).unwrap();
+    assert_eq!(load(&absent, "r1"), Value::from_str("5u64").unwrap());
+    assert!(get.finalize(stack, &store, &mut absent).is_err());
+    contains.finalize(stack, &store, &mut absent).unwrap();
+    assert_eq!(load(&absent, "r3"), Value::from_str("false").unwrap());
+    assert_eq!(
+        get_external.finalize(stack, &store, &mut absent).unwrap_err().to_string(),
+        "Key '2u8' does not exist in mapping 'snapshot_rates.aleo/rates'"
+    );
+
+    // Ensure 'set' writes the snapshot, and the write is read by the later scopes.
+    assert!(set.finalize(stack, &store, &mut absent).unwrap().is_none());
+    let mut later = registers("2u8", &options);
+    get.finalize(stack, &store, &mut later).unwrap();
+    assert_eq!(load(&later, "r2"), Value::from_str("5u64").unwrap());
+
+    // Ensure 'remove' writes the snapshot, and the removal is seen by the later scopes and the holder of the snapshot.
+    assert!(remove.finalize(stack, &store, &mut present).unwrap().is_none());
+    let mut later = registers("1u8", &options);
+    get_or_use.finalize(stack, &store, &mut later).unwrap();
+    contains.finalize(stack, &store, &mut later).unwrap();
+    assert_eq!(load(&later, "r3"), Value::from_str("false").unwrap());
+    let balances = Identifier::from_str("balances").unwrap();
+    assert_eq!(snapshot.get(program.id(), &balances, &Plaintext::from_str("1u8").unwrap()), None);
+    assert_eq!(
+        snapshot.get(program.id(), &balances, &Plaintext::from_str("2u8").unwrap()),
+        Some(Plaintext::from_str("5u64").unwrap())
+    );
+
+    // Ensure the store is read if no snapshot is set, where the mapping does not exist.
+    let mut unset = registers("1u8", &ExecutionOptions::default());
+    assert!(get_or_use.finalize(stack, &store, &mut unset).is_err());
+}
+
+#[test]
+fn test_matches_credits_record() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    // Returns the result of matching the given record entries as a credits record.
+    let address = "aleo1qr2ha4pfs5l28aze88yn6fhleeythklkczrule2v838uwj65n5gqxt9djx";
+    let matches = |entries: &str| {
+        let record = format!("{{ owner: {address}.private, {entries}, _nonce: 0group.public }}");
+        stack.matches_credits_record(&Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::from_str(&record).unwrap())
+    };
+
+    // Ensure the microcredits are returned for a credits record.
+    assert_eq!(matches("microcredits: 100u64.private").unwrap(), 100);
+
+    // Ensure records with a different layout fail.
+    assert!(matches("amount: 100u64.private").is_err());
+    assert!(matches("microcredits: 100u32.private").is_err());
+    assert!(matches("microcredits: 100u64.public").is_err());
+    assert!(matches("microcredits: 100u64.private, memo: 1field.private").is_err());
+}
+
+#[test]
+fn test_matches_valid_address() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let address = |group| Literal::Address(Address::<CurrentNetwork>::new(group));
+
+    // Ensure a parsed address is valid.
+    let literal = Literal::from_str("aleo1qr2ha4pfs5l28aze88yn6fhleeythklkczrule2v838uwj65n5gqxt9djx").unwrap();
+    assert!(stack.matches_valid_address(&literal).is_ok());
+    assert!(stack.matches_valid_address(&address(Group::generator())).is_ok());
+
+    // Ensure a point off the curve is rejected.
+    let off_curve = Group::from_xy_coordinates_unchecked(Field::one(), Field::one());
+    assert!(stack.matches_valid_address(&address(off_curve)).unwrap_err().to_string().ends_with("is not on the curve"));
+
+    // Ensure a point of small order, which is on the curve but not in the prime-order subgroup, is rejected.
+    let small_order = Group::from_xy_coordinates_unchecked(Field::zero(), -Field::one());
+    let error = stack.matches_valid_address(&address(small_order)).unwrap_err();
+    assert!(error.to_string().ends_with("is not in the prime-order subgroup"));
+
+    // Ensure a literal that is not an address is rejected.
+    assert!(stack.matches_valid_address(&Literal::from_str("1field").unwrap()).is_err());
+}
+
+#[test]
+fn test_mapping_entry_size() {
+    // Initialize the stack.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program entry_sizes.aleo;
+
+struct point:
+    x as field;
+    y as u8;
+
+mapping balances:
+    key as address.public;
+    value as u64.public;
+
+mapping paths:
+    key as u32.public;
+    value as [point; 2u32].public;
+
+mapping names:
+    key as field.public;
+    value as string.public;
+
+function noop:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+    let entry_size = |name: &str| stack.mapping_entry_size(&Identifier::from_str(name).unwrap());
+
+    // Ensure the size matches the serialized size of a sample key and value.
+    let serialized_size = |key: &str, value: &str| {
+        let key = Plaintext::<CurrentNetwork>::from_str(key).unwrap();
+        let value = Value::<CurrentNetwork>::from_str(value).unwrap();
+        (key.to_bytes_le().unwrap().len() + value.to_bytes_le().unwrap().len()) as u64
+    };
+    let address = "aleo1qr2ha4pfs5l28aze88yn6fhleeythklkczrule2v838uwj65n5gqxt9djx";
+    assert_eq!(entry_size("balances").unwrap(), serialized_size(address, "5u64"));
+    assert_eq!(entry_size("paths").unwrap(), serialized_size("1u32", "[{ x: 1field, y: 2u8 }, { x: 3field, y: 4u8 }]"));
+
+    // Ensure strings, and unknown mappings, are not supported.
+    assert!(entry_size("names").is_err());
+    assert!(entry_size("unknown").is_err());
+}
+
+#[test]
+fn test_load_with_origin() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let mut registers = sample_registers(stack, rng);
+    registers.store(stack, &Register::Locator(0), Value::from_str("1field").unwrap()).unwrap();
+    registers.store(stack, &Register::Locator(1), Value::from_str("2field").unwrap()).unwrap();
+    registers.set_caller(Address::from_str("aleo1qr2ha4pfs5l28aze88yn6fhleeythklkczrule2v838uwj65n5gqxt9djx").unwrap());
+    let origin = |operand: &str| {
+        registers.load_with_origin(stack, &Operand::from_str(operand).unwrap()).map(|(_, origin)| origin)
+    };
+
+    // Ensure the origin of each kind of operand is returned, along with its value.
+    let (value, origin_of_input) = registers.load_with_origin(stack, &Operand::from_str("r0").unwrap()).unwrap();
+    assert_eq!(value, registers.load(stack, &Operand::from_str("r0").unwrap()).unwrap());
+    assert_eq!(origin_of_input, RegisterOrigin::Input);
+    assert_eq!(origin("r1").unwrap(), RegisterOrigin::Computed);
+    assert_eq!(origin("self.caller").unwrap(), RegisterOrigin::Input);
+    assert_eq!(origin("5u8").unwrap(), RegisterOrigin::Literal);
+
+    // Ensure an unassigned register fails.
+    assert!(origin("r2").is_err());
+}
+
+#[test]
+fn test_matches_partial_struct() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let point = Identifier::from_str("point").unwrap();
+    let matches = |plaintext: &str, provided: &[&str]| {
+        let provided = provided.iter().map(|name| Identifier::from_str(name).unwrap()).collect();
+        stack.matches_partial_struct(&Plaintext::from_str(plaintext).unwrap(), &point, &provided)
+    };
+
+    // Ensure the provided members are checked, as the struct is filled in.
+    assert!(matches("{ x: 1field }", &[]).is_ok());
+    assert!(matches("{ x: 1field }", &["x"]).is_ok());
+    assert!(matches("{ x: 1field, y: 2field }", &["x", "y"]).is_ok());
+
+    // Ensure mistyped, missing, and undeclared members fail.
+    assert!(matches("{ x: 1u8 }", &["x"]).unwrap_err().to_string().starts_with("Invalid member 'point.x'"));
+    assert!(matches("{ x: 1u8 }", &[]).is_ok());
+    assert_eq!(matches("{ x: 1field }", &["y"]).unwrap_err().to_string(), "Member 'y' of 'point' is not provided");
+    assert!(matches("{ x: 1field }", &["z"]).is_err());
+    assert!(matches("{ z: 1field }", &[]).is_err());
+
+    // Ensure a plaintext that is not a struct fails.
+    assert!(matches("1field", &[]).is_err());
+}
+
+#[test]
+fn test_swap_registers() {
+    // Initialize the stack.
+    let program = sample_program();
+    let stack = &sample_stack(&program);
+
+    // Initialize the finalize registers for `deposit`.
+    let function_name = Identifier::from_str("deposit").unwrap();
+    let mut registers = FinalizeRegisters::<CurrentNetwork>::new(
+        FinalizeGlobalState::from(1, 1, [0; 32]),
+        <CurrentNetwork as Network>::TransitionID::default(),
+        function_name,
+        stack.get_finalize_types(&function_name).unwrap().clone(),
+    );
+    let caller = Value::from_str("aleo1qr2ha4pfs5l28aze88yn6fhleeythklkczrule2v838uwj65n5gqxt9djx").unwrap();
+    registers.store(stack, &Register::Locator(0), caller).unwrap();
+    registers.store(stack, &Register::Locator(1), Value::from_str("5u64").unwrap()).unwrap();
+    registers.store(stack, &Register::Locator(2), Value::from_str("1u64").unwrap()).unwrap();
+    let load = |registers: &FinalizeRegisters<CurrentNetwork>, operand: &str| {
+        registers.load(stack, &Operand::from_str(operand).unwrap()).unwrap()
+    };
+
+    // Ensure an unassigned register cannot be swapped.
+    assert!(registers.swap_registers(stack, 2, 3).is_err());
+    registers.store(stack, &Register::Locator(3), Value::from_str("6u64").unwrap()).unwrap();
+
+    // Ensure the values are exchanged.
+    registers.swap_registers(stack, 2, 3).unwrap();
+    assert_eq!(load(&registers, "r2"), Value::from_str("6u64").unwrap());
+    assert_eq!(load(&registers, "r3"), Value::from_str("1u64").unwrap());
+
+    // Ensure input registers cannot be swapped.
+    assert_eq!(registers.swap_registers(stack, 1, 3).unwrap_err().to_string(), "Cannot swap input register 'r1'");
+
+    // Initialize the finalize registers for a finalize scope with registers of different types.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program mixed.aleo;
+
+function mix:
+    input r0 as u64.public;
+    async mix r0 into r1;
+    output r1 as mixed.aleo/mix.future;
+
+finalize mix:
+    input r0 as u64.public;
+    add r0 r0 into r1;
+    cast r0 into r2 as field;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+    let function_name = Identifier::from_str("mix").unwrap();
+    let mut registers = FinalizeRegisters::<CurrentNetwork>::new(
+        FinalizeGlobalState::from(1, 1, [0; 32]),
+        <CurrentNetwork as Network>::TransitionID::default(),
+        function_name,
+        stack.get_finalize_types(&function_name).unwrap().clone(),
+    );
+    registers.store(stack, &Register::Locator(0), Value::from_str("5u64").unwrap()).unwrap();
+    registers.store(stack, &Register::Locator(1), Value::from_str("10u64").unwrap()).unwrap();
+    registers.store(stack, &Register::Locator(2), Value::from_str("5field").unwrap()).unwrap();
+
+    // Ensure registers of different types cannot be swapped, and are left unchanged.
+    assert_eq!(
+        registers.swap_registers(stack, 1, 2).unwrap_err().to_string(),
+        "Cannot swap 'r1' of type 'u64.public' with 'r2' of type 'field.public'"
+    );
+    assert_eq!(registers.load(stack, &Operand::from_str("r1").unwrap()).unwrap(), Value::from_str("10u64").unwrap());
+    assert_eq!(registers.load(stack, &Operand::from_str("r2").unwrap()).unwrap(), Value::from_str("5field").unwrap());
+}
+
+#[test]
+fn test_verify_mapping_keys_deterministic() {
+    // Initialize the stack.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program lottery.aleo;
+
+mapping winners:
+    key as field.public;
+    value as u64.public;
+
+function draw:
+    input r0 as u64.public;
+    async draw r0 into r1;
+    output r1 as lottery.aleo/draw.future;
+
+finalize draw:
+    input r0 as u64.public;
+    rand.chacha into r1 as field;
+    add r1 1field into r2;
+    set r0 into winners[r2];
+
+function record_draw:
+    input r0 as field.public;
+    async record_draw r0 into r1;
+    output r1 as lottery.aleo/record_draw.future;
+
+finalize record_draw:
+    input r0 as field.public;
+    rand.chacha into r1 as u64;
+    set r1 into winners[r0];",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+    let verify = |name: &str| stack.verify_mapping_keys_deterministic(&Identifier::from_str(name).unwrap());
+
+    // Ensure a key derived from randomness is rejected, reporting the command.
+    let error = verify("draw").unwrap_err().to_string();
+    assert!(error.starts_with("Command 2 ('set r0 into winners[r2];')"), "{error}");
+
+    // Ensure a random value stored under a deterministic key is accepted.
+    assert!(verify("record_draw").is_ok());
+
+    // Ensure functions without randomness are accepted.
+    let program = sample_program();
+    let stack = &sample_stack(&program);
+    assert!(stack.verify_mapping_keys_deterministic(&Identifier::from_str("deposit").unwrap()).is_ok());
+    assert!(stack.verify_mapping_keys_deterministic(&Identifier::from_str("foo").unwrap()).is_ok());
+}
+
+#[test]
+fn test_load_struct_members() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program struct_members.aleo;
+
+struct point:
+    x as field;
+    y as field;
+    z as u8;
+
+function norm:
+    input r0 as point.private;
+    input r1 as field.private;
+    output r0.x as field.private;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+
+    // Initialize the registers.
+    let inputs = ["{ x: 1field, y: 2field, z: 3u8 }", "4field"];
+    let mut registers = sample_registers_for(stack, "norm", &inputs, rng);
+    registers.store(stack, &Register::Locator(0), Value::from_str(inputs[0]).unwrap()).unwrap();
+    registers.store(stack, &Register::Locator(1), Value::from_str(inputs[1]).unwrap()).unwrap();
+    let load = |register: u64, members: &[&str]| {
+        let members = members.iter().map(|member| Identifier::from_str(member).unwrap()).collect::<Vec<_>>();
+        registers.load_struct_members(stack, &Register::Locator(register), &members)
+    };
+
+    // Ensure the members are loaded in the requested order.
+    let expected = [Plaintext::from_str("3u8").unwrap(), Plaintext::from_str("1field").unwrap()];
+    assert_eq!(load(0, &["z", "x"]).unwrap(), expected);
+    assert!(load(0, &[]).unwrap().is_empty());
+
+    // Ensure the first missing member is reported.
+    assert_eq!(load(0, &["x", "w", "v"]).unwrap_err().to_string(), "Member 'w' is not found in 'r0'");
+
+    // Ensure a register that is not a struct fails.
+    assert!(load(1, &["x"]).is_err());
+}
+
+#[test]
+fn test_value_hash() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let hash = |value: &Value<CurrentNetwork>| stack.value_hash(value).unwrap();
+
+    // Ensure equal values hash identically, regardless of their formatting and cached bits.
+    let value = Value::from_str("{ x: 1field, y: [2u8, 3u8] }").unwrap();
+    let reformatted = Value::from_str("{x:1field,y:[2u8,3u8]}").unwrap();
+    assert_eq!(hash(&value), hash(&reformatted));
+    let _ = value.to_bits_le();
+    assert_eq!(hash(&value), hash(&reformatted));
+
+    // Ensure distinct values hash differently, including values with the same members in a different order.
+    assert_ne!(hash(&value), hash(&Value::from_str("{ x: 1field, y: [3u8, 2u8] }").unwrap()));
+    assert_ne!(hash(&value), hash(&Value::from_str("{ y: [2u8, 3u8], x: 1field }").unwrap()));
+    assert_ne!(hash(&Value::from_str("1u8").unwrap()), hash(&Value::from_str("1u16").unwrap()));
+}
+
+#[test]
+fn test_set_call_interceptor() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    // Retrieve the call to 'twice' in 'bar'.
+    let function = stack.get_function_ref(&Identifier::from_str("bar").unwrap()).unwrap();
+    let Instruction::Call(call) = &function.instructions()[0] else { panic!("Expected a call instruction") };
+
+    // Initialize the registers, and set an interceptor that mocks 'twice'.
+    let mut registers = sample_registers(stack, rng);
+    let intercepted = Arc::new(Mutex::new(Vec::new()));
+    let intercepted_clone = intercepted.clone();
+    registers.set_call_interceptor(Box::new(move |callee, inputs| {
+        intercepted_clone.lock().push((*callee, inputs.to_vec()));
+        match callee.resource().to_string() == "twice" {
+            true => Some(vec![Value::from_str("7field").unwrap()]),
+            false => None,
+        }
+    }));
+    registers.store(stack, &Register::Locator(0), Value::from_str("1field").unwrap()).unwrap();
+
+    // Ensure the intercepted outputs are stored in place of evaluating the callee.
+    CallTrait::evaluate(call, stack, &mut registers).unwrap();
+    let output = registers.load(stack, &Operand::Register(Register::Locator(1))).unwrap();
+    assert_eq!(output, Value::from_str("7field").unwrap());
+    assert_eq!(*intercepted.lock(), vec![(
+        Locator::from_str("stack_helpers.aleo/twice").unwrap(),
+        vec![Value::from_str("1field").unwrap()]
+    )]);
+
+    // Ensure an interceptor returning the wrong number of outputs fails.
+    let mut registers = sample_registers(stack, rng);
+    registers.set_call_interceptor(Box::new(|_, _| Some(vec![])));
+    registers.store(stack, &Register::Locator(0), Value::from_str("1field").unwrap()).unwrap();
+    assert!(CallTrait::evaluate(call, stack, &mut registers).is_err());
+
+    // Ensure an intercepted call halts in execute mode, rather than ignoring the interceptor.
+    let mut registers = sample_registers(stack, rng);
+    registers.set_call_interceptor(Box::new(|_, _| Some(vec![Value::from_str("7field").unwrap()])));
+    let value = circuit::Value::<CurrentAleo>::new(Mode::Private, Value::from_str("1field").unwrap());
+    registers.store_circuit(stack, &Register::Locator(0), value).unwrap();
+    let error = CallTrait::execute(call, stack, &mut registers, rng).unwrap_err();
+    assert_eq!(error.to_string(), "Cannot intercept the call to 'stack_helpers.aleo/twice' in execute mode");
+    CurrentAleo::reset();
+
+    // Initialize the programs, where `parent` calls `child`.
+    let child = Program::<CurrentNetwork>::from_str(
+        r"
+program child.aleo;
+
+function grow:
+    input r0 as field.private;
+    add r0 r0 into r1;
+    output r1 as field.private;",
+    )
+    .unwrap();
+    let parent = Program::<CurrentNetwork>::from_str(
+        r"
+import child.aleo;
+
+program parent.aleo;
+
+function quadruple:
+    input r0 as field.private;
+    call child.aleo/grow r0 into r1;
+    add r1 r1 into r2;
+    output r2 as field.private;",
+    )
+    .unwrap();
+    let mut process = crate::test_helpers::sample_process(&child);
+    process.add_program(&parent).unwrap();
+
+    // Authorize a call to `quadruple`, which includes the request of the callee.
+    let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let function_name = Identifier::from_str("quadruple").unwrap();
+    let authorization = process
+        .authorize::<CurrentAleo, _>(&private_key, parent.id(), function_name, ["1field"].into_iter(), rng)
+        .unwrap();
+    assert_eq!(authorization.len(), 2);
+
+    // Set an interceptor in the options that mocks 'grow'.
+    let mut options = ExecutionOptions::default();
+    options.set_call_interceptor(Box::new(|_, _| Some(vec![Value::from_str("5field").unwrap()])));
+
+    // Ensure a call that is not permitted halts before the interceptor is consulted.
+    let mut restricted = options.clone();
+    restricted.set_call_allowlist([*parent.id()].into_iter().collect());
+    let error = process.evaluate_with_options::<CurrentAleo>(authorization.replicate(), &restricted).unwrap_err();
+    assert!(error.to_string().contains("call to child.aleo not permitted"), "{error}");
+
+    // Ensure the intercepted call consumes the request of the callee, and its outputs are used by the caller.
+    let replica = authorization.replicate();
+    let response = process.evaluate_with_options::<CurrentAleo>(replica.clone(), &options).unwrap();
+    assert_eq!(response.outputs(), [Value::from_str("10field").unwrap()]);
+    assert!(replica.is_empty());
+}
+
+#[test]
+fn test_record_type_layouts() {
+    // Initialize the stack.
+    let program = Program::from_str(
+        r"
+program record_layouts.aleo;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+    memo as field.public;
+
+record ticket:
+    owner as address.public;
+    seat as u8.constant;
+
+function foo:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+
+    // Ensure every record type is listed with the visibility of each entry, in declaration order.
+    let id = |name: &str| Identifier::<CurrentNetwork>::from_str(name).unwrap();
+    let layouts = stack.record_type_layouts().unwrap();
+    assert_eq!(layouts.len(), 2);
+    assert_eq!(layouts[&id("token")], vec![
+        (id("owner"), EntryVisibility::Private),
+        (id("amount"), EntryVisibility::Private),
+        (id("memo"), EntryVisibility::Public),
+    ]);
+    assert_eq!(layouts[&id("ticket")], vec![
+        (id("owner"), EntryVisibility::Public),
+        (id("seat"), EntryVisibility::Constant)
+    ]);
+}
+
+#[test]
+fn test_load_future_arguments() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    // Initialize the registers for `deposit`.
+    let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+    let function_name = Identifier::from_str("deposit").unwrap();
+    let mut registers = sample_registers_for(stack, "deposit", &["5u64"], rng);
+
+    // Store the input and the future.
+    let amount = Plaintext::from_str("5u64").unwrap();
+    let future = Future::new(*stack.program_id(), function_name, vec![
+        Argument::Plaintext(Plaintext::from(Literal::Address(address))),
+        Argument::Plaintext(amount.clone()),
+    ]);
+    registers.store(stack, &Register::Locator(0), Value::Plaintext(amount.clone())).unwrap();
+    registers.store(stack, &Register::Locator(1), Value::Future(future)).unwrap();
+
+    // Ensure the arguments of the future are loaded in order.
+    let arguments = registers.load_future_arguments(stack, &Operand::from_str("r1").unwrap()).unwrap();
+    assert_eq!(arguments, vec![Value::Plaintext(Plaintext::from(Literal::Address(address))), Value::Plaintext(amount)]);
+
+    // Ensure a non-future operand fails.
+    let error = registers.load_future_arguments(stack, &Operand::from_str("r0").unwrap()).unwrap_err();
+    assert_eq!(error.to_string(), "Operand 'r0' must be a future, found '5u64'");
+}
+
+#[test]
+fn test_peak_live_registers() {
+    // Initialize the stack.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program live_registers.aleo;
+
+function sum:
+    input r0 as field.private;
+    input r1 as field.private;
+    input r2 as field.private;
+    add r0 r1 into r3;
+    add r3 r2 into r4;
+    output r4 as field.private;
+
+function chain:
+    input r0 as field.private;
+    add r0 r0 into r1;
+    add r1 r1 into r2;
+    add r2 r2 into r3;
+    output r3 as field.private;
+
+function unused:
+    input r0 as field.private;
+    input r1 as field.private;
+    add r0 r0 into r2;
+    output r2 as field.private;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+    let peak = |function_name: &str| stack.peak_live_registers(&Identifier::from_str(function_name).unwrap());
+
+    // Ensure the inputs that are read later are live alongside the first sum.
+    assert_eq!(peak("sum").unwrap(), 4);
+    // Ensure each register in a chain is dead once the next one is assigned.
+    assert_eq!(peak("chain").unwrap(), 2);
+    // Ensure an unused input is live only at the start.
+    assert_eq!(peak("unused").unwrap(), 2);
+
+    // Ensure an unknown function fails.
+    assert!(peak("missing").is_err());
+}
+
+#[test]
+fn test_reset_registers() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    // Assign the registers, then reset them.
+    let mut registers = sample_registers(stack, rng);
+    let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    registers.set_signer(Address::try_from(&private_key).unwrap());
+    registers.store(stack, &Register::Locator(0), Value::from_str("1field").unwrap()).unwrap();
+    registers.store(stack, &Register::Locator(1), Value::from_str("2field").unwrap()).unwrap();
+    registers.reset();
+
+    // Ensure the registers and the signer are cleared.
+    assert!(registers.load(stack, &Operand::from_str("r0").unwrap()).is_err());
+    assert!(registers.signer().is_err());
+    // Ensure the registers can be assigned again, starting from the first register.
+    registers.store(stack, &Register::Locator(0), Value::from_str("3field").unwrap()).unwrap();
+    assert_eq!(registers.load(stack, &Operand::from_str("r0").unwrap()).unwrap(), Value::from_str("3field").unwrap());
+
+    // Ensure the finalize registers can be assigned again, starting from the first register.
+    let function_name = Identifier::from_str("deposit").unwrap();
+    let mut finalize_registers = FinalizeRegisters::<CurrentNetwork>::new(
+        FinalizeGlobalState::from(1, 1, [0; 32]),
+        <CurrentNetwork as Network>::TransitionID::default(),
+        function_name,
+        stack.get_finalize_types(&function_name).unwrap().clone(),
+    );
+    let address = Value::from_str("aleo1qr2ha4pfs5l28aze88yn6fhleeythklkczrule2v838uwj65n5gqxt9djx").unwrap();
+    finalize_registers.store(stack, &Register::Locator(0), address.clone()).unwrap();
+    assert!(finalize_registers.store(stack, &Register::Locator(0), address.clone()).is_err());
+    finalize_registers.reset();
+    assert!(finalize_registers.load(stack, &Operand::from_str("r0").unwrap()).is_err());
+    finalize_registers.store(stack, &Register::Locator(0), address.clone()).unwrap();
+
+    // Ensure the step budget is restored to its configured value.
+    let mut registers = sample_registers(stack, rng);
+    registers.set_step_budget(2);
+    registers.store(stack, &Register::Locator(0), Value::from_str("1field").unwrap()).unwrap();
+    registers.store(stack, &Register::Locator(1), Value::from_str("2field").unwrap()).unwrap();
+    assert!(registers.load(stack, &Operand::from_str("r0").unwrap()).is_err());
+    registers.reset();
+    registers.store(stack, &Register::Locator(0), Value::from_str("1field").unwrap()).unwrap();
+    registers.load(stack, &Operand::from_str("r0").unwrap()).unwrap();
+    finalize_registers.set_step_budget(1);
+    finalize_registers.reset();
+    finalize_registers.store(stack, &Register::Locator(0), address.clone()).unwrap();
+    assert!(finalize_registers.load(stack, &Operand::from_str("r0").unwrap()).is_err());
+    finalize_registers.reset();
+    finalize_registers.store(stack, &Register::Locator(0), address).unwrap();
+}
+
+#[test]
+fn test_matches_array_dims() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let matches =
+        |plaintext: &str, dims: &[u32]| stack.matches_array_dims(&Plaintext::from_str(plaintext).unwrap(), dims);
+
+    // Ensure arrays matching the dimensions pass, including a prefix of the dimensions.
+    assert!(matches("[[1u8, 2u8, 3u8], [4u8, 5u8, 6u8]]", &[2, 3]).is_ok());
+    assert!(matches("[[1u8, 2u8, 3u8], [4u8, 5u8, 6u8]]", &[2]).is_ok());
+    assert!(matches("1u8", &[]).is_ok());
+
+    // Ensure a mismatched dimension reports the level, and the expected and actual lengths.
+    let error = matches("[[1u8, 2u8, 3u8], [4u8, 5u8, 6u8]]", &[3, 3]).unwrap_err();
+    assert_eq!(error.to_string(), "Array at level 0 expects 3 elements, found 2");
+    let error = matches("[[1u8, 2u8, 3u8], [4u8, 5u8, 6u8]]", &[2, 4]).unwrap_err();
+    assert_eq!(error.to_string(), "Array at level 1 expects 4 elements, found 3");
+
+    // Ensure a level that is not an array fails.
+    let error = matches("[1u8, 2u8]", &[2, 1]).unwrap_err();
+    assert_eq!(error.to_string(), "Expected an array at level 1, found '1u8'");
+}
+
+#[test]
+fn test_register_definition() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let bar = Identifier::from_str("bar").unwrap();
+
+    // Ensure the input register has no defining instruction.
+    assert_eq!(stack.register_definition(&bar, 0).unwrap(), None);
+    // Ensure the destination registers are defined by their instructions.
+    assert_eq!(stack.register_definition(&bar, 1).unwrap(), Some(0));
+    assert_eq!(stack.register_definition(&bar, 2).unwrap(), Some(1));
+
+    // Ensure an undefined register, and an unknown function, fail.
+    let error = stack.register_definition(&bar, 3).unwrap_err();
+    assert_eq!(error.to_string(), "Register 'r3' is not defined in 'bar'");
+    assert!(stack.register_definition(&Identifier::from_str("missing").unwrap(), 0).is_err());
+}
+
+#[test]
+fn test_validate_operand_arity() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+    let registers = sample_registers(stack, rng);
+    let validate = |opcode: &str, operands: &[&str]| {
+        let operands = operands.iter().map(|operand| Operand::from_str(operand).unwrap()).collect::<Vec<_>>();
+        registers.validate_operand_arity(opcode, &operands)
+    };
+
+    // Ensure the expected number of operands passes.
+    assert!(validate("abs", &["r0"]).is_ok());
+    assert!(validate("add", &["r0", "1field"]).is_ok());
+    assert!(validate("hash.bhp256", &["r0"]).is_ok());
+    assert!(validate("hash_many.psd2", &["r0", "1u16"]).is_ok());
+    assert!(validate("ternary", &["true", "r0", "r0"]).is_ok());
+    // Ensure opcodes with a variable number of operands accept any number of operands.
+    assert!(validate("call", &["r0", "r0", "r0"]).is_ok());
+    assert!(validate("cast", &["r0"]).is_ok());
+
+    // Ensure a mismatched number of operands fails.
+    assert_eq!(validate("add", &["r0"]).unwrap_err().to_string(), "Opcode 'add' expects 2 operands, found 1");
+    assert_eq!(validate("sign.verify", &["r0"]).unwrap_err().to_string(), "Opcode 'sign.verify' expects 3 operands, found 1");
+    // Ensure an unknown opcode fails.
+    assert!(validate("add.unknown", &["r0", "r0"]).is_err());
+}
+
+#[test]
+fn test_struct_member_type() {
+    // Initialize the stack.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program struct_members.aleo;
+
+struct point:
+    x as field;
+    y as field;
+
+struct segment:
+    start as point;
+    end as point;
+    weights as [u8; 2u32];
+
+function noop:
+    input r0 as segment.private;
+    output r0 as segment.private;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+    let id = |name: &str| Identifier::<CurrentNetwork>::from_str(name).unwrap();
+
+    // Ensure the member types are resolved.
+    assert_eq!(stack.struct_member_type(&id("segment"), &id("start")).unwrap(), PlaintextType::from_str("point").unwrap());
+    assert_eq!(stack.struct_member_type(&id("point"), &id("x")).unwrap(), PlaintextType::from_str("field").unwrap());
+    // Ensure a path of members resolves the nested struct member.
+    let path = [id("end"), id("y")];
+    assert_eq!(stack.struct_member_path_type(&id("segment"), &path).unwrap(), PlaintextType::from_str("field").unwrap());
+
+    // Ensure missing members, paths through non-structs, and unknown structs fail.
+    let error = stack.struct_member_type(&id("point"), &id("z")).unwrap_err();
+    assert_eq!(error.to_string(), "Member 'z' is not found in struct 'point'");
+    assert!(stack.struct_member_path_type(&id("segment"), &[id("weights"), id("x")]).is_err());
+    assert!(stack.struct_member_path_type(&id("segment"), &[]).is_err());
+    assert!(stack.struct_member_type(&id("missing"), &id("x")).is_err());
+}
+
+#[test]
+fn test_assert_outputs_complete() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program output_order.aleo;
+
+function ordered:
+    input r0 as field.private;
+    add r0 r0 into r1;
+    output r0 as field.private;
+    output r1 as field.private;
+    output 1field as field.public;
+
+function swapped:
+    input r0 as field.private;
+    add r0 r0 into r1;
+    output r1 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+
+    // Initializes the registers for the given function, and assigns the first `num_registers` registers.
+    let mut registers = |function_name: &str, num_registers: u64| {
+        let mut registers = sample_registers_for(stack, function_name, &["1field"], rng);
+        let function_name = Identifier::from_str(function_name).unwrap();
+        for locator in 0..num_registers {
+            registers.store(stack, &Register::Locator(locator), Value::from_str("1field").unwrap()).unwrap();
+        }
+        (registers, function_name)
+    };
+
+    // Ensure the outputs of a complete function pass.
+    let (complete, function_name) = registers("ordered", 2);
+    assert!(complete.assert_outputs_complete(stack, &function_name).is_ok());
+
+    // Ensure an unassigned output register fails.
+    let (incomplete, function_name) = registers("ordered", 1);
+    let error = incomplete.assert_outputs_complete(stack, &function_name).unwrap_err();
+    assert_eq!(error.to_string(), "Output 1 ('r1') of 'ordered' is not assigned");
+
+    // Ensure output registers assigned in a different order than they are output pass, as this is legal.
+    let (swapped, function_name) = registers("swapped", 2);
+    assert!(swapped.assert_outputs_complete(stack, &function_name).is_ok());
+}
+
+#[test]
+fn test_load_constant_circuit() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the stack.
+    let program = sample_program();
+    let stack = &sample_stack(&program);
+    let registers = sample_registers(stack, rng);
+    let load = |operand: &str| registers.load_constant_circuit(stack, &Operand::from_str(operand).unwrap());
+
+    // Ensure literals and program IDs are loaded as constants.
+    let value = load("5u64").unwrap();
+    assert_eq!(value.eject_mode(), Mode::Constant);
+    assert_eq!(value.eject_value(), Value::from_str("5u64").unwrap());
+    let value = load("stack_helpers.aleo").unwrap();
+    assert_eq!(value.eject_mode(), Mode::Constant);
+    assert_eq!(value.eject_value(), Value::from_str(&program.id().to_address().unwrap().to_string()).unwrap());
+
+    // Ensure registers, and operands that are not known at compile time, fail.
+    assert_eq!(load("r0").err().unwrap().to_string(), "Operand 'r0' is not a constant");
+    assert!(load("self.caller").is_err());
+}
+
+#[test]
+fn test_json_matches_value_type() {
+    // Initialize the stack.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program json_inputs.aleo;
+
+struct point:
+    x as i8;
+    y as u128;
+
+record ticket:
+    owner as address.private;
+    seats as [u8; 2u32].private;
+
+function noop:
+    input r0 as point.private;
+    output r0 as point.private;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+    let matches = |json: serde_json::Value, value_type: &str| {
+        stack.json_matches_value_type(&json, &ValueType::from_str(value_type).unwrap())
+    };
+
+    // Ensure compatible JSON passes.
+    assert!(matches(serde_json::json!(true), "boolean.public").is_ok());
+    assert!(matches(serde_json::json!("1field"), "field.private").is_ok());
+    assert!(matches(serde_json::json!({ "x": -5, "y": "340282366920938463463374607431768211455" }), "point.private").is_ok());
+    let address = "aleo1qr2ha4pfs5l28aze88yn6fhleeythklkczrule2v838uwj65n5gqxt9djx";
+    assert!(matches(serde_json::json!({ "owner": address, "seats": [1, 2] }), "ticket.record").is_ok());
+
+    // Ensure numbers out of range fail.
+    let error = matches(serde_json::json!({ "x": 128, "y": 0 }), "point.private").unwrap_err();
+    assert_eq!(error.to_string(), "Expected a 'i8' at '$.x', found '128'");
+    assert!(matches(serde_json::json!(-1), "u64.public").is_err());
+
+    // Ensure mismatched structure fails, locating the mismatch.
+    let error = matches(serde_json::json!({ "x": 1 }), "point.private").unwrap_err();
+    assert_eq!(error.to_string(), "Missing member 'y' at '$'");
+    let error = matches(serde_json::json!({ "x": 1, "y": 2, "z": 3 }), "point.private").unwrap_err();
+    assert_eq!(error.to_string(), "Unexpected member 'z' at '$'");
+    let error = matches(serde_json::json!({ "owner": address, "seats": [1, 2, 3] }), "ticket.record").unwrap_err();
+    assert_eq!(error.to_string(), "Expected 2 elements at '$.seats', found 3");
+    let error = matches(serde_json::json!({ "owner": address, "seats": [1, 256] }), "ticket.record").unwrap_err();
+    assert_eq!(error.to_string(), "Expected a 'u8' at '$.seats[1]', found '256'");
+
+    // Ensure futures are not supported.
+    assert!(matches(serde_json::json!({}), "json_inputs.aleo/noop.future").is_err());
+}
+
+#[test]
+fn test_finalize_awaits() {
+    // Initialize the programs, where `relay` awaits two calls to `child_awaits.aleo/bump`.
+    let child = Program::<CurrentNetwork>::from_str(
+        r"
+program child_awaits.aleo;
+
+mapping counts:
+    key as u8.public;
+    value as u64.public;
+
+function bump:
+    input r0 as u64.public;
+    async bump r0 into r1;
+    output r1 as child_awaits.aleo/bump.future;
+
+finalize bump:
+    input r0 as u64.public;
+    set r0 into counts[0u8];",
+    )
+    .unwrap();
+    let parent = Program::<CurrentNetwork>::from_str(
+        r"
+import child_awaits.aleo;
+
+program parent_awaits.aleo;
+
+function relay:
+    input r0 as u64.public;
+    call child_awaits.aleo/bump r0 into r1;
+    call child_awaits.aleo/bump r0 into r2;
+    async relay r1 r2 into r3;
+    output r3 as parent_awaits.aleo/relay.future;
+
+finalize relay:
+    input r0 as child_awaits.aleo/bump.future;
+    input r1 as child_awaits.aleo/bump.future;
+    await r0;
+    await r1;
+
+function noop:
+    input r0 as u64.public;
+    output r0 as u64.public;",
+    )
+    .unwrap();
+    let mut process = crate::test_helpers::sample_process(&child);
+    process.add_program(&parent).unwrap();
+    let stack = process.get_stack(parent.id()).unwrap();
+
+    // Ensure the awaited finalize scopes are returned in the order they are awaited.
+    let bump = Locator::from_str("child_awaits.aleo/bump").unwrap();
+    assert_eq!(stack.finalize_awaits(&Identifier::from_str("relay").unwrap()).unwrap(), vec![bump, bump]);
+    // Ensure a finalize scope without awaits, and a function without a finalize scope, await nothing.
+    let child_stack = process.get_stack(child.id()).unwrap();
+    assert!(child_stack.finalize_awaits(&Identifier::from_str("bump").unwrap()).unwrap().is_empty());
+    assert!(stack.finalize_awaits(&Identifier::from_str("noop").unwrap()).unwrap().is_empty());
+}
+
+#[test]
+fn test_function_bytecode_size() {
+    // Initialize the stack.
+    let program = sample_program();
+    let stack = &sample_stack(&program);
+
+    // Ensure the size is the total size of the serialized instructions.
+    let function_name = Identifier::from_str("bar").unwrap();
+    let expected = program
+        .get_function_ref(&function_name)
+        .unwrap()
+        .instructions()
+        .iter()
+        .map(|instruction| instruction.to_bytes_le().unwrap().len())
+        .sum::<usize>();
+    assert!(expected > 0);
+    assert_eq!(stack.function_bytecode_size(&function_name).unwrap(), expected);
+
+    // Ensure a function with more instructions is larger.
+    let foo = stack.function_bytecode_size(&Identifier::from_str("foo").unwrap()).unwrap();
+    assert!(expected > foo);
+
+    // Ensure closures and unknown functions fail.
+    assert!(stack.function_bytecode_size(&Identifier::from_str("twice").unwrap()).is_err());
+    assert!(stack.function_bytecode_size(&Identifier::from_str("unknown").unwrap()).is_err());
+}
+
+#[test]
+fn test_detect_name_collisions() {
+    // Initialize the programs, where both programs define `point`, and only the child defines `token`.
+    let child = Program::<CurrentNetwork>::from_str(
+        r"
+program child_names.aleo;
+
+struct point:
+    x as field;
+    y as field;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function noop:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let parent = Program::<CurrentNetwork>::from_str(
+        r"
+import child_names.aleo;
+
+program parent_names.aleo;
+
+struct point:
+    x as u8;
+
+struct line:
+    a as point;
+    b as point;
+
+function noop:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    let mut process = crate::test_helpers::sample_process(&child);
+    process.add_program(&parent).unwrap();
+
+    // Ensure only the name defined in both programs is reported, with the programs that define it.
+    let collisions = process.get_stack(parent.id()).unwrap().detect_name_collisions().unwrap();
+    assert_eq!(collisions, vec![(Identifier::from_str("point").unwrap(), vec![*child.id(), *parent.id()])]);
+
+    // Ensure a program without imports has no collisions.
+    assert!(process.get_stack(child.id()).unwrap().detect_name_collisions().unwrap().is_empty());
+
+    // Initialize a program that only imports the parent, so the child is a transitive import.
+    let grandparent = Program::<CurrentNetwork>::from_str(
+        r"
+import parent_names.aleo;
+
+program grandparent_names.aleo;
+
+record token:
+    owner as address.private;
+    amount as u128.private;
+
+function noop:
+    input r0 as field.private;
+    output r0 as field.private;",
+    )
+    .unwrap();
+    process.add_program(&grandparent).unwrap();
+
+    // Ensure the names of the transitive import are resolved through the parent.
+    let collisions = process.get_stack(grandparent.id()).unwrap().detect_name_collisions().unwrap();
+    assert_eq!(collisions, vec![
+        (Identifier::from_str("point").unwrap(), vec![*child.id(), *parent.id()]),
+        (Identifier::from_str("token").unwrap(), vec![*child.id(), *grandparent.id()]),
+    ]);
+}
+
+#[test]
+fn test_assertions() {
+    // Initialize the stack.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program assertions.aleo;
+
+function guard:
+    input r0 as u64.public;
+    input r1 as u64.private;
+    assert.eq r0 r1;
+    add r0 r1 into r2;
+    assert.neq r2 0u64;
+    output r2 as u64.private;",
+    )
+    .unwrap();
+    let stack = &sample_stack(&program);
+
+    // Ensure each assertion is returned with its index and operands.
+    let operand = |operand: &str| Operand::from_str(operand).unwrap();
+    assert_eq!(stack.assertions(&Identifier::from_str("guard").unwrap()).unwrap(), vec![
+        (0, operand("r0"), operand("r1")),
+        (2, operand("r2"), operand("0u64")),
+    ]);
+
+    // Ensure an unknown function fails.
+    assert!(stack.assertions(&Identifier::from_str("unknown").unwrap()).is_err());
+}
+
+#[test]
+fn test_instruction_costs() {
+    // Initialize the stack.
+    let stack = &sample_stack(&sample_program());
+
+    // Ensure each finalize command is priced by the cost table, in order.
+    let finalize = stack.get_function_ref(&Identifier::from_str("deposit").unwrap()).unwrap().finalize_logic().unwrap();
+    let expected = finalize
+        .commands()
+        .iter()
+        .enumerate()
+        .map(|(index, command)| (index, crate::cost_per_command(command).unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(stack.instruction_costs(&Identifier::from_str("deposit").unwrap()).unwrap(), expected);
+    // Ensure `get.or_use`, `add`, and `set` are priced as expected.
+    assert_eq!(expected, vec![(0, 25_000), (1, 2_000), (2, 100_000)]);
+
+    // Ensure a function without a finalize scope has no costs, and an unknown function fails.
+    assert!(stack.instruction_costs(&Identifier::from_str("foo").unwrap()).unwrap().is_empty());
+    assert!(stack.instruction_costs(&Identifier::from_str("unknown").unwrap()).is_err());
+}
@@ -12,17 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{CallStack, Closure, FinalizeTypes, RegisterTypes};
+use crate::{CallCost, CallStack, Closure, ExecutionOptions, ExecutionTrace, FinalizeTypes, RegisterTypes};
 use console::{
     account::Address,
     network::Network,
     prelude::{CryptoRng, Result, Rng},
-    program::{Identifier, ProgramID, Response, Value},
+    program::{Identifier, Locator, ProgramID, Response, Value},
     types::Field,
 };
+use synthesizer_program::{Instruction, StackMatches, StackProgram};
+
+use indexmap::IndexMap;
+use std::collections::BTreeSet;
+
+/// The interceptor that is consulted before a call, and returns the outputs to use in place of the call, if any.
+pub type CallInterceptor<N> = Box<dyn FnMut(&Locator<N>, &[Value<N>]) -> Option<Vec<Value<N>>> + Send>;
 
 pub trait StackEvaluate<N: Network>: Clone {
-    /// Evaluates a program closure on the given inputs.
+    /// Evaluates a program closure on the given inputs, with the given options.
     ///
     /// # Errors
     /// This method will halt if the given inputs are not the same length as the input statements.
@@ -31,24 +38,26 @@ pub trait StackEvaluate<N: Network>: Clone {
         closure: &Closure<N>,
         inputs: &[Value<N>],
         call_stack: CallStack<N>,
+        options: &ExecutionOptions<N>,
         signer: Address<N>,
         caller: Address<N>,
         tvk: Field<N>,
     ) -> Result<Vec<Value<N>>>;
 
-    /// Evaluates a program function on the given inputs.
+    /// Evaluates a program function on the given inputs, with the given options.
     ///
     /// # Errors
     /// This method will halt if the given inputs are not the same length as the input statements.
     fn evaluate_function<A: circuit::Aleo<Network = N>>(
         &self,
         call_stack: CallStack<N>,
+        options: &ExecutionOptions<N>,
         caller: Option<ProgramID<N>>,
     ) -> Result<Response<N>>;
 }
 
 pub trait StackExecute<N: Network> {
-    /// Executes a program closure on the given inputs.
+    /// Executes a program closure on the given inputs, with the given options.
     ///
     /// # Errors
     /// This method will halt if the given inputs are not the same length as the input statements.
@@ -57,12 +66,13 @@ pub trait StackExecute<N: Network> {
         closure: &Closure<N>,
         inputs: &[circuit::Value<A>],
         call_stack: CallStack<N>,
+        options: &ExecutionOptions<N>,
         signer: circuit::Address<A>,
         caller: circuit::Address<A>,
         tvk: circuit::Field<A>,
     ) -> Result<Vec<circuit::Value<A>>>;
 
-    /// Executes a program function on the given inputs.
+    /// Executes a program function on the given inputs, with the given options.
     ///
     /// Note: To execute a transition, do **not** call this method. Instead, call `Process::execute`.
     ///
@@ -71,6 +81,7 @@ pub trait StackExecute<N: Network> {
     fn execute_function<A: circuit::Aleo<Network = N>, R: CryptoRng + Rng>(
         &self,
         call_stack: CallStack<N>,
+        options: &ExecutionOptions<N>,
         console_caller: Option<ProgramID<N>>,
         rng: &mut R,
     ) -> Result<Response<N>>;
@@ -82,9 +93,68 @@ pub trait StackProgramTypes<N: Network> {
 
     /// Returns the register types for the given finalize name.
     fn get_finalize_types(&self, name: &Identifier<N>) -> Result<&FinalizeTypes<N>>;
+
+    /// Derives the register types of every closure and function in the program, in parallel.
+    fn derive_all_register_types_parallel(&self) -> Result<IndexMap<Identifier<N>, RegisterTypes<N>>>;
 }
 
 pub trait RegistersCall<N: Network> {
     /// Returns the current call stack.
     fn call_stack(&self) -> CallStack<N>;
+
+    /// Returns the options that the registers are run with.
+    fn options(&self) -> &ExecutionOptions<N>;
+
+    /// Returns the estimated cost of calling the given closure or function on the given inputs.
+    /// The inputs are checked against the declared input types of the callee, but the callee is not
+    /// evaluated and no transition is produced.
+    fn simulate_call(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        callee: &Locator<N>,
+        inputs: &[Value<N>],
+    ) -> Result<CallCost<N>>;
+
+    /// Restricts the external calls, including those of nested calls, to the given set of programs.
+    fn set_call_allowlist(&mut self, allowed: BTreeSet<ProgramID<N>>);
+
+    /// Checks that an external call to the given program is permitted by the call allowlist, if one is set.
+    fn check_call_permitted(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        program_id: &ProgramID<N>,
+    ) -> Result<()>;
+
+    /// Sets the maximum depth of nested calls, including the call itself.
+    fn set_max_call_depth(&mut self, max: usize);
+
+    /// Checks that a call to the given closure or function does not exceed the maximum call depth, if one is set.
+    fn check_call_depth(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        resource: &Identifier<N>,
+    ) -> Result<()>;
+
+    /// Sets the interceptor that is consulted before every call, including nested calls, once the call is permitted.
+    /// When evaluating, if the interceptor returns outputs, the callee is not evaluated and the outputs are used instead.
+    /// When executing, a call that the interceptor would intercept halts, as the callee must be executed.
+    fn set_call_interceptor(&mut self, interceptor: CallInterceptor<N>);
+
+    /// Returns the outputs of the call interceptor for the given callee and inputs, if one is set and intercepts the call.
+    fn intercept_call(&self, callee: &Locator<N>, inputs: &[Value<N>]) -> Option<Vec<Value<N>>>;
+}
+
+pub trait RegistersRecorder<N: Network> {
+    /// Starts recording the loads and stores performed by the registers, discarding any previous trace.
+    fn start_recording(&mut self);
+
+    /// Records the given instruction, if recording. The subsequent loads and stores are recorded under the instruction,
+    /// until `end_instruction` is called.
+    fn begin_instruction(&mut self, instruction: &Instruction<N>);
+
+    /// Ends the current instruction, if recording, so that the subsequent loads and stores are recorded as steps of their own.
+    fn end_instruction(&mut self);
+
+    /// Returns the trace recorded since recording started, and stops recording.
+    fn take_trace(&mut self) -> ExecutionTrace<N>;
 }
@@ -0,0 +1,25 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network> Process<N> {
+    /// Runs the structural checks of the given program, and returns the warnings and errors they report.
+    /// The program is checked before its stack is built, so the checks also report the programs that
+    /// the process would reject when adding them. The imports of the program must already be in the process.
+    #[inline]
+    pub fn verify_program(&self, program: &Program<N>) -> Result<Vec<ProgramWarning<N>>> {
+        Ok(Stack::program_warnings(program, |program_id| self.get_stack(*program_id)))
+    }
+}
@@ -150,6 +150,25 @@ impl<N: Network, Instruction: InstructionTrait<N>, Command: CommandTrait<N>> Fun
         Ok(())
     }
 
+    /// Replaces the instruction at the given index with the given instruction.
+    ///
+    /// # Errors
+    /// This method will halt if the instruction at the given index does not exist.
+    #[inline]
+    pub fn replace_instruction(&mut self, index: usize, instruction: Instruction) -> Result<()> {
+        // Ensure the destination register is a locator.
+        for register in instruction.destinations() {
+            ensure!(matches!(register, Register::Locator(..)), "Destination register must be a locator");
+        }
+
+        // Replace the instruction.
+        match self.instructions.get_mut(index) {
+            Some(entry) => *entry = instruction,
+            None => bail!("Instruction {index} does not exist in '{}'", self.name),
+        }
+        Ok(())
+    }
+
     /// Adds the output statement to the function.
     ///
     /// # Errors
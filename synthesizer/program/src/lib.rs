@@ -549,6 +549,31 @@ impl<N: Network, Instruction: InstructionTrait<N>, Command: CommandTrait<N>> Pro
         }
         Ok(())
     }
+
+    /// Replaces the function of the same name in the program with the given function.
+    ///
+    /// # Errors
+    /// This method will halt if the function does not exist in the program.
+    /// This method will halt if the function exceeds the maximum number of inputs, instructions, or outputs.
+    #[inline]
+    pub fn replace_function(&mut self, function: FunctionCore<N, Instruction, Command>) -> Result<()> {
+        // Retrieve the function name.
+        let function_name = *function.name();
+
+        // Ensure the number of inputs is within the allowed range.
+        ensure!(function.inputs().len() <= N::MAX_INPUTS, "Function exceeds maximum number of inputs");
+        // Ensure the number of instructions is within the allowed range.
+        ensure!(function.instructions().len() <= N::MAX_INSTRUCTIONS, "Function exceeds maximum instructions");
+        // Ensure the number of outputs is within the allowed range.
+        ensure!(function.outputs().len() <= N::MAX_OUTPUTS, "Function exceeds maximum number of outputs");
+
+        // Replace the function in the program.
+        match self.functions.get_mut(&function_name) {
+            Some(entry) => *entry = function,
+            None => bail!("Function '{function_name}' is not defined."),
+        }
+        Ok(())
+    }
 }
 
 impl<N: Network, Instruction: InstructionTrait<N>, Command: CommandTrait<N>> ProgramCore<N, Instruction, Command> {
@@ -0,0 +1,51 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Operand;
+use console::{
+    network::Network,
+    program::{Locator, Register},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallSite<N: Network> {
+    /// The locator of the called closure or function.
+    callee: Locator<N>,
+    /// The operands passed as inputs to the callee.
+    inputs: Vec<Operand<N>>,
+    /// The registers that are assigned the outputs of the callee.
+    outputs: Vec<Register<N>>,
+}
+
+impl<N: Network> CallSite<N> {
+    /// Initializes a new call site.
+    pub const fn new(callee: Locator<N>, inputs: Vec<Operand<N>>, outputs: Vec<Register<N>>) -> Self {
+        Self { callee, inputs, outputs }
+    }
+
+    /// Returns the locator of the called closure or function.
+    pub const fn callee(&self) -> &Locator<N> {
+        &self.callee
+    }
+
+    /// Returns the operands passed as inputs to the callee.
+    pub fn inputs(&self) -> &[Operand<N>] {
+        &self.inputs
+    }
+
+    /// Returns the registers that are assigned the outputs of the callee.
+    pub fn outputs(&self) -> &[Register<N>] {
+        &self.outputs
+    }
+}
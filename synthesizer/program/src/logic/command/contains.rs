@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::{
-    traits::{FinalizeStoreTrait, RegistersLoad, RegistersStore, StackMatches, StackProgram},
+    traits::{FinalizeRegistersState, FinalizeStoreTrait, RegistersLoad, RegistersStore, StackMatches, StackProgram},
     Opcode,
     Operand,
 };
@@ -74,18 +74,26 @@ impl<N: Network> Contains<N> {
         &self,
         stack: &(impl StackMatches<N> + StackProgram<N>),
         store: &impl FinalizeStoreTrait<N>,
-        registers: &mut (impl RegistersLoad<N> + RegistersStore<N>),
+        registers: &mut (impl RegistersLoad<N> + RegistersStore<N> + FinalizeRegistersState<N>),
     ) -> Result<()> {
-        // Ensure the mapping exists in storage.
-        if !store.contains_mapping_confirmed(stack.program_id(), &self.mapping)? {
-            bail!("Mapping '{}/{}' does not exist in storage", stack.program_id(), self.mapping);
-        }
-
         // Load the operand as a plaintext.
         let key = registers.load_plaintext(stack, &self.key)?;
 
-        // Determine if the key exists in the mapping.
-        let contains_key = store.contains_key_speculative(*stack.program_id(), self.mapping, &key)?;
+        // Determine if the key exists in the mapping, from the mapping snapshot if one is set.
+        let contains_key = match registers.mapping_snapshot_value(stack.program_id(), &self.mapping, &key) {
+            Some(value) => {
+                // Ensure the mapping exists in the program.
+                stack.program().get_mapping(&self.mapping)?;
+                value.is_some()
+            }
+            None => {
+                // Ensure the mapping exists in storage.
+                if !store.contains_mapping_confirmed(stack.program_id(), &self.mapping)? {
+                    bail!("Mapping '{}/{}' does not exist in storage", stack.program_id(), self.mapping);
+                }
+                store.contains_key_speculative(*stack.program_id(), self.mapping, &key)?
+            }
+        };
 
         // Assign the value to the destination register.
         registers.store(stack, &self.destination, Value::from(Literal::Boolean(Boolean::new(contains_key))))?;
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::{
-    traits::{FinalizeStoreTrait, RegistersLoad, RegistersStore, StackMatches, StackProgram},
+    traits::{FinalizeRegistersState, FinalizeStoreTrait, RegistersLoad, RegistersStore, StackMatches, StackProgram},
     MappingLocator,
     Opcode,
     Operand,
@@ -109,7 +109,7 @@ impl<N: Network> GetOrUse<N> {
         &self,
         stack: &(impl StackMatches<N> + StackProgram<N>),
         store: &impl FinalizeStoreTrait<N>,
-        registers: &mut (impl RegistersLoad<N> + RegistersStore<N>),
+        registers: &mut (impl RegistersLoad<N> + RegistersStore<N> + FinalizeRegistersState<N>),
     ) -> Result<()> {
         // Determine the program ID and mapping name.
         let (program_id, mapping_name) = match self.mapping {
@@ -117,19 +117,37 @@ impl<N: Network> GetOrUse<N> {
             MappingLocator::Resource(mapping_name) => (*stack.program_id(), mapping_name),
         };
 
-        // Ensure the mapping exists in storage.
-        if !store.contains_mapping_confirmed(&program_id, &mapping_name)? {
-            bail!("Mapping '{program_id}/{mapping_name}' does not exist in storage");
-        }
-
         // Load the operand as a plaintext.
         let key = registers.load_plaintext(stack, &self.key)?;
 
-        // Retrieve the value from storage as a literal.
-        let value = match store.get_value_speculative(program_id, mapping_name, &key)? {
-            Some(Value::Plaintext(plaintext)) => Value::Plaintext(plaintext),
-            Some(Value::Record(..)) => bail!("Cannot 'get.or_use' a 'record'"),
-            Some(Value::Future(..)) => bail!("Cannot 'get.or_use' a 'future'"),
+        // Retrieve the value from the mapping snapshot, if one is set, in place of storage.
+        let value = match registers.mapping_snapshot_value(&program_id, &mapping_name, &key) {
+            Some(value) => {
+                // Ensure the mapping exists in the program, as the snapshot is complete.
+                let program = match stack.is_current_program(&program_id) {
+                    true => stack.program(),
+                    false => stack.get_external_program(&program_id)?,
+                };
+                program.get_mapping(&mapping_name)?;
+                value
+            }
+            None => {
+                // Ensure the mapping exists in storage.
+                if !store.contains_mapping_confirmed(&program_id, &mapping_name)? {
+                    bail!("Mapping '{program_id}/{mapping_name}' does not exist in storage");
+                }
+                // Retrieve the value from storage as a plaintext.
+                match store.get_value_speculative(program_id, mapping_name, &key)? {
+                    Some(Value::Plaintext(plaintext)) => Some(plaintext),
+                    Some(Value::Record(..)) => bail!("Cannot 'get.or_use' a 'record'"),
+                    Some(Value::Future(..)) => bail!("Cannot 'get.or_use' a 'future'"),
+                    None => None,
+                }
+            }
+        };
+
+        let value = match value {
+            Some(plaintext) => Value::Plaintext(plaintext),
             // If a key does not exist, then use the default value.
             None => Value::Plaintext(registers.load_plaintext(stack, &self.default)?),
         };
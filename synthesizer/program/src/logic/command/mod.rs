@@ -53,6 +53,7 @@ use crate::{
     FinalizeOperation,
     FinalizeRegistersState,
     Instruction,
+    Opcode,
 };
 use console::{
     network::prelude::*,
@@ -145,6 +146,24 @@ impl<N: Network> CommandTrait<N> for Command<N> {
 }
 
 impl<N: Network> Command<N> {
+    /// Returns the opcode of the command.
+    #[inline]
+    pub const fn opcode(&self) -> Opcode {
+        match self {
+            Command::Instruction(instruction) => instruction.opcode(),
+            Command::Await(_) => Await::<N>::opcode(),
+            Command::Contains(_) => Contains::<N>::opcode(),
+            Command::Get(_) => Get::<N>::opcode(),
+            Command::GetOrUse(_) => GetOrUse::<N>::opcode(),
+            Command::RandChaCha(_) => RandChaCha::<N>::opcode(),
+            Command::Remove(_) => Remove::<N>::opcode(),
+            Command::Set(_) => Set::<N>::opcode(),
+            Command::BranchEq(_) => BranchEq::<N>::opcode(),
+            Command::BranchNeq(_) => BranchNeq::<N>::opcode(),
+            Command::Position(_) => Position::<N>::opcode(),
+        }
+    }
+
     /// Finalizes the command.
     #[inline]
     pub fn finalize(
@@ -169,7 +188,7 @@ impl<N: Network> Command<N> {
             // Finalize the 'remove' command, and return the finalize operation.
             Command::Remove(remove) => remove.finalize(stack, store, registers),
             // Finalize the 'set' command, and return the finalize operation.
-            Command::Set(set) => set.finalize(stack, store, registers).map(Some),
+            Command::Set(set) => set.finalize(stack, store, registers),
             // 'branch.eq' and 'branch.neq' commands are processed by the caller of this method.
             Command::BranchEq(_) | Command::BranchNeq(_) => {
                 bail!("`branch` commands cannot be finalized directly.")
@@ -87,16 +87,19 @@ impl<N: Network> RandChaCha<N> {
         // Load the operands values.
         let seeds: Vec<_> = self.operands.iter().map(|operand| registers.load(stack, operand)).try_collect()?;
 
-        // Construct the random seed.
-        let preimage = to_bits_le![
-            registers.state().random_seed(),
+        // Construct the random seed, using the seed set in the registers in place of the global seed, if one is set.
+        let mut preimage = match registers.randomness_seed() {
+            Some(seed) => seed.to_bits_le(),
+            None => registers.state().random_seed().to_bits_le(),
+        };
+        preimage.extend(to_bits_le![
             **registers.transition_id(),
             stack.program_id(),
             registers.function_name(),
             self.destination.locator(),
             self.destination_type.type_id(),
             seeds
-        ];
+        ]);
 
         // Hash the preimage.
         let digest = N::hash_bhp1024(&preimage)?.to_bytes_le()?;
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::{
-    traits::{FinalizeStoreTrait, RegistersLoad, StackMatches, StackProgram},
+    traits::{FinalizeRegistersState, FinalizeStoreTrait, RegistersLoad, StackMatches, StackProgram},
     FinalizeOperation,
     Opcode,
     Operand,
@@ -63,8 +63,19 @@ impl<N: Network> Remove<N> {
         &self,
         stack: &(impl StackMatches<N> + StackProgram<N>),
         store: &impl FinalizeStoreTrait<N>,
-        registers: &mut impl RegistersLoad<N>,
+        registers: &mut (impl RegistersLoad<N> + FinalizeRegistersState<N>),
     ) -> Result<Option<FinalizeOperation<N>>> {
+        // If a mapping snapshot is set, remove the key from the snapshot in place of storage.
+        if registers.has_mapping_snapshot() {
+            // Ensure the mapping exists in the program.
+            stack.program().get_mapping(&self.mapping)?;
+            // Load the key operand as a plaintext.
+            let key = registers.load_plaintext(stack, &self.key)?;
+            // Remove the key from the snapshot, which produces no finalize operation.
+            registers.update_mapping_snapshot(*stack.program_id(), self.mapping, &key, None)?;
+            return Ok(None);
+        }
+
         // Ensure the mapping exists in storage.
         if !store.contains_mapping_confirmed(stack.program_id(), &self.mapping)? {
             bail!("Mapping '{}/{}' does not exist in storage", stack.program_id(), self.mapping);
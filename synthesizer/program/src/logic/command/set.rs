@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::{
-    traits::{FinalizeStoreTrait, RegistersLoad, StackMatches, StackProgram},
+    traits::{FinalizeRegistersState, FinalizeStoreTrait, RegistersLoad, StackMatches, StackProgram},
     FinalizeOperation,
     Opcode,
     Operand,
@@ -74,8 +74,21 @@ impl<N: Network> Set<N> {
         &self,
         stack: &(impl StackMatches<N> + StackProgram<N>),
         store: &impl FinalizeStoreTrait<N>,
-        registers: &mut impl RegistersLoad<N>,
-    ) -> Result<FinalizeOperation<N>> {
+        registers: &mut (impl RegistersLoad<N> + FinalizeRegistersState<N>),
+    ) -> Result<Option<FinalizeOperation<N>>> {
+        // If a mapping snapshot is set, update the value in the snapshot in place of storage.
+        if registers.has_mapping_snapshot() {
+            // Ensure the mapping exists in the program.
+            stack.program().get_mapping(&self.mapping)?;
+            // Load the key operand as a plaintext.
+            let key = registers.load_plaintext(stack, &self.key)?;
+            // Load the value operand as a plaintext.
+            let value = registers.load_plaintext(stack, &self.value)?;
+            // Update the value in the snapshot, which produces no finalize operation.
+            registers.update_mapping_snapshot(*stack.program_id(), self.mapping, &key, Some(value))?;
+            return Ok(None);
+        }
+
         // Ensure the mapping exists in storage.
         if !store.contains_mapping_confirmed(stack.program_id(), &self.mapping)? {
             bail!("Mapping '{}/{}' does not exist in storage", stack.program_id(), self.mapping);
@@ -87,7 +100,7 @@ impl<N: Network> Set<N> {
         let value = Value::Plaintext(registers.load_plaintext(stack, &self.value)?);
 
         // Update the value in storage, and return the finalize operation.
-        store.update_key_value(*stack.program_id(), self.mapping, key, value)
+        store.update_key_value(*stack.program_id(), self.mapping, key, value).map(Some)
     }
 }
 
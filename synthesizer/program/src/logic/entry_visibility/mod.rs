@@ -0,0 +1,44 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::{network::Network, program::EntryType};
+
+/// The visibility of a record entry.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EntryVisibility {
+    /// The entry is a constant, and is visible to everyone.
+    Constant,
+    /// The entry is public, and is visible to everyone.
+    Public,
+    /// The entry is private, and is only visible with the view key of the record owner.
+    Private,
+}
+
+impl EntryVisibility {
+    /// Returns `true` if the entry can only be read with the view key of the record owner.
+    pub const fn is_private(&self) -> bool {
+        matches!(self, Self::Private)
+    }
+}
+
+impl<N: Network> From<&EntryType<N>> for EntryVisibility {
+    /// Returns the visibility of the given entry type.
+    fn from(entry_type: &EntryType<N>) -> Self {
+        match entry_type {
+            EntryType::Constant(..) => Self::Constant,
+            EntryType::Public(..) => Self::Public,
+            EntryType::Private(..) => Self::Private,
+        }
+    }
+}
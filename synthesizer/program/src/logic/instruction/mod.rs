@@ -372,6 +372,18 @@ macro_rules! opcodes {
     ($_object:expr, |$_reader:ident| $_operation:block, { $( $variant:ident, )+ }) => { [$( $variant::<N>::opcode() ),+] }
 }
 
+/// Returns a slice of the number of operands of each instruction.
+///
+/// ## Example
+/// ```ignore
+/// num_operands!(Instruction, |None| {}, { Add, Sub, Mul, Div })
+/// ```
+macro_rules! num_operands {
+    ($_object:expr, |$_reader:ident| $_operation:block, { $( $variant:ident, )+ }) => {
+        [$( $variant::<N>::num_operands() ),+]
+    }
+}
+
 impl<N: Network> InstructionTrait<N> for Instruction<N> {
     /// Returns the destination registers of the instruction.
     #[inline]
@@ -391,6 +403,10 @@ impl<N: Network> Instruction<N> {
     /// The list of all instruction opcodes.
     pub const OPCODES: &'static [Opcode] = &instruction!(opcodes, Instruction, |None| {});
 
+    /// The number of operands of each instruction, in the order of `OPCODES`.
+    /// An instruction with a variable number of operands is `None`.
+    pub const NUM_OPERANDS: &'static [Option<usize>] = &instruction!(num_operands, Instruction, |None| {});
+
     /// Returns the opcode of the instruction.
     #[inline]
     pub const fn opcode(&self) -> Opcode {
@@ -403,6 +419,12 @@ impl<N: Network> Instruction<N> {
         instruction!(self, |instruction| instruction.operands())
     }
 
+    /// Returns the instruction with the operand at the given index replaced by the given operand.
+    #[inline]
+    pub fn with_operand(&self, index: usize, operand: Operand<N>) -> Result<Self> {
+        instruction!(self, |instruction| Ok(instruction.with_operand(index, operand)?.into()))
+    }
+
     /// Evaluates the instruction.
     #[inline]
     pub fn evaluate(
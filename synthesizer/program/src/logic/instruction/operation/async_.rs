@@ -46,6 +46,12 @@ impl<N: Network> Async<N> {
         Opcode::Async
     }
 
+    /// Returns the number of operands, or `None` if the number of operands is variable.
+    #[inline]
+    pub const fn num_operands() -> Option<usize> {
+        None
+    }
+
     /// Returns the function name.
     #[inline]
     pub const fn function_name(&self) -> &Identifier<N> {
@@ -61,6 +67,17 @@ impl<N: Network> Async<N> {
         &self.operands
     }
 
+    /// Returns the instruction with the operand at the given index replaced by the given operand.
+    #[inline]
+    pub fn with_operand(&self, index: usize, operand: Operand<N>) -> Result<Self> {
+        let mut instruction = self.clone();
+        match instruction.operands.get_mut(index) {
+            Some(entry) => *entry = operand,
+            None => bail!("Operand {index} does not exist in '{}'", Self::opcode()),
+        }
+        Ok(instruction)
+    }
+
     /// Returns the destination register.
     #[inline]
     pub fn destinations(&self) -> Vec<Register<N>> {
@@ -127,6 +127,12 @@ impl<N: Network> Call<N> {
         Opcode::Call
     }
 
+    /// Returns the number of operands, or `None` if the number of operands is variable.
+    #[inline]
+    pub const fn num_operands() -> Option<usize> {
+        None
+    }
+
     /// Return the operator.
     #[inline]
     pub const fn operator(&self) -> &CallOperator<N> {
@@ -139,6 +145,17 @@ impl<N: Network> Call<N> {
         &self.operands
     }
 
+    /// Returns the instruction with the operand at the given index replaced by the given operand.
+    #[inline]
+    pub fn with_operand(&self, index: usize, operand: Operand<N>) -> Result<Self> {
+        let mut instruction = self.clone();
+        match instruction.operands.get_mut(index) {
+            Some(entry) => *entry = operand,
+            None => bail!("Operand {index} does not exist in '{}'", Self::opcode()),
+        }
+        Ok(instruction)
+    }
+
     /// Returns the destination registers.
     #[inline]
     pub fn destinations(&self) -> Vec<Register<N>> {
@@ -87,6 +87,12 @@ impl<N: Network, const VARIANT: u8> CommitInstruction<N, VARIANT> {
         }
     }
 
+    /// Returns the number of operands.
+    #[inline]
+    pub const fn num_operands() -> Option<usize> {
+        Some(2)
+    }
+
     /// Returns the operands in the operation.
     #[inline]
     pub fn operands(&self) -> &[Operand<N>] {
@@ -96,6 +102,17 @@ impl<N: Network, const VARIANT: u8> CommitInstruction<N, VARIANT> {
         &self.operands
     }
 
+    /// Returns the instruction with the operand at the given index replaced by the given operand.
+    #[inline]
+    pub fn with_operand(&self, index: usize, operand: Operand<N>) -> Result<Self> {
+        let mut instruction = self.clone();
+        match instruction.operands.get_mut(index) {
+            Some(entry) => *entry = operand,
+            None => bail!("Operand {index} does not exist in '{}'", Self::opcode()),
+        }
+        Ok(instruction)
+    }
+
     /// Returns the destination register.
     #[inline]
     pub fn destinations(&self) -> Vec<Register<N>> {
@@ -12,9 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod call_site;
+pub use call_site::*;
+
 pub mod command;
 pub use command::*;
 
+mod entry_visibility;
+pub use entry_visibility::*;
+
 mod finalize_global_state;
 pub use finalize_global_state::*;
 
@@ -23,3 +29,18 @@ pub use finalize_operation::*;
 
 pub mod instruction;
 pub use instruction::*;
+
+mod program_complexity;
+pub use program_complexity::*;
+
+mod program_diff;
+pub use program_diff::*;
+
+mod program_schema;
+pub use program_schema::*;
+
+mod program_warning;
+pub use program_warning::*;
+
+mod register_origin;
+pub use register_origin::*;
@@ -0,0 +1,99 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ProgramComplexity {
+    /// The number of functions in the program.
+    num_functions: usize,
+    /// The number of closures in the program.
+    num_closures: usize,
+    /// The number of structs in the program.
+    num_structs: usize,
+    /// The number of records in the program.
+    num_records: usize,
+    /// The number of mappings in the program.
+    num_mappings: usize,
+    /// The total number of instructions, across all closures and functions.
+    num_instructions: usize,
+    /// The total number of commands, across all finalize blocks.
+    num_commands: usize,
+    /// The maximum depth of nested calls, across all closures and functions.
+    max_call_depth: usize,
+}
+
+impl ProgramComplexity {
+    /// Initializes a new program complexity summary.
+    #[allow(clippy::too_many_arguments)]
+    pub const fn new(
+        num_functions: usize,
+        num_closures: usize,
+        num_structs: usize,
+        num_records: usize,
+        num_mappings: usize,
+        num_instructions: usize,
+        num_commands: usize,
+        max_call_depth: usize,
+    ) -> Self {
+        Self {
+            num_functions,
+            num_closures,
+            num_structs,
+            num_records,
+            num_mappings,
+            num_instructions,
+            num_commands,
+            max_call_depth,
+        }
+    }
+
+    /// Returns the number of functions in the program.
+    pub const fn num_functions(&self) -> usize {
+        self.num_functions
+    }
+
+    /// Returns the number of closures in the program.
+    pub const fn num_closures(&self) -> usize {
+        self.num_closures
+    }
+
+    /// Returns the number of structs in the program.
+    pub const fn num_structs(&self) -> usize {
+        self.num_structs
+    }
+
+    /// Returns the number of records in the program.
+    pub const fn num_records(&self) -> usize {
+        self.num_records
+    }
+
+    /// Returns the number of mappings in the program.
+    pub const fn num_mappings(&self) -> usize {
+        self.num_mappings
+    }
+
+    /// Returns the total number of instructions, across all closures and functions.
+    pub const fn num_instructions(&self) -> usize {
+        self.num_instructions
+    }
+
+    /// Returns the total number of commands, across all finalize blocks.
+    pub const fn num_commands(&self) -> usize {
+        self.num_commands
+    }
+
+    /// Returns the maximum depth of nested calls, across all closures and functions.
+    pub const fn max_call_depth(&self) -> usize {
+        self.max_call_depth
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::{network::Network, program::Identifier};
+
+use indexmap::IndexMap;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeclarationDiff<N: Network> {
+    /// The names of the declarations that are only in the new program.
+    added: Vec<Identifier<N>>,
+    /// The names of the declarations that are only in the old program.
+    removed: Vec<Identifier<N>>,
+    /// The names of the declarations that are in both programs, with different definitions.
+    changed: Vec<Identifier<N>>,
+}
+
+impl<N: Network> DeclarationDiff<N> {
+    /// Initializes a new declaration diff, by comparing the old and new declarations by name.
+    pub fn new<T: PartialEq>(old: &IndexMap<Identifier<N>, T>, new: &IndexMap<Identifier<N>, T>) -> Self {
+        let added = new.keys().filter(|name| !old.contains_key(*name)).copied().collect();
+        let removed = old.keys().filter(|name| !new.contains_key(*name)).copied().collect();
+        let changed = old
+            .iter()
+            .filter(|(name, declaration)| {
+                new.get(*name).map_or(false, |new_declaration| new_declaration != *declaration)
+            })
+            .map(|(name, _)| *name)
+            .collect();
+        Self { added, removed, changed }
+    }
+
+    /// Returns the names of the declarations that are only in the new program.
+    pub fn added(&self) -> &[Identifier<N>] {
+        &self.added
+    }
+
+    /// Returns the names of the declarations that are only in the old program.
+    pub fn removed(&self) -> &[Identifier<N>] {
+        &self.removed
+    }
+
+    /// Returns the names of the declarations that are in both programs, with different definitions.
+    pub fn changed(&self) -> &[Identifier<N>] {
+        &self.changed
+    }
+
+    /// Returns `true` if no declarations were added, removed, or changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgramDiff<N: Network> {
+    /// The differences in the mappings.
+    mappings: DeclarationDiff<N>,
+    /// The differences in the structs.
+    structs: DeclarationDiff<N>,
+    /// The differences in the records.
+    records: DeclarationDiff<N>,
+    /// The differences in the closures.
+    closures: DeclarationDiff<N>,
+    /// The differences in the functions.
+    functions: DeclarationDiff<N>,
+}
+
+impl<N: Network> ProgramDiff<N> {
+    /// Initializes a new program diff.
+    pub const fn new(
+        mappings: DeclarationDiff<N>,
+        structs: DeclarationDiff<N>,
+        records: DeclarationDiff<N>,
+        closures: DeclarationDiff<N>,
+        functions: DeclarationDiff<N>,
+    ) -> Self {
+        Self { mappings, structs, records, closures, functions }
+    }
+
+    /// Returns the differences in the mappings.
+    pub const fn mappings(&self) -> &DeclarationDiff<N> {
+        &self.mappings
+    }
+
+    /// Returns the differences in the structs.
+    pub const fn structs(&self) -> &DeclarationDiff<N> {
+        &self.structs
+    }
+
+    /// Returns the differences in the records.
+    pub const fn records(&self) -> &DeclarationDiff<N> {
+        &self.records
+    }
+
+    /// Returns the differences in the closures.
+    pub const fn closures(&self) -> &DeclarationDiff<N> {
+        &self.closures
+    }
+
+    /// Returns the differences in the functions.
+    pub const fn functions(&self) -> &DeclarationDiff<N> {
+        &self.functions
+    }
+
+    /// Returns `true` if the programs declare the same mappings, structs, records, closures, and functions.
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+            && self.structs.is_empty()
+            && self.records.is_empty()
+            && self.closures.is_empty()
+            && self.functions.is_empty()
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use indexmap::IndexSet;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgramSchema {
+    /// The opcodes permitted in closures, functions, and finalize scopes (i.e. `add`, `hash.bhp256`).
+    allowed_opcodes: IndexSet<String>,
+    /// The maximum depth of nested calls, across all closures and functions.
+    max_call_depth: usize,
+    /// Whether finalize scopes are permitted to consume randomness.
+    allow_randomness: bool,
+}
+
+impl ProgramSchema {
+    /// Initializes a new program schema.
+    pub fn new(allowed_opcodes: IndexSet<String>, max_call_depth: usize, allow_randomness: bool) -> Self {
+        Self { allowed_opcodes, max_call_depth, allow_randomness }
+    }
+
+    /// Returns the opcodes permitted in closures, functions, and finalize scopes.
+    pub const fn allowed_opcodes(&self) -> &IndexSet<String> {
+        &self.allowed_opcodes
+    }
+
+    /// Returns `true` if the given opcode is permitted.
+    pub fn is_allowed_opcode(&self, opcode: &str) -> bool {
+        self.allowed_opcodes.contains(opcode)
+    }
+
+    /// Returns the maximum depth of nested calls, across all closures and functions.
+    pub const fn max_call_depth(&self) -> usize {
+        self.max_call_depth
+    }
+
+    /// Returns `true` if finalize scopes are permitted to consume randomness.
+    pub const fn allow_randomness(&self) -> bool {
+        self.allow_randomness
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::{
+    network::prelude::*,
+    program::{Identifier, ProgramID},
+};
+
+/// A problem found by the structural checks of a program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProgramWarning<N: Network> {
+    /// The given struct refers to itself, either directly or through other structs.
+    CyclicStruct(Identifier<N>, String),
+    /// A register in the given closure or function is read before it is written.
+    ReadBeforeWrite(Identifier<N>, String),
+    /// The `async` instruction or future output of the given function is inconsistent with its finalize scope.
+    InconsistentFuture(Identifier<N>, String),
+    /// The given function makes more calls, including nested calls, than fit in a transaction.
+    CallLimitExceeded(Identifier<N>, usize),
+    /// The calls of the given function, including nested calls, could not be resolved.
+    UnresolvedCalls(Identifier<N>, String),
+    /// The given import is not used by the program.
+    UnusedImport(ProgramID<N>),
+}
+
+impl<N: Network> ProgramWarning<N> {
+    /// Returns `true` if the warning would cause the program to be rejected.
+    /// An unused import is harmless, while every other warning is an error.
+    pub const fn is_error(&self) -> bool {
+        !matches!(self, Self::UnusedImport(..))
+    }
+}
+
+impl<N: Network> Display for ProgramWarning<N> {
+    /// Prints the warning as a string.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::CyclicStruct(struct_name, error) => write!(f, "Cyclic struct '{struct_name}': {error}"),
+            Self::ReadBeforeWrite(function_name, error) => write!(f, "Read before write in '{function_name}': {error}"),
+            Self::InconsistentFuture(function_name, error) => {
+                write!(f, "Inconsistent future in '{function_name}': {error}")
+            }
+            Self::CallLimitExceeded(function_name, num_calls) => {
+                write!(f, "'{function_name}' makes {num_calls} calls, which is more than fit in a transaction")
+            }
+            Self::UnresolvedCalls(function_name, error) => {
+                write!(f, "Failed to resolve the calls of '{function_name}': {error}")
+            }
+            Self::UnusedImport(program_id) => write!(f, "Import '{program_id}' is not used"),
+        }
+    }
+}
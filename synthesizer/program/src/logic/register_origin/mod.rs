@@ -0,0 +1,41 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Operand;
+use console::{network::Network, program::Register};
+
+/// The origin of the value of an operand.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RegisterOrigin {
+    /// The value is supplied by the caller, as an input register, `self.signer`, or `self.caller`.
+    Input,
+    /// The value is computed by an instruction or command.
+    Computed,
+    /// The value is independent of the inputs, as a literal, program ID, or `block.height`.
+    Literal,
+}
+
+impl RegisterOrigin {
+    /// Returns the origin of the given operand, where `is_input` returns `true` if a register is an input register.
+    pub fn from_operand<N: Network>(operand: &Operand<N>, is_input: impl FnOnce(&Register<N>) -> bool) -> Self {
+        match operand {
+            Operand::Register(register) => match is_input(register) {
+                true => Self::Input,
+                false => Self::Computed,
+            },
+            Operand::Signer | Operand::Caller => Self::Input,
+            Operand::Literal(..) | Operand::ProgramID(..) | Operand::BlockHeight => Self::Literal,
+        }
+    }
+}
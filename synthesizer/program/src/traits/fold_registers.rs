@@ -0,0 +1,157 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    Operand,
+    RecordObserver,
+    RegisterOrigin,
+    RegistersLoad,
+    RegistersSigner,
+    RegistersStore,
+    StackMatches,
+    StackProgram,
+};
+use console::{
+    network::Network,
+    prelude::{anyhow, bail, ensure, Result},
+    program::{Identifier, Plaintext, PlaintextType, Register, Value},
+    types::{Address, Field},
+};
+
+/// The scratch registers used to fold an instruction whose operands are all literals.
+/// The registers hold the value of the single destination, and have no signer, caller, or transition view key.
+pub(crate) struct FoldRegisters<N: Network> {
+    /// The value assigned to the destination, if it is assigned.
+    destination: Option<Value<N>>,
+}
+
+impl<N: Network> FoldRegisters<N> {
+    /// Initializes new scratch registers, with an unassigned destination.
+    pub(crate) const fn new() -> Self {
+        Self { destination: None }
+    }
+
+    /// Returns the value assigned to the destination, if it is assigned.
+    pub(crate) fn into_destination(self) -> Option<Value<N>> {
+        self.destination
+    }
+}
+
+impl<N: Network> RegistersLoad<N> for FoldRegisters<N> {
+    /// Loads the value of a given operand, which must be a literal.
+    fn load(&self, _stack: &(impl StackMatches<N> + StackProgram<N>), operand: &Operand<N>) -> Result<Value<N>> {
+        match operand {
+            Operand::Literal(literal) => Ok(Value::Plaintext(Plaintext::from(literal.clone()))),
+            _ => bail!("Operand '{operand}' cannot be folded, as it is not a literal"),
+        }
+    }
+
+    /// Loads the value of a given operand, which must be a literal.
+    fn load_with_origin(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+    ) -> Result<(Value<N>, RegisterOrigin)> {
+        Ok((self.load(stack, operand)?, RegisterOrigin::Literal))
+    }
+
+    /// Returns the plaintext type of a given operand, which must be a literal.
+    fn operand_plaintext_type(
+        &self,
+        _stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+    ) -> Result<PlaintextType<N>> {
+        match operand {
+            Operand::Literal(literal) => Ok(PlaintextType::Literal(literal.to_type())),
+            _ => bail!("Operand '{operand}' cannot be folded, as it is not a literal"),
+        }
+    }
+}
+
+impl<N: Network> RegistersStore<N> for FoldRegisters<N> {
+    /// Assigns the given value to the destination, assuming the destination is not already assigned.
+    fn store(
+        &mut self,
+        _stack: &(impl StackMatches<N> + StackProgram<N>),
+        register: &Register<N>,
+        stack_value: Value<N>,
+    ) -> Result<()> {
+        ensure!(self.destination.is_none(), "Register '{register}' already exists");
+        self.destination = Some(stack_value);
+        Ok(())
+    }
+
+    /// Assigns the given value to the destination, assuming the destination is not already assigned,
+    /// and returns a reference to the stored value.
+    fn store_and_return(
+        &mut self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        register: &Register<N>,
+        stack_value: Value<N>,
+    ) -> Result<&Value<N>> {
+        self.store(stack, register, stack_value)?;
+        self.destination.as_ref().ok_or_else(|| anyhow!("'{register}' does not exist"))
+    }
+
+    /// Records are never stored when folding, so the observer is never invoked.
+    fn set_record_observer(&mut self, _observer: RecordObserver<N>) {}
+
+    /// The scratch registers hold a single value, so there is no storage to reclaim.
+    fn compact(&mut self) {}
+
+    /// Halts, as the scratch registers hold a single value.
+    fn swap_registers(&mut self, _stack: &(impl StackMatches<N> + StackProgram<N>), a: u64, b: u64) -> Result<()> {
+        bail!("Cannot swap 'r{a}' and 'r{b}' when folding")
+    }
+
+    /// Clears the destination.
+    fn reset(&mut self) {
+        self.destination = None;
+    }
+
+    /// Halts, as the scratch registers do not belong to a function.
+    fn assert_outputs_complete(
+        &self,
+        _stack: &(impl StackMatches<N> + StackProgram<N>),
+        function_name: &Identifier<N>,
+    ) -> Result<()> {
+        bail!("Cannot check the outputs of '{function_name}' when folding")
+    }
+}
+
+impl<N: Network> RegistersSigner<N> for FoldRegisters<N> {
+    /// Halts, as the scratch registers do not have a signer.
+    fn signer(&self) -> Result<Address<N>> {
+        bail!("The signer is not available when folding")
+    }
+
+    /// Ignores the signer, as the scratch registers do not have a signer.
+    fn set_signer(&mut self, _signer: Address<N>) {}
+
+    /// Halts, as the scratch registers do not have a caller.
+    fn caller(&self) -> Result<Address<N>> {
+        bail!("The caller is not available when folding")
+    }
+
+    /// Ignores the caller, as the scratch registers do not have a caller.
+    fn set_caller(&mut self, _caller: Address<N>) {}
+
+    /// Halts, as the scratch registers do not have a transition view key.
+    fn tvk(&self) -> Result<Field<N>> {
+        bail!("The transition view key is not available when folding")
+    }
+
+    /// Ignores the transition view key, as the scratch registers do not have a transition view key.
+    fn set_tvk(&mut self, _tvk: Field<N>) {}
+}
@@ -0,0 +1,125 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::{
+    network::Network,
+    prelude::{bail, ensure, Result},
+    program::{Literal, Plaintext, Value},
+    types::{Address, Field, Group, Scalar},
+};
+
+/// A type that can be converted from a single loaded value.
+pub trait FromValue<N: Network>: Sized {
+    /// Converts the given value into `Self`.
+    fn from_value(value: Value<N>) -> Result<Self>;
+}
+
+/// A type that can be converted from a fixed number of loaded values, such as a tuple of literals.
+pub trait FromValues<N: Network>: Sized {
+    /// The number of values expected by the conversion.
+    const NUM_VALUES: usize;
+
+    /// Converts the given values into `Self`, in order.
+    fn from_values(values: Vec<Value<N>>) -> Result<Self>;
+}
+
+impl<N: Network> FromValue<N> for Value<N> {
+    /// Returns the value as is.
+    #[inline]
+    fn from_value(value: Value<N>) -> Result<Self> {
+        Ok(value)
+    }
+}
+
+impl<N: Network> FromValue<N> for Plaintext<N> {
+    /// Returns the plaintext of the given value.
+    #[inline]
+    fn from_value(value: Value<N>) -> Result<Self> {
+        match value {
+            Value::Plaintext(plaintext) => Ok(plaintext),
+            Value::Record(..) | Value::Future(..) => bail!("Expected a plaintext, found '{value}'"),
+        }
+    }
+}
+
+impl<N: Network> FromValue<N> for Literal<N> {
+    /// Returns the literal of the given value.
+    #[inline]
+    fn from_value(value: Value<N>) -> Result<Self> {
+        match value {
+            Value::Plaintext(Plaintext::Literal(literal, ..)) => Ok(literal),
+            _ => bail!("Expected a literal, found '{value}'"),
+        }
+    }
+}
+
+/// Implements `FromValue` for the type wrapped by the given literal variant.
+macro_rules! impl_from_value_for_literal {
+    ($variant:ident => $type:ty, $name:literal, |$literal:ident| $convert:expr) => {
+        impl<N: Network> FromValue<N> for $type {
+            #[doc = concat!("Returns the ", $name, " of the given value.")]
+            #[inline]
+            fn from_value(value: Value<N>) -> Result<Self> {
+                match Literal::from_value(value)? {
+                    Literal::$variant($literal) => Ok($convert),
+                    literal => bail!("Expected {}, found '{literal}'", $name),
+                }
+            }
+        }
+    };
+}
+
+impl_from_value_for_literal!(Address => Address<N>, "an address", |address| address);
+impl_from_value_for_literal!(Boolean => bool, "a boolean", |boolean| *boolean);
+impl_from_value_for_literal!(Field => Field<N>, "a field", |field| field);
+impl_from_value_for_literal!(Group => Group<N>, "a group", |group| group);
+impl_from_value_for_literal!(Scalar => Scalar<N>, "a scalar", |scalar| scalar);
+impl_from_value_for_literal!(I8 => i8, "an i8", |integer| *integer);
+impl_from_value_for_literal!(I16 => i16, "an i16", |integer| *integer);
+impl_from_value_for_literal!(I32 => i32, "an i32", |integer| *integer);
+impl_from_value_for_literal!(I64 => i64, "an i64", |integer| *integer);
+impl_from_value_for_literal!(I128 => i128, "an i128", |integer| *integer);
+impl_from_value_for_literal!(U8 => u8, "a u8", |integer| *integer);
+impl_from_value_for_literal!(U16 => u16, "a u16", |integer| *integer);
+impl_from_value_for_literal!(U32 => u32, "a u32", |integer| *integer);
+impl_from_value_for_literal!(U64 => u64, "a u64", |integer| *integer);
+impl_from_value_for_literal!(U128 => u128, "a u128", |integer| *integer);
+
+/// Implements `FromValues` for the tuple of the given element types.
+macro_rules! impl_from_values_for_tuple {
+    ($num_values:literal: $($element:ident),+) => {
+        impl<N: Network, $($element: FromValue<N>),+> FromValues<N> for ($($element,)+) {
+            const NUM_VALUES: usize = $num_values;
+
+            /// Converts each of the given values into the element of the tuple at the same position.
+            #[inline]
+            fn from_values(values: Vec<Value<N>>) -> Result<Self> {
+                // Ensure the number of values matches the number of elements.
+                ensure!(
+                    values.len() == Self::NUM_VALUES,
+                    "Expected {} values, found {}",
+                    Self::NUM_VALUES,
+                    values.len()
+                );
+                let mut values = values.into_iter();
+                Ok(($($element::from_value(values.next().expect("The number of values was checked"))?,)+))
+            }
+        }
+    };
+}
+
+impl_from_values_for_tuple!(1: A);
+impl_from_values_for_tuple!(2: A, B);
+impl_from_values_for_tuple!(3: A, B, C);
+impl_from_values_for_tuple!(4: A, B, C, D);
@@ -18,6 +18,12 @@ pub use command::*;
 mod finalize_store;
 pub use finalize_store::*;
 
+mod fold_registers;
+pub(crate) use fold_registers::*;
+
+mod from_values;
+pub use from_values::*;
+
 mod instruction;
 pub use instruction::*;
 
@@ -12,11 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{FinalizeGlobalState, Function, Operand, Program};
+use crate::{
+    CallSite,
+    EntryVisibility,
+    FinalizeGlobalState,
+    FoldRegisters,
+    FromValues,
+    Function,
+    Instruction,
+    Operand,
+    Program,
+    ProgramComplexity,
+    ProgramDiff,
+    ProgramSchema,
+    ProgramWarning,
+    RegisterOrigin,
+};
 use console::{
     network::Network,
-    prelude::{bail, Result},
+    prelude::{anyhow, bail, FromStr, Result, ToBits},
     program::{
+        Argument,
         Future,
         Identifier,
         Literal,
@@ -28,16 +44,31 @@ use console::{
         RecordType,
         Register,
         RegisterType,
+        Request,
+        Response,
         Value,
         ValueType,
     },
-    types::{Address, Field},
+    types::{Address, Field, Group, Scalar},
 };
 
+use indexmap::{IndexMap, IndexSet};
+use std::{collections::BTreeSet, ops::Range};
+
+/// The observer that is invoked with every record stored in the registers.
+/// The observer is `Send`, so that it can be shared with the registers of nested calls through the execution options.
+pub type RecordObserver<N> = Box<dyn FnMut(&Record<N, Plaintext<N>>) + Send>;
+
+/// The layout of a record type, as the name and visibility of each entry, in declaration order.
+pub type RecordTypeLayout<N> = Vec<(Identifier<N>, EntryVisibility)>;
+
 pub trait StackMatches<N: Network> {
     /// Checks that the given value matches the layout of the value type.
     fn matches_value_type(&self, value: &Value<N>, value_type: &ValueType<N>) -> Result<()>;
 
+    /// Checks that the given values match the layouts of the value types, in order.
+    fn matches_value_types(&self, values: &[Value<N>], value_types: &[ValueType<N>]) -> Result<()>;
+
     /// Checks that the given stack value matches the layout of the register type.
     fn matches_register_type(&self, stack_value: &Value<N>, register_type: &RegisterType<N>) -> Result<()>;
 
@@ -47,11 +78,102 @@ pub trait StackMatches<N: Network> {
     /// Checks that the given record matches the layout of the record type.
     fn matches_record(&self, record: &Record<N, Plaintext<N>>, record_name: &Identifier<N>) -> Result<()>;
 
+    /// Checks that the given entry of the record matches the layout of the plaintext type.
+    fn matches_record_entry(
+        &self,
+        record: &Record<N, Plaintext<N>>,
+        entry: &Identifier<N>,
+        expected: &PlaintextType<N>,
+    ) -> Result<()>;
+
     /// Checks that the given plaintext matches the layout of the plaintext type.
     fn matches_plaintext(&self, plaintext: &Plaintext<N>, plaintext_type: &PlaintextType<N>) -> Result<()>;
 
     /// Checks that the given future matches the layout of the future type.
     fn matches_future(&self, future: &Future<N>, locator: &Locator<N>) -> Result<()>;
+
+    /// Checks that the given future matches the finalize signature of the given callee,
+    /// as declared in the program of the callee.
+    fn matches_future_call(&self, future: &Future<N>, callee: &Locator<N>) -> Result<()>;
+
+    /// Checks that the given key and value match the declared key and value types of the given mapping.
+    fn matches_mapping_entry(
+        &self,
+        mapping_name: &Identifier<N>,
+        key: &Plaintext<N>,
+        value: &Plaintext<N>,
+    ) -> Result<()>;
+
+    /// Checks that the given default of a `get.or_use` command matches the declared value type of the given mapping.
+    fn validate_or_use_default(&self, mapping_name: &Identifier<N>, default: &Plaintext<N>) -> Result<()>;
+
+    /// Checks that the inputs of the given request match the declared input types of the function.
+    fn matches_request_inputs(&self, request: &Request<N>, function_name: &Identifier<N>) -> Result<()>;
+
+    /// Checks that the outputs of the given response match the declared output types of the function.
+    fn matches_response_outputs(&self, response: &Response<N>, function_name: &Identifier<N>) -> Result<()>;
+
+    /// Returns the canonical form of the given value.
+    ///
+    /// Canonicalization discards the cached bit representations of the value and its nested members.
+    /// No other transformation is applied: members are not reordered, and arrays, structs,
+    /// and literals are never converted into one another, as they are distinct values.
+    fn canonicalize_value(&self, value: Value<N>) -> Result<Value<N>>;
+
+    /// Returns a hash of the given value, which is the same for equal values regardless of how they were constructed.
+    fn value_hash(&self, value: &Value<N>) -> Result<Field<N>>;
+
+    /// Returns a JSON schema describing the given value type, including its kind, visibility,
+    /// and the layout of any nested members, elements, or record entries.
+    fn value_type_schema(&self, value_type: &ValueType<N>) -> serde_json::Value;
+
+    /// Checks that the given JSON is structurally compatible with the given value type, without constructing the value.
+    fn json_matches_value_type(&self, json: &serde_json::Value, value_type: &ValueType<N>) -> Result<()>;
+
+    /// Returns the least general value type that both given value types are compatible with.
+    ///
+    /// As there is no subtyping between value types, two value types only unify if they describe the same type.
+    /// A record and an external record that refers to the same record in this program unify to the record.
+    /// Value types that differ in visibility do not unify, as visibility determines how a value is committed to.
+    fn unify_value_types(&self, a: &ValueType<N>, b: &ValueType<N>) -> Result<ValueType<N>>;
+
+    /// Returns `true` if the given value types have the same underlying structure, ignoring their visibility.
+    fn types_structurally_equal(&self, a: &ValueType<N>, b: &ValueType<N>) -> bool;
+
+    /// Returns a human-readable rendering of the given value, using the struct and record names,
+    /// member names, and entry names declared in the given value type.
+    fn display_value(&self, value: &Value<N>, value_type: &ValueType<N>) -> Result<String>;
+
+    /// Checks that the given plaintext is an array with no duplicate elements.
+    fn assert_array_elements_unique(&self, plaintext: &Plaintext<N>) -> Result<()>;
+
+    /// Returns the plaintext of the given entry in the given record, such as its `amount`.
+    fn extract_record_field(&self, record: &Record<N, Plaintext<N>>, field: &Identifier<N>) -> Result<Plaintext<N>>;
+
+    /// Checks that the given circuit value ejects to a value of the same type as the given console value.
+    fn assert_type_agreement<A: circuit::Aleo<Network = N>>(
+        &self,
+        value: &Value<N>,
+        circuit_value: &circuit::Value<A>,
+    ) -> Result<()>;
+
+    /// Checks that the given record is a `credits.aleo/credits` record, and returns its amount of microcredits.
+    fn matches_credits_record(&self, record: &Record<N, Plaintext<N>>) -> Result<u64>;
+
+    /// Checks that the given literal is an address, whose group element is on the curve and in the prime-order subgroup.
+    fn matches_valid_address(&self, literal: &Literal<N>) -> Result<()>;
+
+    /// Checks that the given plaintext is a nested array whose length at each level matches the given dimensions,
+    /// where the first dimension is the length of the outermost array.
+    fn matches_array_dims(&self, plaintext: &Plaintext<N>, dims: &[u32]) -> Result<()>;
+
+    /// Checks that the provided members of the given plaintext match the layout of the given struct.
+    fn matches_partial_struct(
+        &self,
+        plaintext: &Plaintext<N>,
+        struct_name: &Identifier<N>,
+        provided: &IndexSet<Identifier<N>>,
+    ) -> Result<()>;
 }
 
 pub trait StackProgram<N: Network> {
@@ -61,6 +183,18 @@ pub trait StackProgram<N: Network> {
     /// Returns the program ID.
     fn program_id(&self) -> &ProgramID<N>;
 
+    /// Returns `true` if the given program ID is the program ID of this stack.
+    #[inline]
+    fn is_current_program(&self, program_id: &ProgramID<N>) -> bool {
+        self.program_id() == program_id
+    }
+
+    /// Returns `true` if the given program ID is **not** the program ID of this stack.
+    #[inline]
+    fn is_external(&self, program_id: &ProgramID<N>) -> bool {
+        !self.is_current_program(program_id)
+    }
+
     /// Returns `true` if the stack contains the external record.
     fn contains_external_record(&self, locator: &Locator<N>) -> bool;
 
@@ -73,6 +207,9 @@ pub trait StackProgram<N: Network> {
     /// Returns `true` if the stack contains the external record.
     fn get_external_record(&self, locator: &Locator<N>) -> Result<&RecordType<N>>;
 
+    /// Returns the record type for the given record name, if it is declared in this program.
+    fn get_record_type(&self, record_name: &Identifier<N>) -> Result<RecordType<N>>;
+
     /// Returns the function with the given function name.
     fn get_function(&self, function_name: &Identifier<N>) -> Result<Function<N>>;
 
@@ -81,6 +218,135 @@ pub trait StackProgram<N: Network> {
 
     /// Returns the expected number of calls for the given function name.
     fn get_number_of_calls(&self, function_name: &Identifier<N>) -> Result<usize>;
+
+    /// Returns the input registers of the given function name, paired with their register types.
+    fn input_register_types(&self, function_name: &Identifier<N>) -> Result<Vec<(Register<N>, RegisterType<N>)>>;
+
+    /// Returns the output operands of the given function name, paired with their register types.
+    fn output_register_types(&self, function_name: &Identifier<N>) -> Result<Vec<(Operand<N>, RegisterType<N>)>>;
+
+    /// Returns the maximum depth of nested calls, starting from the given closure or function.
+    fn get_call_depth(&self, name: &Identifier<N>) -> Result<usize>;
+
+    /// Returns a summary of the size and structure of the program.
+    fn complexity_summary(&self) -> Result<ProgramComplexity>;
+
+    /// Returns the closures and functions in this program that are reachable from the given entry, including itself.
+    fn reachable_resources(&self, entry: &Identifier<N>) -> Result<IndexSet<Identifier<N>>>;
+
+    /// Checks that no register in the given function is read before it is written.
+    /// Note that stack initialization already rejects such programs; this check reports the offending instruction index.
+    fn verify_no_read_before_write(&self, function_name: &Identifier<N>) -> Result<()>;
+
+    /// Returns the byte range of the given function in the source text of the program, if the function exists.
+    /// As the original source is not retained, the range is relative to the canonical source, `program.to_string()`.
+    fn function_source_span(&self, function_name: &Identifier<N>) -> Option<(usize, usize)>;
+
+    /// Returns every literal used as an operand in the closures, functions, and finalize scopes of the program.
+    fn constant_literals(&self) -> Vec<Literal<N>>;
+
+    /// Returns the `(caller, callee)` pairs for every call to a closure or function in this program.
+    fn call_graph(&self) -> Vec<(Identifier<N>, Identifier<N>)>;
+
+    /// Returns the `(caller, callee)` pairs for every call to a function in an external program.
+    fn external_call_graph(&self) -> Vec<(Identifier<N>, Locator<N>)>;
+
+    /// Checks that no struct in the program refers to itself, either directly or through other structs.
+    fn verify_acyclic_structs(&self) -> Result<()>;
+
+    /// Returns `true` if the finalize scope of the given function consumes randomness, such as `rand.chacha`.
+    fn finalize_uses_randomness(&self, function_name: &Identifier<N>) -> Result<bool>;
+
+    /// Returns the number of instructions in each function, paired with the number of commands in its finalize scope.
+    fn instruction_counts(&self) -> IndexMap<Identifier<N>, (usize, usize)>;
+
+    /// Returns the programs reachable through the imports of this program, in dependency order.
+    fn transitive_imports(&self) -> Result<Vec<ProgramID<N>>>;
+
+    /// Checks that the program only uses the opcodes, call depth, and randomness permitted by the given schema.
+    fn validate_against_schema(&self, schema: &ProgramSchema) -> Result<()>;
+
+    /// Returns, for each output of the given function, the indices of the inputs that flow into it.
+    fn output_input_dependencies(&self, function_name: &Identifier<N>) -> Result<Vec<BTreeSet<usize>>>;
+
+    /// Returns a hash of the semantic content of the program, which is independent of its source formatting.
+    fn semantic_hash(&self) -> Result<Field<N>>;
+
+    /// Returns the external record types consumed by the inputs of the given function.
+    fn external_record_dependencies(&self, function_name: &Identifier<N>) -> Result<Vec<Locator<N>>>;
+
+    /// Returns the identifiers of the proving and verifying keys required to execute the given function,
+    /// including the keys of the functions it calls.
+    fn required_key_ids(&self, function_name: &Identifier<N>) -> Result<Vec<Field<N>>>;
+
+    /// Returns the call sites in the given function, with the callee, inputs, and outputs of each call.
+    fn call_sites(&self, function_name: &Identifier<N>) -> Result<Vec<CallSite<N>>>;
+
+    /// Returns the mappings, structs, records, closures, and functions that differ between this program and the given program.
+    fn diff(&self, other: &Self) -> ProgramDiff<N>;
+
+    /// Returns the register locators of the given closure or function, grouped by their register type.
+    fn registers_by_type(&self, function_name: &Identifier<N>) -> Result<IndexMap<RegisterType<N>, Vec<u64>>>;
+
+    /// Runs the structural checks of the program, and returns the warnings and errors they report.
+    fn verify_program(&self) -> Result<Vec<ProgramWarning<N>>>;
+
+    /// Returns the number of bytes in the serialized form of one key-value pair of the given mapping.
+    fn mapping_entry_size(&self, mapping_name: &Identifier<N>) -> Result<u64>;
+
+    /// Checks that no mapping key in the finalize scope of the given function is derived from `rand.chacha`.
+    fn verify_mapping_keys_deterministic(&self, function_name: &Identifier<N>) -> Result<()>;
+
+    /// Returns the entries of every record type in the program, including the owner, with their visibility.
+    fn record_type_layouts(&self) -> Result<IndexMap<Identifier<N>, RecordTypeLayout<N>>>;
+
+    /// Returns the maximum number of registers of the given function that are live at the same time.
+    fn peak_live_registers(&self, function_name: &Identifier<N>) -> Result<usize>;
+
+    /// Returns the index of the instruction in the given function that assigns the given register,
+    /// or `None` if the register is an input register.
+    fn register_definition(&self, function_name: &Identifier<N>, locator: u64) -> Result<Option<usize>>;
+
+    /// Returns the declared type of the given member of the given struct.
+    #[inline]
+    fn struct_member_type(&self, struct_name: &Identifier<N>, member: &Identifier<N>) -> Result<PlaintextType<N>> {
+        self.struct_member_path_type(struct_name, std::slice::from_ref(member))
+    }
+
+    /// Returns the declared type of the member at the given path in the given struct, where each member
+    /// of the path is a member of the struct before it, as in the access `r0.a.b`.
+    fn struct_member_path_type(&self, struct_name: &Identifier<N>, path: &[Identifier<N>]) -> Result<PlaintextType<N>>;
+
+    /// Returns the locators of the external finalize scopes awaited by the finalize scope of the given function,
+    /// in the order they are awaited.
+    fn finalize_awaits(&self, function_name: &Identifier<N>) -> Result<Vec<Locator<N>>>;
+
+    /// Returns the number of bytes in the serialization of the instructions of the given function.
+    fn function_bytecode_size(&self, function_name: &Identifier<N>) -> Result<usize>;
+
+    /// Returns the struct and record names that are defined in more than one program in the import closure,
+    /// along with the programs that define each name.
+    #[allow(clippy::type_complexity)]
+    fn detect_name_collisions(&self) -> Result<Vec<(Identifier<N>, Vec<ProgramID<N>>)>>;
+
+    /// Returns the index and the two operands of each `assert.eq` and `assert.neq` instruction in the given function.
+    #[allow(clippy::type_complexity)]
+    fn assertions(&self, function_name: &Identifier<N>) -> Result<Vec<(usize, Operand<N>, Operand<N>)>>;
+
+    /// Returns the index and the cost in microcredits of each command in the finalize scope of the given function.
+    fn instruction_costs(&self, function_name: &Identifier<N>) -> Result<Vec<(usize, u64)>>;
+}
+
+pub trait StackProgramMut<N: Network> {
+    /// Substitutes the operand at the given index of the given instruction in the given function,
+    /// ensuring the new operand has the same register type as the operand it replaces.
+    fn substitute_operand(
+        &mut self,
+        function_name: &Identifier<N>,
+        instruction_index: usize,
+        operand_index: usize,
+        new_operand: Operand<N>,
+    ) -> Result<()>;
 }
 
 pub trait FinalizeRegistersState<N: Network> {
@@ -92,6 +358,30 @@ pub trait FinalizeRegistersState<N: Network> {
 
     /// Returns the function name for the finalize scope.
     fn function_name(&self) -> &Identifier<N>;
+
+    /// Returns the seed for the `rand` commands, if one is set in place of the seed from the global state.
+    fn randomness_seed(&self) -> Option<Field<N>>;
+
+    /// Returns `None` if no mapping snapshot is set, and otherwise the value of the given key in the given mapping
+    /// of the snapshot, if it exists.
+    fn mapping_snapshot_value(
+        &self,
+        program_id: &ProgramID<N>,
+        mapping_name: &Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Option<Option<Plaintext<N>>>;
+
+    /// Returns `true` if a mapping snapshot is set, in which case the mapping commands use it in place of the store.
+    fn has_mapping_snapshot(&self) -> bool;
+
+    /// Sets the value of the given key in the given mapping of the snapshot, or removes the key if the value is `None`.
+    fn update_mapping_snapshot(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        key: &Plaintext<N>,
+        value: Option<Plaintext<N>>,
+    ) -> Result<()>;
 }
 
 pub trait RegistersSigner<N: Network> {
@@ -112,6 +402,16 @@ pub trait RegistersSigner<N: Network> {
 
     /// Sets the transition view key.
     fn set_tvk(&mut self, tvk: Field<N>);
+
+    /// Returns the effective caller, which is the transition caller if it is set,
+    /// and otherwise the transition signer, as at the root of the call stack.
+    #[inline]
+    fn effective_caller(&self) -> Result<Address<N>> {
+        match self.caller() {
+            Ok(caller) => Ok(caller),
+            Err(_) => self.signer(),
+        }
+    }
 }
 
 pub trait RegistersSignerCircuit<N: Network, A: circuit::Aleo<Network = N>> {
@@ -142,6 +442,139 @@ pub trait RegistersLoad<N: Network> {
     /// In the case of register members, this method should halt if the member is not found.
     fn load(&self, stack: &(impl StackMatches<N> + StackProgram<N>), operand: &Operand<N>) -> Result<Value<N>>;
 
+    /// Loads the value of a given operand, and returns it with whether it is an input, computed, or literal value.
+    ///
+    /// # Errors
+    /// This method should halt if the register locator is not found.
+    /// In the case of register members, this method should halt if the member is not found.
+    fn load_with_origin(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+    ) -> Result<(Value<N>, RegisterOrigin)>;
+
+    /// Returns the plaintext type of a given operand, without loading its value.
+    ///
+    /// # Errors
+    /// This method should halt if the register type is not found.
+    /// This method should halt if the operand is not a plaintext.
+    fn operand_plaintext_type(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+    ) -> Result<PlaintextType<N>>;
+
+    /// Evaluates the operation with the given opcode on the given operands, if every operand is a literal,
+    /// and returns the folded constant. If any operand is not a literal, this method returns `None`.
+    ///
+    /// # Errors
+    /// This method should halt if the opcode is not an operation with a single destination, such as `add`.
+    /// This method should halt if the operation fails on the given literals, including if it overflows or divides by zero.
+    #[inline]
+    fn try_fold_operands(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        opcode: &str,
+        operands: &[Operand<N>],
+    ) -> Result<Option<Value<N>>> {
+        // Ensure every operand is a literal.
+        if !operands.iter().all(|operand| matches!(operand, Operand::Literal(..))) {
+            return Ok(None);
+        }
+        // Construct the instruction, with a placeholder destination.
+        let operands = operands.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let instruction = Instruction::<N>::from_str(&format!("{opcode} {} into r0;", operands.join(" ")))?;
+        // Ensure the instruction does not invoke another closure or function.
+        if matches!(instruction, Instruction::Call(..) | Instruction::Async(..)) {
+            bail!("Opcode '{opcode}' cannot be folded")
+        }
+        // Evaluate the instruction in scratch registers.
+        // As an operation that overflows or divides by zero halts, the halt is caught and returned as an error.
+        let mut registers = FoldRegisters::new();
+        let result =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| instruction.evaluate(stack, &mut registers)));
+        match result {
+            Ok(result) => result?,
+            Err(payload) => {
+                // Retrieve the halt message, if there is one.
+                let message = match (payload.downcast_ref::<String>(), payload.downcast_ref::<&str>()) {
+                    (Some(message), _) => message.clone(),
+                    (None, Some(message)) => message.to_string(),
+                    (None, None) => "unknown error".to_string(),
+                };
+                bail!("Failed to fold '{instruction}': {message}")
+            }
+        }
+        match registers.into_destination() {
+            Some(value) => Ok(Some(value)),
+            None => bail!("Opcode '{opcode}' did not produce a value"),
+        }
+    }
+
+    /// Returns the constant value of a given operand, without consulting the registers.
+    /// This method returns `Some` for literals, and `None` for all other operands, including registers.
+    #[inline]
+    fn const_value(&self, operand: &Operand<N>) -> Option<Value<N>> {
+        match operand {
+            Operand::Literal(literal) => Some(Value::Plaintext(Plaintext::from(literal.clone()))),
+            _ => None,
+        }
+    }
+
+    /// Loads the value of a given operand, after relocating its register by the given offset.
+    /// Operands that are not registers, such as literals, are loaded as-is.
+    ///
+    /// # Errors
+    /// This method should halt if the relocated register locator overflows.
+    /// This method should halt if the register locator is not found.
+    /// In the case of register members, this method should halt if the member is not found.
+    #[inline]
+    fn load_with_offset(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+        offset: u64,
+    ) -> Result<Value<N>> {
+        // Relocates the given locator by the offset.
+        let relocate = |locator: &u64| {
+            locator
+                .checked_add(offset)
+                .ok_or_else(|| anyhow!("Register locator '{locator}' overflows with offset {offset}"))
+        };
+        match operand {
+            Operand::Register(Register::Locator(locator)) => {
+                self.load(stack, &Operand::Register(Register::Locator(relocate(locator)?)))
+            }
+            Operand::Register(Register::Access(locator, path)) => {
+                self.load(stack, &Operand::Register(Register::Access(relocate(locator)?, path.clone())))
+            }
+            _ => self.load(stack, operand),
+        }
+    }
+
+    /// Checks that every input register of the given function is assigned, and still matches its declared input type.
+    /// As registers are write-once, this guards against corruption of the register storage itself.
+    ///
+    /// # Errors
+    /// This method should halt if the given function does not exist.
+    /// This method should halt if an input register is not assigned, or does not match its input type.
+    #[inline]
+    fn assert_input_registers_unchanged(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        function_name: &Identifier<N>,
+    ) -> Result<()> {
+        for input in stack.get_function_ref(function_name)?.inputs() {
+            // Load the input register.
+            let value = self.load(stack, &Operand::Register(input.register().clone()))?;
+            // Ensure the value matches the input type.
+            if let Err(error) = stack.matches_value_type(&value, input.value_type()) {
+                bail!("Input register '{}' of '{function_name}' was modified: {error}", input.register())
+            }
+        }
+        Ok(())
+    }
+
     /// Loads the literal of a given operand.
     ///
     /// # Errors
@@ -182,6 +615,340 @@ pub trait RegistersLoad<N: Network> {
             Value::Record(..) | Value::Future(..) => bail!("Operand must be a plaintext"),
         }
     }
+
+    /// Loads the elements in the given range of the array of a given operand.
+    /// As the array is loaded as a whole, the cost of the load is that of the whole array, regardless of the range.
+    ///
+    /// # Errors
+    /// This method should halt if the given operand is not an array.
+    /// This method should halt if the given range is out of bounds for the array.
+    /// This method should halt if the register locator is not found.
+    /// In the case of register members, this method should halt if the member is not found.
+    #[inline]
+    fn load_array_slice(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+        range: Range<usize>,
+    ) -> Result<Vec<Plaintext<N>>> {
+        match self.load_plaintext(stack, operand)? {
+            Plaintext::Array(mut elements, ..) => {
+                // Ensure the range is within the bounds of the array.
+                if range.start > range.end || range.end > elements.len() {
+                    bail!(
+                        "Range {}..{} is out of bounds for an array of length {}",
+                        range.start,
+                        range.end,
+                        elements.len()
+                    )
+                }
+                // Move the elements in the range out of the loaded array.
+                // Note that the whole array is cloned when it is loaded, and the elements outside the range are dropped.
+                Ok(elements.drain(range).collect())
+            }
+            plaintext => bail!("Operand must be an array, found '{plaintext}'"),
+        }
+    }
+
+    /// Loads the given members of the struct in the given register, in the order of the given members.
+    /// The struct is resolved once for all of the members.
+    ///
+    /// # Errors
+    /// This method should halt if the given register is not a struct.
+    /// This method should halt if any of the given members is not found, and reports the first such member.
+    /// This method should halt if the register locator is not found.
+    #[inline]
+    fn load_struct_members(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        register: &Register<N>,
+        members: &[Identifier<N>],
+    ) -> Result<Vec<Plaintext<N>>> {
+        match self.load_plaintext(stack, &Operand::Register(register.clone()))? {
+            Plaintext::Struct(struct_, ..) => members
+                .iter()
+                .map(|member| {
+                    struct_
+                        .get(member)
+                        .cloned()
+                        .ok_or_else(|| anyhow!("Member '{member}' is not found in '{register}'"))
+                })
+                .collect(),
+            plaintext => bail!("Register '{register}' must be a struct, found '{plaintext}'"),
+        }
+    }
+
+    /// Checks that the number of given operands matches the number of operands expected by the given opcode.
+    /// Opcodes with a variable number of operands, such as `call` and `cast`, accept any number of operands.
+    ///
+    /// # Errors
+    /// This method should halt if the opcode is not an instruction opcode.
+    /// This method should halt if the number of operands does not match the number expected by the opcode.
+    #[inline]
+    fn validate_operand_arity(&self, opcode: &str, operands: &[Operand<N>]) -> Result<()> {
+        // Retrieve the number of operands expected by the opcode.
+        let Some(index) = Instruction::<N>::OPCODES.iter().position(|candidate| **candidate == opcode) else {
+            bail!("'{opcode}' is not an instruction opcode")
+        };
+        // Ensure the number of operands matches, if the opcode expects a fixed number of operands.
+        if let Some(expected) = Instruction::<N>::NUM_OPERANDS[index] {
+            if operands.len() != expected {
+                bail!("Opcode '{opcode}' expects {expected} operands, found {}", operands.len())
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads the arguments of the future in the given operand, in the order they were captured.
+    ///
+    /// # Errors
+    /// This method should halt if the given operand is not a future.
+    /// This method should halt if the register locator is not found.
+    /// In the case of register members, this method should halt if the member is not found.
+    #[inline]
+    fn load_future_arguments(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+    ) -> Result<Vec<Value<N>>> {
+        match self.load(stack, operand)? {
+            Value::Future(future) => Ok(future
+                .arguments()
+                .iter()
+                .map(|argument| match argument {
+                    Argument::Plaintext(plaintext) => Value::Plaintext(plaintext.clone()),
+                    Argument::Future(future) => Value::Future(future.clone()),
+                })
+                .collect()),
+            value => bail!("Operand '{operand}' must be a future, found '{value}'"),
+        }
+    }
+
+    /// Loads the little-endian bits of a given literal operand, extended or truncated to the given width.
+    /// Literals are serialized without a type header, so that their bits are the bits of the literal itself.
+    /// Signed integers are sign-extended, while all other literals are padded with zeros.
+    ///
+    /// # Errors
+    /// This method should halt if the given operand is not a literal.
+    /// This method should halt if truncating the bits to the given width would change the value,
+    /// i.e. if a discarded bit differs from the sign bit of a signed integer, or is set for any other literal.
+    /// This method should halt if the register locator is not found.
+    /// In the case of register members, this method should halt if the member is not found.
+    #[inline]
+    fn load_bits_le_width(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+        width: usize,
+    ) -> Result<Vec<bool>> {
+        let (mut bits, is_signed) = match self.load(stack, operand)? {
+            Value::Plaintext(Plaintext::Literal(literal, ..)) => {
+                let is_signed = matches!(
+                    literal,
+                    Literal::I8(..) | Literal::I16(..) | Literal::I32(..) | Literal::I64(..) | Literal::I128(..)
+                );
+                (literal.to_bits_le(), is_signed)
+            }
+            value => bail!("Operand '{operand}' must be a literal, found '{value}'"),
+        };
+        match width < bits.len() {
+            true => {
+                // Retrieve the sign bit of the truncated bits, which is unset for unsigned literals.
+                let sign = is_signed && width > 0 && bits[width - 1];
+                // Ensure every discarded bit matches the sign bit, so that the truncation is lossless.
+                if bits.iter().skip(width).any(|bit| *bit != sign) {
+                    bail!("Operand '{operand}' does not fit in {width} bits")
+                }
+                bits.truncate(width);
+            }
+            false => {
+                // Extend the bits with the sign bit, which is unset for unsigned literals.
+                let sign = is_signed && bits.last().copied().unwrap_or(false);
+                bits.resize(width, sign);
+            }
+        }
+        Ok(bits)
+    }
+
+    /// Loads the group element of a given operand.
+    ///
+    /// # Errors
+    /// This method should halt if the given operand is not a group element.
+    /// This method should halt if the register locator is not found.
+    /// In the case of register members, this method should halt if the member is not found.
+    #[inline]
+    fn load_group(&self, stack: &(impl StackMatches<N> + StackProgram<N>), operand: &Operand<N>) -> Result<Group<N>> {
+        match self.load_literal(stack, operand)? {
+            Literal::Group(group) => Ok(group),
+            literal => bail!("Operand must be a group element, found '{literal}'"),
+        }
+    }
+
+    /// Loads the scalar of a given operand.
+    ///
+    /// # Errors
+    /// This method should halt if the given operand is not a scalar.
+    /// This method should halt if the register locator is not found.
+    /// In the case of register members, this method should halt if the member is not found.
+    #[inline]
+    fn load_scalar(&self, stack: &(impl StackMatches<N> + StackProgram<N>), operand: &Operand<N>) -> Result<Scalar<N>> {
+        match self.load_literal(stack, operand)? {
+            Literal::Scalar(scalar) => Ok(scalar),
+            literal => bail!("Operand must be a scalar, found '{literal}'"),
+        }
+    }
+
+    /// Loads the address of the program, as referenced by the `Operand::ProgramID` of the current program.
+    ///
+    /// # Errors
+    /// This method should halt if the program ID cannot be converted into an address.
+    #[inline]
+    fn load_program_address(&self, stack: &(impl StackMatches<N> + StackProgram<N>)) -> Result<Address<N>> {
+        match self.load_literal(stack, &Operand::ProgramID(*stack.program_id()))? {
+            Literal::Address(address) => Ok(address),
+            literal => bail!("Program ID must resolve to an address, found '{literal}'"),
+        }
+    }
+
+    /// Loads the integer literal of a given operand, ensuring it fits within the given number of bits.
+    /// Signed integers are checked against the two's complement range of the given number of bits.
+    ///
+    /// # Errors
+    /// This method should halt if the given operand is not an integer.
+    /// This method should halt if the integer does not fit within the given number of bits.
+    /// This method should halt if the register locator is not found.
+    /// In the case of register members, this method should halt if the member is not found.
+    #[inline]
+    fn load_literal_within(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+        bits: usize,
+    ) -> Result<Literal<N>> {
+        // Returns `true` if the given unsigned integer fits within the given number of bits.
+        let fits_unsigned = |value: u128| bits >= 128 || value >> bits == 0;
+        // Returns `true` if the given signed integer fits within the given number of bits.
+        let fits_signed = |value: i128| match bits {
+            0 => value == 0,
+            bits if bits >= 128 => true,
+            bits => (-(1i128 << (bits - 1))..(1i128 << (bits - 1))).contains(&value),
+        };
+
+        // Load the literal.
+        let literal = self.load_literal(stack, operand)?;
+        let fits = match &literal {
+            Literal::I8(integer) => fits_signed(**integer as i128),
+            Literal::I16(integer) => fits_signed(**integer as i128),
+            Literal::I32(integer) => fits_signed(**integer as i128),
+            Literal::I64(integer) => fits_signed(**integer as i128),
+            Literal::I128(integer) => fits_signed(**integer),
+            Literal::U8(integer) => fits_unsigned(**integer as u128),
+            Literal::U16(integer) => fits_unsigned(**integer as u128),
+            Literal::U32(integer) => fits_unsigned(**integer as u128),
+            Literal::U64(integer) => fits_unsigned(**integer as u128),
+            Literal::U128(integer) => fits_unsigned(**integer),
+            literal => bail!("Operand must be an integer, found '{literal}'"),
+        };
+        match fits {
+            true => Ok(literal),
+            false => bail!("Integer '{literal}' does not fit within {bits} bits"),
+        }
+    }
+
+    /// Loads the value of a given operand, adapted to the given register type.
+    ///
+    /// A record may be adapted to an external record type that refers to the current program,
+    /// as both describe the same record layout. No other coercions are considered sound.
+    ///
+    /// # Errors
+    /// This method should halt if the value does not match, and cannot be adapted to, the register type.
+    /// This method should halt if the register locator is not found.
+    /// In the case of register members, this method should halt if the member is not found.
+    #[inline]
+    fn load_as(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+        register_type: &RegisterType<N>,
+    ) -> Result<Value<N>> {
+        self.load_as_logged(stack, operand, register_type).map(|(value, _)| value)
+    }
+
+    /// Loads the value of a given operand, adapted to the given register type,
+    /// along with a flag indicating whether the value was coerced to the register type.
+    ///
+    /// # Errors
+    /// This method should halt if the value does not match, and cannot be adapted to, the register type.
+    /// This method should halt if the register locator is not found.
+    /// In the case of register members, this method should halt if the member is not found.
+    #[inline]
+    fn load_as_logged(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+        register_type: &RegisterType<N>,
+    ) -> Result<(Value<N>, bool)> {
+        // Load the value.
+        let value = self.load(stack, operand)?;
+        // If the value already matches the register type, return it as is.
+        if stack.matches_register_type(&value, register_type).is_ok() {
+            return Ok((value, false));
+        }
+        // Otherwise, attempt to adapt the value to the register type.
+        match (&value, register_type) {
+            // A record is a valid external record of the current program, if it matches the local record type.
+            (Value::Record(record), RegisterType::ExternalRecord(locator))
+                if stack.is_current_program(locator.program_id()) =>
+            {
+                stack.matches_record(record, locator.resource())?
+            }
+            _ => bail!("Operand '{operand}' cannot be coerced to type '{register_type}'"),
+        }
+        Ok((value, true))
+    }
+
+    /// Loads the values of the given operands, and converts them into the given tuple type, in order.
+    /// For example, `let (a, b): (u64, u64) = registers.load_tuple(stack, operands)?;`.
+    ///
+    /// # Errors
+    /// This method should halt if the number of operands does not match the number of elements in the tuple.
+    /// This method should halt if any of the values cannot be converted into its element type.
+    /// This method should halt if the register locator is not found.
+    /// In the case of register members, this method should halt if the member is not found.
+    #[inline]
+    fn load_tuple<T: FromValues<N>>(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operands: &[Operand<N>],
+    ) -> Result<T> {
+        // Ensure the number of operands matches the number of elements in the tuple.
+        if operands.len() != T::NUM_VALUES {
+            bail!("Expected {} operands, found {}", T::NUM_VALUES, operands.len())
+        }
+        // Load the values, and convert them into the tuple.
+        let values = operands.iter().map(|operand| self.load(stack, operand)).collect::<Result<Vec<_>>>()?;
+        T::from_values(values)
+    }
+
+    /// Loads the value of a given operand, and checks that it is equal to the expected value.
+    ///
+    /// # Errors
+    /// This method should halt if the loaded value is not equal to the expected value, and reports both values.
+    /// This method should halt if the register locator is not found.
+    /// In the case of register members, this method should halt if the member is not found.
+    #[inline]
+    fn load_assert_eq(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+        expected: &Value<N>,
+    ) -> Result<()> {
+        let value = self.load(stack, operand)?;
+        match &value == expected {
+            true => Ok(()),
+            false => bail!("Operand '{operand}' is '{value}', expected '{expected}'"),
+        }
+    }
 }
 
 pub trait RegistersLoadCircuit<N: Network, A: circuit::Aleo<Network = N>> {
@@ -196,6 +963,27 @@ pub trait RegistersLoadCircuit<N: Network, A: circuit::Aleo<Network = N>> {
         operand: &Operand<N>,
     ) -> Result<circuit::Value<A>>;
 
+    /// Loads the value of a given operand, sharing the bit decomposition of a plaintext register across loads.
+    ///
+    /// The bit decomposition is computed once on the stored register, and reused by every subsequent load of
+    /// the register (or its members) in these registers. As this changes the synthesized constraints, callers
+    /// must use it consistently when synthesizing the circuit keys and when proving.
+    ///
+    /// The decomposition is computed eagerly, on the first shared load of the register, and covers the whole
+    /// stored plaintext. This adds the constraints of decomposing every member of the register (e.g. a field
+    /// member is decomposed into its bits), even if only one member is loaded, or if the loaded value is never
+    /// decomposed. It should only be used for operands of bit-level instructions, such as the hash and commit
+    /// instructions, which decompose the whole value anyway.
+    ///
+    /// # Errors
+    /// This method should halt if the register locator is not found.
+    /// In the case of register members, this method should halt if the member is not found.
+    fn load_circuit_shared(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+    ) -> Result<circuit::Value<A>>;
+
     /// Loads the literal of a given operand.
     ///
     /// # Errors
@@ -234,6 +1022,27 @@ pub trait RegistersLoadCircuit<N: Network, A: circuit::Aleo<Network = N>> {
             circuit::Value::Record(..) | circuit::Value::Future(..) => bail!("Operand must be a plaintext"),
         }
     }
+
+    /// Loads the value of a given operand as a constant circuit value, which adds no constraints.
+    /// Only operands known when the program is compiled, namely literals and program IDs, are supported.
+    ///
+    /// # Errors
+    /// This method should halt if the given operand is a register, or is not known when the program is compiled.
+    #[inline]
+    fn load_constant_circuit(
+        &self,
+        _stack: &(impl StackMatches<N> + StackProgram<N>),
+        operand: &Operand<N>,
+    ) -> Result<circuit::Value<A>> {
+        use circuit::Inject;
+
+        let literal = match operand {
+            Operand::Literal(literal) => literal.clone(),
+            Operand::ProgramID(program_id) => Literal::Address(program_id.to_address()?),
+            _ => bail!("Operand '{operand}' is not a constant"),
+        };
+        Ok(circuit::Value::Plaintext(circuit::Plaintext::from(circuit::Literal::constant(literal))))
+    }
 }
 
 pub trait RegistersStore<N: Network> {
@@ -250,6 +1059,74 @@ pub trait RegistersStore<N: Network> {
         stack_value: Value<N>,
     ) -> Result<()>;
 
+    /// Assigns the given value to the given register, assuming the register is not already assigned,
+    /// and returns a reference to the stored value.
+    ///
+    /// # Errors
+    /// This method should halt if the given register is a register member.
+    /// This method should halt if the given register is an input register.
+    /// This method should halt if the register is already used.
+    fn store_and_return(
+        &mut self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        register: &Register<N>,
+        stack_value: Value<N>,
+    ) -> Result<&Value<N>>;
+
+    /// Sets the observer that is invoked with every record stored in the registers, after the record is stored.
+    fn set_record_observer(&mut self, observer: RecordObserver<N>);
+
+    /// Shrinks the storage of the registers to fit the assigned registers.
+    /// This method does not change the value of any assigned register.
+    fn compact(&mut self);
+
+    /// Exchanges the values assigned to the given registers, which must be assigned destination registers of the same type.
+    ///
+    /// # Errors
+    /// This method should halt if either register is an input register.
+    /// This method should halt if either register is not assigned.
+    /// This method should halt if the registers are not of the same type.
+    fn swap_registers(&mut self, stack: &(impl StackMatches<N> + StackProgram<N>), a: u64, b: u64) -> Result<()>;
+
+    /// Clears every assigned register, including the input registers, so the registers can be reused.
+    /// The storage of the registers is retained, and the step budget, if any, is restored to its configured value.
+    /// The registers are not reinitialized, so the context they were created with, such as the call stack, is kept.
+    fn reset(&mut self);
+
+    /// Checks that every output register of the given function is assigned.
+    /// The outputs may be assigned in any order, as a function may output its registers in a different order
+    /// than it assigns them.
+    ///
+    /// # Errors
+    /// This method should halt if an output register is not assigned.
+    fn assert_outputs_complete(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        function_name: &Identifier<N>,
+    ) -> Result<()>;
+
+    /// Assigns the given value to the given register, assuming the register is not already assigned,
+    /// and returns a commitment to the stored value, computed as the BHP-1024 hash of its bits.
+    /// As the commitment is not randomized, it binds to the value but does not hide it.
+    ///
+    /// # Errors
+    /// This method should halt if the given register is a register member.
+    /// This method should halt if the given register is an input register.
+    /// This method should halt if the register is already used.
+    #[inline]
+    fn store_with_commitment(
+        &mut self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        register: &Register<N>,
+        stack_value: Value<N>,
+    ) -> Result<Field<N>> {
+        // Compute the commitment to the value.
+        let commitment = N::hash_bhp1024(&stack_value.to_bits_le())?;
+        // Store the value.
+        self.store(stack, register, stack_value)?;
+        Ok(commitment)
+    }
+
     /// Assigns the given literal to the given register, assuming the register is not already assigned.
     ///
     /// # Errors